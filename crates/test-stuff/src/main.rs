@@ -1,5 +1,20 @@
-use vine_parse::parse_expression;
+use vine_diagnostics::{Format, SourceFile};
+use vine_parse::parse_module;
 
 fn main() {
-    println!("{:#?}", parse_expression("if True then 10 else False"))
+    let file = SourceFile::new(
+        "language-design/finite.vn",
+        include_str!("../../../language-design/finite.vn"),
+    );
+
+    match parse_module(file.source()) {
+        Ok(module) => println!("{module:#?}"),
+        Err(diagnostics) => {
+            let format = Format::for_stderr();
+
+            for diagnostic in diagnostics {
+                eprintln!("{}", format.render(&diagnostic, &file));
+            }
+        }
+    }
 }
@@ -0,0 +1,84 @@
+use std::{fs, process::ExitCode};
+
+use crate::{check::analyze, read, report, usage};
+
+/// The backends named in the roadmap that do not exist yet.
+const PLANNED: &[&str] = &["js", "vvm", "llvm", "wasm", "jvm"];
+
+/// Runs `vine build`, which compiles a file for a backend.
+///
+/// The only backend is `mir`, which writes the control flow graphs of
+/// [`vine_mir`] as text after validating them.
+pub fn run(mut arguments: impl Iterator<Item = String>) -> ExitCode {
+    let mut target = None;
+    let mut output = None;
+    let mut path = None;
+
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--target" if target.is_none() => match arguments.next() {
+                Some(name) => target = Some(name),
+                None => return usage(),
+            },
+            "-o" if output.is_none() => match arguments.next() {
+                Some(name) => output = Some(name),
+                None => return usage(),
+            },
+            _ if !argument.starts_with('-') && path.is_none() => path = Some(argument),
+            _ => return usage(),
+        }
+    }
+
+    match target.as_deref() {
+        Some("mir") => {}
+        Some(target) if PLANNED.contains(&target) => {
+            eprintln!("error: the `{target}` backend is not implemented yet");
+            return ExitCode::FAILURE;
+        }
+        Some(target) => {
+            eprintln!("error: unknown backend `{target}`; the only backend is `mir`");
+            return ExitCode::from(2);
+        }
+        None => return usage(),
+    }
+
+    let Some(file) = read(path.as_deref()) else {
+        return ExitCode::FAILURE;
+    };
+
+    let Some((module, resolution)) = analyze(&file) else {
+        return ExitCode::FAILURE;
+    };
+
+    let (items, diagnostics) = vine_parse::lower::lower_module(&module);
+
+    if report(&diagnostics, &file) {
+        return ExitCode::FAILURE;
+    }
+
+    let program = vine_mir::lower(&items, &resolution);
+
+    let errors = program.validate();
+
+    if !errors.is_empty() {
+        for error in errors {
+            eprintln!("error: invalid MIR: {error}");
+        }
+
+        return ExitCode::FAILURE;
+    }
+
+    let text = program.to_string();
+
+    match output {
+        Some(output) => {
+            if let Err(error) = fs::write(&output, text) {
+                eprintln!("error: cannot write {output}: {error}");
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{text}"),
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,59 @@
+use std::process::ExitCode;
+
+use vine_diagnostics::SourceFile;
+use vine_parse::{ast::Module, lower::check_patterns, parse_module};
+use vine_resolve::Resolution;
+
+use crate::{read, report, usage};
+
+/// Runs `vine check`, which reports the errors and warnings in files.
+pub fn run(arguments: impl Iterator<Item = String>) -> ExitCode {
+    let paths = arguments.collect::<Vec<_>>();
+
+    if paths.iter().any(|path| path.starts_with('-')) {
+        return usage();
+    }
+
+    let mut success = true;
+
+    if paths.is_empty() {
+        success = read(None).is_some_and(|file| analyze(&file).is_some());
+    }
+
+    for path in &paths {
+        success &= read(Some(path)).is_some_and(|file| analyze(&file).is_some());
+    }
+
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Parses, resolves and checks a file, reporting its diagnostics.
+/// Returns [`None`] if it has errors.
+pub fn analyze(file: &SourceFile) -> Option<(Module<'_>, Resolution<'_>)> {
+    let module = match parse_module(file.source()) {
+        Ok(module) => module,
+        Err(diagnostics) => {
+            report(&diagnostics, file);
+            return None;
+        }
+    };
+
+    let mut diagnostics = check_patterns(&module);
+    let (resolution, resolve_diagnostics) = vine_resolve::resolve(&module);
+    diagnostics.extend(resolve_diagnostics);
+
+    // Types are only meaningful if the names are resolved.
+    if !diagnostics.iter().any(|diagnostic| diagnostic.is_error()) {
+        diagnostics.extend(vine_check::check(&module, &resolution));
+    }
+
+    if report(&diagnostics, file) {
+        None
+    } else {
+        Some((module, resolution))
+    }
+}
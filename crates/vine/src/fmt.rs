@@ -0,0 +1,94 @@
+use std::{
+    fs,
+    io::{self, Read},
+    process::ExitCode,
+};
+
+use vine_diagnostics::{Format, SourceFile};
+
+use crate::usage;
+
+/// Runs `vine fmt`.
+///
+/// Files are formatted in place; without files, stdin is formatted to stdout.
+/// With `--check`, nothing is written and the command fails if any input
+/// is not formatted, which is meant for CI.
+pub fn run(arguments: impl Iterator<Item = String>) -> ExitCode {
+    let mut check = false;
+    let mut paths = Vec::new();
+
+    for argument in arguments {
+        match argument.as_str() {
+            "--check" => check = true,
+            _ if argument.starts_with('-') => return usage(),
+            _ => paths.push(argument),
+        }
+    }
+
+    let mut success = true;
+
+    if paths.is_empty() {
+        let mut source = String::new();
+
+        if let Err(error) = io::stdin().read_to_string(&mut source) {
+            eprintln!("error: cannot read stdin: {error}");
+            return ExitCode::FAILURE;
+        }
+
+        match format("<stdin>", &source) {
+            Some(output) if check && output != source => {
+                eprintln!("<stdin> is not formatted");
+                success = false;
+            }
+            Some(_) if check => {}
+            Some(output) => print!("{output}"),
+            None => success = false,
+        }
+    }
+
+    for path in paths {
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(error) => {
+                eprintln!("error: cannot read {path}: {error}");
+                success = false;
+                continue;
+            }
+        };
+
+        match format(&path, &source) {
+            Some(output) if output == source => {}
+            Some(_) if check => {
+                eprintln!("{path} is not formatted");
+                success = false;
+            }
+            Some(output) => {
+                if let Err(error) = fs::write(&path, output) {
+                    eprintln!("error: cannot write {path}: {error}");
+                    success = false;
+                }
+            }
+            None => success = false,
+        }
+    }
+
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Formats a source file, printing its syntax errors if there are any.
+fn format(name: &str, source: &str) -> Option<String> {
+    vine_fmt::format(source)
+        .map_err(|diagnostics| {
+            let file = SourceFile::new(name, source);
+            let format = Format::for_stderr();
+
+            for diagnostic in diagnostics {
+                eprintln!("{}", format.render(&diagnostic, &file));
+            }
+        })
+        .ok()
+}
@@ -0,0 +1,60 @@
+use std::process::ExitCode;
+
+use vine_lex::dump::{filtered_tokens, raw_tokens};
+use vine_parse::{Parser, dump};
+
+use crate::{read, report, single_path, usage};
+
+/// Runs `vine lex`, which prints the tokens of a file, one per line.
+///
+/// With `--raw`, the tokens are printed before filtering, including whitespace
+/// and comments.
+pub fn lex(arguments: impl Iterator<Item = String>) -> ExitCode {
+    let mut raw = false;
+    let mut path = None;
+
+    for argument in arguments {
+        match argument.as_str() {
+            "--raw" if !raw => raw = true,
+            _ if !argument.starts_with('-') && path.is_none() => path = Some(argument),
+            _ => return usage(),
+        }
+    }
+
+    let Some(file) = read(path.as_deref()) else {
+        return ExitCode::FAILURE;
+    };
+
+    if raw {
+        print!("{}", raw_tokens(file.source()));
+    } else {
+        print!("{}", filtered_tokens(file.source()));
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Runs `vine parse`, which prints the syntax tree of every item of a file.
+///
+/// With syntax errors, the tree recovered from them is printed as well, with
+/// `error` nodes where the errors are.
+pub fn parse(arguments: impl Iterator<Item = String>) -> ExitCode {
+    let path = match single_path(arguments) {
+        Ok(path) => path,
+        Err(code) => return code,
+    };
+
+    let Some(file) = read(path.as_deref()) else {
+        return ExitCode::FAILURE;
+    };
+
+    let mut parser = Parser::new(vine_lex::lex(file.source()));
+    let module = parser.parse_module();
+    print!("{}", dump::module(&module));
+
+    if report(&parser.into_diagnostics(), &file) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
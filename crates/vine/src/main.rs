@@ -0,0 +1,105 @@
+//! The `vine` command line tool.
+//!
+//! Exits with 0 on success, 1 if an input has errors or cannot be read or
+//! written, and 2 for invalid arguments.
+
+mod build;
+mod check;
+mod fmt;
+mod inspect;
+mod repl;
+mod run;
+#[cfg(test)]
+mod tests;
+
+use std::{
+    env, fs,
+    io::{self, Read},
+    process::ExitCode,
+};
+
+use vine_diagnostics::{Diagnostic, Format, SourceFile};
+
+const USAGE: &str = "\
+usage: vine <command> [arguments]
+
+Commands that take a file read stdin if it is omitted.
+
+commands:
+    lex [--raw] [file]                      print the tokens of a file, unfiltered with `--raw`
+    parse [file]                            print the syntax tree of a file as S-expressions
+    check [files...]                        report the errors in files
+    run [file]                              run a file and print the values of its expressions
+    build --target <backend> [-o out] [file]
+                                            compile a file; the only backend is `mir`
+    fmt [--check] [files...]                format files in place, or stdin to stdout
+    repl                                    evaluate expressions interactively";
+
+fn main() -> ExitCode {
+    let mut arguments = env::args().skip(1);
+
+    match arguments.next().as_deref() {
+        Some("lex") => inspect::lex(arguments),
+        Some("parse") => inspect::parse(arguments),
+        Some("check") => check::run(arguments),
+        Some("run") => {
+            let arguments = arguments.collect::<Vec<_>>();
+            vine_interpret::with_stack(|| run::run(arguments.into_iter()))
+        }
+        Some("build") => build::run(arguments),
+        Some("fmt") => fmt::run(arguments),
+        Some("repl") => {
+            let arguments = arguments.collect::<Vec<_>>();
+            vine_interpret::with_stack(|| repl::run(arguments.into_iter()))
+        }
+        _ => usage(),
+    }
+}
+
+/// Prints the usage and returns the exit code for invalid arguments.
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::from(2)
+}
+
+/// Returns the only argument, which must not be an option, if there is one.
+fn single_path(mut arguments: impl Iterator<Item = String>) -> Result<Option<String>, ExitCode> {
+    match (arguments.next(), arguments.next()) {
+        (None, _) => Ok(None),
+        (Some(path), None) if !path.starts_with('-') => Ok(Some(path)),
+        _ => Err(usage()),
+    }
+}
+
+/// Reads the file at `path`, or stdin without a path.
+fn read(path: Option<&str>) -> Option<SourceFile> {
+    let (name, source) = match path {
+        Some(path) => (path, fs::read_to_string(path)),
+        None => {
+            let mut source = String::new();
+            (
+                "<stdin>",
+                io::stdin().read_to_string(&mut source).map(|_| source),
+            )
+        }
+    };
+
+    match source {
+        Ok(source) => Some(SourceFile::new(name, source)),
+        Err(error) => {
+            eprintln!("error: cannot read {name}: {error}");
+            None
+        }
+    }
+}
+
+/// Prints the diagnostics to stderr and returns whether any of them is an error.
+fn report(diagnostics: &[Diagnostic], file: &SourceFile) -> bool {
+    let format = Format::for_stderr();
+
+    for diagnostic in diagnostics {
+        eprintln!("{}", format.render(diagnostic, file));
+    }
+
+    diagnostics.iter().any(Diagnostic::is_error)
+}
@@ -0,0 +1,585 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeSet,
+    io::{self, BufRead, Write},
+    process::ExitCode,
+};
+
+use parser_tools::Span;
+use vine_check::{Checker, Type, TypeTable};
+use vine_diagnostics::{Diagnostic, FileId, Format, SourceFile, SourceMap};
+use vine_interpret::Interpreter;
+use vine_lex::{
+    dump::{filtered_tokens, raw_tokens},
+    lex,
+};
+use vine_parse::{
+    OperatorTable, Parser,
+    ast::{Expression, Module},
+    dump,
+    lower::{Core, Pattern},
+    parse_module,
+};
+use vine_resolve::{BindingId, BindingKind, Resolution, Resolver};
+
+use crate::usage;
+
+const HELP: &str = "\
+Enter expressions to evaluate them and definitions to add them to the session.
+Input continues on the next line while it is incomplete or the last line is indented;
+an empty line ends it.
+
+:type <expression>    print the type of an expression without evaluating it
+:ast <input>          print the syntax tree of the input
+:tokens <input>       print the tokens of the input
+:tokens --raw <input> print the tokens of the input before filtering
+:help                 print this help
+:quit                 exit (or press Ctrl+D)";
+
+/// Runs `vine repl`.
+pub fn run(mut arguments: impl Iterator<Item = String>) -> ExitCode {
+    if arguments.next().is_some() {
+        return usage();
+    }
+
+    let history = History::default();
+    let mut session = Session::new(&history, Format::for_stderr());
+    let mut input = String::new();
+    let mut lines = io::stdin().lock().lines();
+
+    loop {
+        print!("{}", if input.is_empty() { "> " } else { "... " });
+        let _ = io::stdout().flush();
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(error)) => {
+                eprintln!("error: cannot read stdin: {error}");
+                return ExitCode::FAILURE;
+            }
+            None => {
+                println!();
+                return ExitCode::SUCCESS;
+            }
+        };
+
+        if input.is_empty() && line.trim().is_empty() {
+            continue;
+        }
+
+        if !input.is_empty() {
+            input.push('\n');
+        }
+
+        input.push_str(line.trim_end());
+
+        if !line.trim().is_empty() && !input.starts_with(':') && needs_more(&input) {
+            continue;
+        }
+
+        if input.trim() == ":quit" {
+            return ExitCode::SUCCESS;
+        }
+
+        let reply = session.submit(input.trim_end());
+        print!("{}", reply.output);
+        eprint!("{}", reply.errors);
+        input.clear();
+    }
+}
+
+/// Whether the input continues on the next line: it is incomplete, or it has
+/// multiple lines and the last one is indented, like the cases of a `match`.
+pub fn needs_more(input: &str) -> bool {
+    is_incomplete(input)
+        || input.contains('\n')
+            && input
+                .lines()
+                .last()
+                .is_some_and(|line| line.starts_with([' ', '\t']))
+}
+
+/// Whether the input has syntax errors at its end that more input could fix,
+/// like an unclosed `(`, a `match` without cases or an `if` without `then`.
+pub fn is_incomplete(input: &str) -> bool {
+    let mut parser = Parser::new(lex(input));
+    parser.parse_module();
+
+    let end = input.trim_end().len() as u32;
+
+    parser.diagnostics().iter().any(|diagnostic| {
+        diagnostic
+            .primary_range()
+            .is_some_and(|range| range.is_empty() && range.start >= end)
+    })
+}
+
+/// What the session prints for one input.
+#[derive(Debug, Default, PartialEq)]
+pub struct Reply {
+    pub output: String,
+    pub errors: String,
+}
+
+/// The inputs of a [`Session`]. The session refers to their sources and lowered
+/// items for as long as it lives, so they are kept outside of it.
+#[derive(Default)]
+pub struct History<'a> {
+    sources: RefCell<Vec<Box<str>>>,
+    items: RefCell<Vec<Box<[Span<Core<'a>>]>>>,
+}
+
+impl<'a> History<'a> {
+    fn source(&'a self, source: &str) -> &'a str {
+        push(&self.sources, source.into())
+    }
+
+    fn items(&'a self, items: Vec<Span<Core<'a>>>) -> &'a [Span<Core<'a>>] {
+        push(&self.items, items.into_boxed_slice())
+    }
+}
+
+/// Adds a value to a list that never removes values and returns a reference to it.
+fn push<T: ?Sized>(values: &RefCell<Vec<Box<T>>>, value: Box<T>) -> &T {
+    let mut values = values.borrow_mut();
+    values.push(value);
+    let value: *const T = &**values.last().unwrap();
+
+    // SAFETY: The value is behind a box, so it does not move when the list grows,
+    // and it lives as long as the list, which never removes it.
+    unsafe { &*value }
+}
+
+/// The state of a REPL session.
+///
+/// Each input is parsed, checked and evaluated on its own, against the definitions
+/// the session accepted before, so it may refer to them and use their custom
+/// operators. Names refer to their latest definition, so the definitions that refer
+/// to a name that an input defines again are checked and evaluated again as well.
+pub struct Session<'a> {
+    format: Format,
+    history: &'a History<'a>,
+
+    /// The sources of the inputs. Their ranges do not overlap, so the
+    /// resolution and the interpreter can hold the items of all inputs.
+    files: SourceMap,
+
+    /// The custom operators declared by the accepted definitions.
+    operators: OperatorTable<'a>,
+
+    /// The names of the accepted definitions.
+    resolver: Resolver<'a>,
+
+    /// The accepted definitions, in order.
+    definitions: Vec<Definition<'a>>,
+    interpreter: Interpreter<'a>,
+}
+
+/// An accepted definition or abstract declaration.
+struct Definition<'a> {
+    item: Span<Expression<'a>>,
+    names: Vec<Span<&'a str>>,
+    merge: bool,
+
+    /// The module-level names the definition refers to.
+    dependencies: BTreeSet<&'a str>,
+
+    /// The bindings of the names and their types.
+    types: Vec<(BindingId, Type<'a>)>,
+}
+
+/// A lowered item of an input.
+struct Item<'a> {
+    core: Span<Core<'a>>,
+    names: Vec<Span<&'a str>>,
+    merge: bool,
+}
+
+/// An input checked together with the accepted definitions it affects.
+struct Checked<'a> {
+    file: FileId,
+
+    /// The definitions that are checked again, followed by the items of the input.
+    module: Module<'a>,
+
+    /// The lowered items of the input.
+    items: Vec<Item<'a>>,
+    operators: OperatorTable<'a>,
+    resolver: Resolver<'a>,
+    types: TypeTable<'a>,
+
+    /// The indices of the definitions that the input replaces.
+    replaced: BTreeSet<usize>,
+
+    /// The indices of the definitions that are checked again.
+    rechecked: BTreeSet<usize>,
+}
+
+impl<'a> Session<'a> {
+    pub fn new(history: &'a History<'a>, format: Format) -> Self {
+        Self {
+            format,
+            history,
+            files: SourceMap::new(),
+            operators: OperatorTable::new(),
+            resolver: Resolver::new(),
+            definitions: Vec::new(),
+            interpreter: Interpreter::new(),
+        }
+    }
+
+    /// Evaluates an input or runs a meta-command.
+    pub fn submit(&mut self, input: &str) -> Reply {
+        let mut reply = Reply::default();
+
+        match input.split_once(char::is_whitespace).unwrap_or((input, "")) {
+            (":help", _) => reply.output = format!("{HELP}\n"),
+            (":type", expression) => self.print_type(expression.trim(), &mut reply),
+            (":ast", input) => match parse_module(input.trim()) {
+                Ok(module) => reply.output = dump::module(&module),
+                Err(diagnostics) => {
+                    let file = SourceFile::new("<input>", input.trim());
+
+                    for diagnostic in diagnostics {
+                        reply.errors += &self.format.render(&diagnostic, &file);
+                        reply.errors += "\n";
+                    }
+                }
+            },
+            (":tokens", input) => {
+                reply.output = match input.trim().strip_prefix("--raw") {
+                    Some(input) => raw_tokens(input.trim()),
+                    None => filtered_tokens(input.trim()),
+                }
+            }
+            (command, _) if command.starts_with(':') => {
+                reply.errors = format!("error: unknown command `{command}`; see `:help`\n");
+            }
+            _ => self.evaluate(input, &mut reply),
+        }
+
+        reply
+    }
+
+    fn evaluate(&mut self, input: &str, reply: &mut Reply) {
+        let Some(Checked {
+            file,
+            module,
+            items,
+            operators,
+            resolver,
+            types,
+            replaced,
+            rechecked,
+        }) = self.check(input, reply)
+        else {
+            return;
+        };
+        let resolution = resolver.resolution();
+
+        for index in &rechecked {
+            let definition = &mut self.definitions[*index];
+            definition.types = bound_types(&definition.names, resolution, &types);
+
+            for name in &definition.names {
+                self.interpreter.forget(name.value);
+            }
+        }
+
+        let mut index = 0;
+        self.definitions.retain(|_| {
+            index += 1;
+            !replaced.contains(&(index - 1))
+        });
+        self.operators = operators;
+
+        let (cores, items): (Vec<_>, Vec<_>) = items
+            .into_iter()
+            .map(|Item { core, names, merge }| (core, (names, merge)))
+            .unzip();
+        let parsed = module.items.into_iter().skip(rechecked.len());
+
+        for ((core, (names, merge)), item) in
+            self.history.items(cores).iter().zip(items).zip(parsed)
+        {
+            if !Interpreter::is_definition(core) {
+                match self.interpreter.evaluate(core) {
+                    Ok(value) => {
+                        reply.output += &match types.get(&core.range) {
+                            Some(ty) => format!("{value} : {ty}\n"),
+                            None => format!("{value}\n"),
+                        }
+                    }
+                    Err(error) => self.report(&[error.to_diagnostic()], file, reply),
+                }
+
+                continue;
+            }
+
+            self.interpreter.define(core);
+
+            if !matches!(core.value, Core::Abstract { .. }) {
+                for name in &names {
+                    match self.interpreter.global(name.value) {
+                        Some(Ok(value)) => {
+                            reply.output += &match types.get(&name.range) {
+                                Some(ty) => format!("{} = {value} : {ty}\n", name.value),
+                                None => format!("{} = {value}\n", name.value),
+                            }
+                        }
+                        Some(Err(error)) => self.report(&[error.to_diagnostic()], file, reply),
+                        None => {}
+                    }
+                }
+            }
+
+            self.definitions.push(Definition {
+                dependencies: dependencies(resolution, &item),
+                types: bound_types(&names, resolution, &types),
+                item,
+                names,
+                merge,
+            });
+        }
+
+        self.resolver = resolver;
+    }
+
+    fn print_type(&mut self, expression: &str, reply: &mut Reply) {
+        let Some(checked) = self.check(expression, reply) else {
+            return;
+        };
+
+        if let Some(item) = checked.module.items[checked.rechecked.len()..].last()
+            && let Some(ty) = checked.types.get(&item.range)
+        {
+            reply.output += &format!("{ty}\n");
+        }
+    }
+
+    /// Parses the input and checks it against the accepted definitions, together
+    /// with the definitions it affects. Reports the diagnostics of the input and
+    /// returns [`None`] if there are errors.
+    fn check(&mut self, input: &str, reply: &mut Reply) -> Option<Checked<'a>> {
+        let source = self.history.source(input);
+        let file = self.files.add(SourceFile::new("<repl>", input));
+        let start = self.files.file(file).start();
+
+        // The ranges of the input continue after the ranges of the earlier inputs.
+        let tokens = lex(source).map(|token| Span {
+            range: token.range.start + start..token.range.end + start,
+            ..token
+        });
+        let mut parser = Parser::with_operators(tokens, self.operators.clone());
+        let input = parser.parse_module();
+
+        if !parser.diagnostics().is_empty() {
+            self.report(parser.diagnostics(), file, reply);
+            return None;
+        }
+
+        let mut lower_diagnostics = Vec::new();
+        let items = lower_items(&input, &mut lower_diagnostics);
+        let (replaced, rechecked) = self.affected(&items);
+        let mut resolver = self.resolver.clone();
+
+        for index in replaced.iter().chain(&rechecked) {
+            for name in &self.definitions[*index].names {
+                resolver.undeclare(name.value);
+            }
+        }
+
+        let module = Module {
+            items: rechecked
+                .iter()
+                .map(|index| self.definitions[*index].item.clone())
+                .chain(input.items)
+                .collect(),
+        };
+        resolver.resolve_module(&module);
+        let mut diagnostics = resolver.take_diagnostics();
+
+        let mut checker = Checker::new(resolver.resolution());
+
+        for (index, definition) in self.definitions.iter().enumerate() {
+            if !replaced.contains(&index) && !rechecked.contains(&index) {
+                checker.declare(&definition.item, definition.types.iter().cloned());
+            }
+        }
+
+        checker.check_module(&module);
+        let (types, type_diagnostics) = checker.finish();
+        diagnostics.extend(type_diagnostics);
+
+        // Patterns are lowered by the checker as well, so their errors are only
+        // reported once when lowering finds no others.
+        if !diagnostics.iter().any(Diagnostic::is_error) {
+            diagnostics.extend(lower_diagnostics);
+        }
+
+        // The definitions that are checked again were accepted before,
+        // so only the diagnostics of the input are reported.
+        diagnostics.retain(|diagnostic| {
+            diagnostic
+                .primary_range()
+                .is_none_or(|range| range.start >= start)
+        });
+        self.report(&diagnostics, file, reply);
+
+        if diagnostics.iter().any(Diagnostic::is_error) {
+            return None;
+        }
+
+        Some(Checked {
+            file,
+            module,
+            items,
+            operators: parser.into_operators(),
+            resolver,
+            types,
+            replaced,
+            rechecked,
+        })
+    }
+
+    /// Returns the indices of the accepted definitions that the items replace and
+    /// of the ones to check again: those that add to the same `@merge` definitions
+    /// as the items, and those that refer to names the items or the other
+    /// definitions to check again define.
+    fn affected(&self, items: &[Item]) -> (BTreeSet<usize>, BTreeSet<usize>) {
+        let replaced = (0..self.definitions.len())
+            .filter(|index| {
+                items
+                    .iter()
+                    .any(|item| conflicts(&self.definitions[*index], item))
+            })
+            .collect::<BTreeSet<_>>();
+        let mut changed = items
+            .iter()
+            .flat_map(|item| &item.names)
+            .map(|name| name.value)
+            .collect::<BTreeSet<_>>();
+        let mut rechecked = BTreeSet::new();
+
+        loop {
+            let affected = self
+                .definitions
+                .iter()
+                .enumerate()
+                .filter(|(index, definition)| {
+                    !replaced.contains(index)
+                        && !rechecked.contains(index)
+                        && (definition
+                            .names
+                            .iter()
+                            .any(|name| changed.contains(name.value))
+                            || definition
+                                .dependencies
+                                .iter()
+                                .any(|name| changed.contains(name)))
+                })
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>();
+
+            if affected.is_empty() {
+                return (replaced, rechecked);
+            }
+
+            for index in affected {
+                changed.extend(self.definitions[index].names.iter().map(|name| name.value));
+                rechecked.insert(index);
+            }
+        }
+    }
+
+    /// Reports diagnostics in the input their primary range is in, or in `file`
+    /// if they have none, with positions relative to that input.
+    fn report(&self, diagnostics: &[Diagnostic], file: FileId, reply: &mut Reply) {
+        for diagnostic in diagnostics {
+            let file = diagnostic
+                .primary_range()
+                .and_then(|range| self.files.lookup(range.start))
+                .map_or(file, |(file, _)| file);
+            let file = self.files.file(file);
+            let start = file.start();
+
+            // Labels in other inputs cannot be shown with this one.
+            let mut diagnostic = diagnostic.clone();
+            diagnostic.labels.retain(|label| {
+                label.range.start >= start && label.range.end <= start + file.len()
+            });
+
+            for label in &mut diagnostic.labels {
+                label.range = label.range.start - start..label.range.end - start;
+            }
+
+            reply.errors += &self.format.render(&diagnostic, file);
+            reply.errors += "\n";
+        }
+    }
+}
+
+/// Lowers the items of the module.
+fn lower_items<'a>(module: &Module<'a>, diagnostics: &mut Vec<Diagnostic>) -> Vec<Item<'a>> {
+    module
+        .items
+        .iter()
+        .map(|item| {
+            let core = Core::lower(item, diagnostics);
+            let merge =
+                Core::attributes(&core).any(|attribute| attribute.value.name.value == "merge");
+
+            let names = match &Core::unattributed(&core).value {
+                Core::Definition { pattern, .. } => Pattern::bound_names(pattern),
+                Core::Abstract { name } => vec![name.clone()],
+                _ => Vec::new(),
+            };
+
+            Item { core, names, merge }
+        })
+        .collect()
+}
+
+/// Whether a new item replaces an accepted definition. `@merge` definitions
+/// are added to the previous ones instead.
+fn conflicts(definition: &Definition, item: &Item) -> bool {
+    !(definition.merge && item.merge)
+        && item.names.iter().any(|name| {
+            definition
+                .names
+                .iter()
+                .any(|other| other.value == name.value)
+        })
+}
+
+/// Returns the module-level names that the identifiers in the item refer to. The
+/// names of definitions in blocks are included as well, which only means that the
+/// item is checked again more often than needed.
+fn dependencies<'a>(resolution: &Resolution<'a>, item: &Span<Expression>) -> BTreeSet<&'a str> {
+    resolution
+        .identifiers_in(item.range.clone())
+        .map(|(_, id)| resolution.binding(id))
+        .filter(|binding| {
+            matches!(
+                binding.kind,
+                BindingKind::Definition | BindingKind::Abstract
+            )
+        })
+        .map(|binding| binding.name)
+        .collect()
+}
+
+/// Returns the bindings of the names and their types.
+fn bound_types<'a>(
+    names: &[Span<&'a str>],
+    resolution: &Resolution<'a>,
+    types: &TypeTable<'a>,
+) -> Vec<(BindingId, Type<'a>)> {
+    names
+        .iter()
+        .filter_map(|name| {
+            let id = resolution.binding_of(&name.range)?;
+            Some((id, types.get(&name.range)?.clone()))
+        })
+        .collect()
+}
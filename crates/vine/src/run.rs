@@ -0,0 +1,42 @@
+use std::process::ExitCode;
+
+use vine_parse::lower::lower_module;
+
+use crate::{check::analyze, read, report, single_path};
+
+/// Runs `vine run`, which checks a file, evaluates it and prints
+/// the values of its expressions that are not definitions.
+pub fn run(arguments: impl Iterator<Item = String>) -> ExitCode {
+    let path = match single_path(arguments) {
+        Ok(path) => path,
+        Err(code) => return code,
+    };
+
+    let Some(file) = read(path.as_deref()) else {
+        return ExitCode::FAILURE;
+    };
+
+    let Some((module, _)) = analyze(&file) else {
+        return ExitCode::FAILURE;
+    };
+
+    let (items, diagnostics) = lower_module(&module);
+
+    if report(&diagnostics, &file) {
+        return ExitCode::FAILURE;
+    }
+
+    match vine_interpret::run(&items) {
+        Ok(values) => {
+            for value in values {
+                println!("{value}");
+            }
+
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            report(&[error.to_diagnostic()], &file);
+            ExitCode::FAILURE
+        }
+    }
+}
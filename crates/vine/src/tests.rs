@@ -0,0 +1,128 @@
+#![allow(non_snake_case)]
+
+mod Session {
+    use vine_diagnostics::Format;
+
+    use crate::repl::*;
+
+    fn outputs(inputs: &[&str]) -> Vec<String> {
+        let history = History::default();
+        let mut session = Session::new(&history, Format::Plain);
+
+        inputs
+            .iter()
+            .map(|input| {
+                let reply = session.submit(input);
+                reply.output + &reply.errors
+            })
+            .collect()
+    }
+
+    #[test]
+    fn incomplete_input() {
+        assert!(is_incomplete("(1, 2"));
+        assert!(is_incomplete("f = function x =>"));
+        assert!(is_incomplete("match x"));
+        assert!(is_incomplete("if x"));
+        assert!(!is_incomplete("(1, 2)"));
+        assert!(!is_incomplete("1 +)"));
+
+        assert!(needs_more("match x\n    case 1 => 2"));
+        assert!(!needs_more("(1,\n2)"));
+    }
+
+    #[test]
+    fn submit() {
+        assert_eq!(
+            outputs(&[
+                "abstract Zero",
+                "abstract One",
+                "f = function x is set {Zero, One} => match x\n    case Zero => One\n    case One => Zero",
+                "f Zero",
+                ":type f",
+                "f = function x => x",
+                "f 1",
+            ]),
+            [
+                "",
+                "",
+                "f = function : function _ is Zero | One => One | Zero\n",
+                "One : One | Zero\n",
+                "function _ is Zero | One => One | Zero\n",
+                "f = function : function _ is Any => Any\n",
+                "1 : Any\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn submit_errors() {
+        let outputs = outputs(&["x = y", "x", "1 / 0", ":nothing"]);
+
+        assert!(
+            outputs[0].contains("cannot find `y` in this scope"),
+            "{}",
+            outputs[0]
+        );
+        assert!(
+            outputs[1].contains("cannot find `x` in this scope"),
+            "{}",
+            outputs[1]
+        );
+        assert!(outputs[2].contains("division by zero"), "{}", outputs[2]);
+        assert_eq!(
+            outputs[3],
+            "error: unknown command `:nothing`; see `:help`\n"
+        );
+    }
+
+    #[test]
+    fn submit_continuation() {
+        let outputs = outputs(&["x = 5", "- 1", "x", "y = if True then 1", "else 2", "y"]);
+
+        assert_eq!(outputs[1], "-1 : -1\n");
+        assert_eq!(outputs[2], "5 : 5\n");
+        assert!(outputs[4].starts_with("error"), "{}", outputs[4]);
+        assert_eq!(outputs[5], "1 : Any\n");
+    }
+
+    #[test]
+    fn submit_redefinition() {
+        assert_eq!(
+            outputs(&[
+                "f = function x => x + 1",
+                "y = f 1",
+                "z = (y, y)",
+                "f = function x => \"a\"",
+                "z",
+            ])[3..],
+            [
+                "f = function : function _ is Any => \"a\"\n",
+                "(\"a\", \"a\") : (\"a\", \"a\")\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn submit_positions() {
+        let outputs = outputs(&["a = 1", "b = 2", "a +\n  c", "1 / (a - 1)"]);
+
+        assert!(outputs[2].contains("<repl>:2:3"), "{}", outputs[2]);
+        assert!(outputs[3].contains("<repl>:1:1"), "{}", outputs[3]);
+    }
+
+    #[test]
+    fn meta_commands() {
+        let outputs = outputs(&[":tokens a = 1", ":tokens --raw a =1", ":ast -1"]);
+
+        assert_eq!(
+            outputs[0],
+            "0..1 Identifier \"a\"\n2..3 Equals \"=\"\n4..5 Number \"1\"\n"
+        );
+        assert_eq!(
+            outputs[1],
+            "0..1 IdentifierOrKeyword \"a\"\n1..2 Whitespace \" \"\n2..3 Equals \"=\"\n3..4 Number \"1\"\n"
+        );
+        assert_eq!(outputs[2], "(negate 0..2\n  (number 1 1..2))\n");
+    }
+}
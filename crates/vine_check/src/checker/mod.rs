@@ -0,0 +1,841 @@
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    iter,
+    ops::Range,
+};
+
+use parser_tools::Span;
+use vine_diagnostics::Diagnostic;
+use vine_parse::{
+    ast::{BinaryOperation, Expression, GroupKind, MatchCase, Module, UnaryOperation},
+    lower::{Literal, Pattern},
+};
+use vine_resolve::{BindingId, BindingKind, Resolution};
+
+use crate::{ErrorInfo, Primitive, Shape, Type, TypeTable};
+
+pub struct Checker<'a, 'source> {
+    resolution: &'a Resolution<'source>,
+
+    /// The left- and right-hand sides of module-level definitions.
+    /// Merged definitions have multiple.
+    definitions: BTreeMap<BindingId, Vec<Definition<'a, 'source>>>,
+
+    /// The types of the bindings that were checked so far.
+    bindings: BTreeMap<BindingId, Type<'source>>,
+
+    /// The definitions whose domain or type is being evaluated, to stop at cycles.
+    evaluating: BTreeSet<BindingId>,
+
+    /// The parameters without a domain whose type is being inferred.
+    inferring: BTreeSet<BindingId>,
+
+    /// The ranges of the uses of the parameters in [`Self::inferring`].
+    uses: BTreeMap<(u32, u32), BindingId>,
+    types: TypeTable<'source>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+type Definition<'a, 'source> = (&'a Span<Expression<'source>>, &'a Span<Expression<'source>>);
+
+/// Returns the sides of a module-level definition of a name.
+fn definition<'a, 'source>(item: &'a Span<Expression<'source>>) -> Option<Definition<'a, 'source>> {
+    match &Expression::unattributed(item).value {
+        Expression::Binary {
+            left,
+            operation: BinaryOperation::Definition,
+            right,
+        } if let Expression::Identifier(_) = left.value => Some((left, right)),
+        _ => None,
+    }
+}
+
+impl<'a, 'source> Checker<'a, 'source> {
+    pub fn new(resolution: &'a Resolution<'source>) -> Self {
+        Self {
+            resolution,
+            definitions: BTreeMap::new(),
+            bindings: BTreeMap::new(),
+            evaluating: BTreeSet::new(),
+            inferring: BTreeSet::new(),
+            uses: BTreeMap::new(),
+            types: TypeTable::default(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    pub const fn types(&self) -> &TypeTable<'source> {
+        &self.types
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Returns the diagnostics, ordered by their position in the source.
+    ///
+    /// Definitions are checked when they are first used, so the diagnostics
+    /// are not reported in source order.
+    pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.finish().1
+    }
+
+    /// Returns the computed types and the diagnostics, ordered by their position in the source.
+    pub fn finish(mut self) -> (TypeTable<'source>, Vec<Diagnostic>) {
+        self.diagnostics
+            .sort_by_key(|diagnostic| diagnostic.primary_range().map(|range| range.start));
+        (self.types, self.diagnostics)
+    }
+
+    /// Adds a module-level item that was checked before, like a definition of an
+    /// earlier input in the REPL, with the types of its bindings. The checked modules
+    /// can refer to it, but it is not checked again.
+    pub fn declare(
+        &mut self,
+        item: &'a Span<Expression<'source>>,
+        types: impl IntoIterator<Item = (BindingId, Type<'source>)>,
+    ) {
+        self.add_definition(item);
+        self.bindings.extend(types);
+    }
+
+    pub fn check_module(&mut self, module: &'a Module<'source>) {
+        for item in &module.items {
+            self.add_definition(item);
+        }
+
+        for item in &module.items {
+            match definition(item) {
+                Some((left, _)) if let Some(id) = self.resolution.binding_of(&left.range) => {
+                    self.definition(id);
+                }
+                _ => {
+                    self.check_expression(item);
+                }
+            }
+        }
+    }
+
+    /// Adds the item to [`Self::definitions`] if it is a definition of a name.
+    fn add_definition(&mut self, item: &'a Span<Expression<'source>>) {
+        if let Some((left, right)) = definition(item)
+            && let Some(id) = self.resolution.binding_of(&left.range)
+        {
+            self.definitions.entry(id).or_default().push((left, right));
+        }
+    }
+
+    /// Checks an expression and returns the type of its value.
+    pub fn check_expression(&mut self, expression: &Span<Expression<'source>>) -> Type<'source> {
+        let ty = self.expression(expression);
+        self.types.insert(&expression.range, ty.clone());
+        ty
+    }
+
+    fn expression(&mut self, expression: &Span<Expression<'source>>) -> Type<'source> {
+        match &expression.value {
+            Expression::Error => Type::any(),
+            Expression::Abstract { name } => {
+                let ty = self.identifier(&name.range);
+                self.types.insert(&name.range, ty.clone());
+                ty
+            }
+            Expression::Number(number) => Shape::Literal(Literal::Number(number.clone())).into(),
+            Expression::String(string) => Shape::Literal(Literal::String(string.clone())).into(),
+            Expression::Character(character) => {
+                Shape::Literal(Literal::Character(*character)).into()
+            }
+            Expression::Identifier(_) => self.identifier(&expression.range),
+            Expression::Grouped {
+                kind: GroupKind::Parenthesized,
+                expression: items,
+            } => self.group(items, Self::check_expression),
+            Expression::Grouped {
+                kind: GroupKind::Braced,
+                expression: items,
+            } => items
+                .iter()
+                .fold(Type::any(), |_, item| self.check_expression(item)),
+            Expression::Grouped {
+                kind: GroupKind::Set,
+                expression: items,
+            } => Shape::Set(items.iter().fold(Type::never(), |ty, item| {
+                let item = self.check_expression(item);
+                ty.union(&item)
+            }))
+            .into(),
+            Expression::Grouped {
+                expression: items, ..
+            } => {
+                for item in items {
+                    self.check_expression(item);
+                }
+
+                Type::any()
+            }
+            Expression::Unary {
+                operation: UnaryOperation::Negate,
+                inner,
+            } if let Expression::Number(number) = &inner.value => {
+                Shape::Literal(Literal::Number(-number)).into()
+            }
+            Expression::Unary { operation, inner } => {
+                let ty = self.check_expression(inner);
+                self.unary(*operation, (ty, inner.range.clone()))
+            }
+            Expression::Binary {
+                left,
+                operation: BinaryOperation::Definition,
+                right,
+            } => {
+                let ty = self.check_expression(right);
+                let pattern = Pattern::lower(left, &mut self.diagnostics);
+                self.bind(&pattern, ty);
+                Type::any()
+            }
+            Expression::Binary {
+                left,
+                operation: BinaryOperation::Access,
+                right,
+            } => {
+                let ty = self.check_expression(left);
+
+                let Expression::Identifier(name) = right.value else {
+                    return Type::any();
+                };
+
+                ty.field(name).unwrap_or_else(|| {
+                    self.report(ErrorInfo::NoField {
+                        range: right.range.clone(),
+                        name: name.into(),
+                        found: ty.to_string(),
+                    });
+                    Type::any()
+                })
+            }
+            Expression::Binary {
+                left,
+                operation,
+                right,
+            } => {
+                let left = (self.check_expression(left), left.range.clone());
+                let right = (self.check_expression(right), right.range.clone());
+                self.binary(*operation, left, right)
+            }
+            Expression::Infix { left, right, .. } => {
+                self.check_expression(left);
+                self.check_expression(right);
+                Type::any()
+            }
+            Expression::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                let ty = self.check_expression(condition);
+                let boolean = self.boolean();
+                self.expect(&ty, &boolean, &condition.range);
+
+                let then = self.check_expression(then);
+
+                match otherwise {
+                    Some(otherwise) => then.union(&self.check_expression(otherwise)),
+                    None => Type::any(),
+                }
+            }
+            Expression::Match {
+                on,
+                first_case,
+                other_cases,
+            } => self.check_match(on, iter::once(first_case).chain(other_cases)),
+            Expression::Call { function, argument } => {
+                let function = (self.check_expression(function), function.range.clone());
+                let argument = (self.check_expression(argument), argument.range.clone());
+                self.call(function, argument)
+            }
+            Expression::Function {
+                parameter_pattern,
+                parameter_domain,
+                body,
+            } => {
+                let pattern = Pattern::lower(parameter_pattern, &mut self.diagnostics);
+                let mut domain = self.pattern(&pattern);
+
+                if let Some(parameter_domain) = parameter_domain {
+                    domain = domain.intersection(&self.domain(parameter_domain));
+                    self.check_expression(parameter_domain);
+                }
+
+                self.bind(&pattern, domain.clone());
+
+                // Without a domain, the parameter is narrowed to the values its uses require.
+                let inferred = match pattern.value {
+                    Pattern::Binding(_) if parameter_domain.is_none() => self
+                        .resolution
+                        .binding_of(&pattern.range)
+                        .filter(|id| self.resolution.binding(*id).kind == BindingKind::Parameter),
+                    _ => None,
+                };
+
+                if let Some(id) = inferred {
+                    self.inferring.insert(id);
+                }
+
+                let codomain = self.check_expression(body);
+
+                if let Some(id) = inferred {
+                    self.inferring.remove(&id);
+                    domain = self.bindings[&id].clone();
+                    self.types.insert(&pattern.range, domain.clone());
+
+                    for ((start, end), _) in self.uses.extract_if(.., |_, use_id| *use_id == id) {
+                        self.types.insert(&(start..end), domain.clone());
+                    }
+                }
+
+                Type::function(domain, codomain)
+            }
+            Expression::Attributed {
+                expression: inner, ..
+            } => self.check_expression(inner),
+        }
+    }
+
+    /// Returns the type of a module-level definition, checking its right-hand sides
+    /// the first time. The type of merged function definitions is a single function
+    /// accepting the values of any of them.
+    fn definition(&mut self, id: BindingId) -> Type<'source> {
+        if let Some(ty) = self.bindings.get(&id) {
+            return ty.clone();
+        }
+
+        if !self.evaluating.insert(id) {
+            return Type::any();
+        }
+
+        let definitions = self.definitions.get(&id).cloned().unwrap_or_default();
+        let types = definitions
+            .iter()
+            .map(|(_, right)| self.check_expression(right))
+            .collect::<Vec<_>>();
+
+        let ty = if types.len() > 1
+            && let Some(functions) = types
+                .iter()
+                .flat_map(Type::shapes)
+                .map(|shape| match shape {
+                    Shape::Function { domain, codomain } => Some((domain, codomain)),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+        {
+            let (domain, codomain) = functions.into_iter().fold(
+                (Type::never(), Type::never()),
+                |(domain, codomain), (other_domain, other_codomain)| {
+                    (domain.union(other_domain), codomain.union(other_codomain))
+                },
+            );
+
+            Type::function(domain, codomain)
+        } else {
+            types
+                .iter()
+                .fold(Type::never(), |ty, other| ty.union(other))
+        };
+
+        self.evaluating.remove(&id);
+        self.bindings.insert(id, ty.clone());
+        self.types.insert_definition(id, ty.clone());
+
+        for (left, _) in definitions {
+            self.types.insert(&left.range, ty.clone());
+        }
+
+        ty
+    }
+
+    /// Returns the type of the value an identifier refers to.
+    fn identifier(&mut self, range: &Range<u32>) -> Type<'source> {
+        let Some(id) = self.resolution.binding_of(range) else {
+            return Type::any();
+        };
+
+        if self.inferring.contains(&id) {
+            self.uses.insert((range.start, range.end), id);
+        }
+
+        if let Some(ty) = self.bindings.get(&id) {
+            return ty.clone();
+        }
+
+        if let Some(atom) = self.atom(id) {
+            return atom;
+        }
+
+        match self.resolution.binding(id).kind {
+            BindingKind::Definition if self.definitions.contains_key(&id) => self.definition(id),
+            _ => Type::any(),
+        }
+    }
+
+    /// Checks the cases of a match and returns the union of their results.
+    ///
+    /// The cases must cover all values of `on` and each must be reachable.
+    /// If `on` is a parameter whose type is being inferred, it is narrowed to
+    /// the values the cases match instead.
+    fn check_match<'c>(
+        &mut self,
+        on: &Span<Expression<'source>>,
+        cases: impl Iterator<Item = &'c Span<MatchCase<'source>>>,
+    ) -> Type<'source>
+    where
+        'source: 'c,
+    {
+        let mut remaining = self.check_expression(on);
+        let mut covered = Type::never();
+        let mut result = Type::never();
+
+        for case in cases {
+            let pattern = Pattern::lower(&case.value.pattern, &mut self.diagnostics);
+            let mut matched = self.pattern(&pattern);
+
+            if let Some(domain) = &case.value.domain {
+                matched = matched.intersection(&self.domain(domain));
+                self.check_expression(domain);
+            }
+
+            let reached = remaining.intersection(&matched);
+            covered = covered.union(&matched);
+
+            if reached.is_never() {
+                self.report(ErrorInfo::UnreachableCase {
+                    range: case.range.clone(),
+                });
+            }
+
+            self.bind(&pattern, reached);
+            remaining = remaining.difference(&matched);
+            result = result.union(&self.check_expression(&case.value.maps_to));
+        }
+
+        if let Some(id) = self.uses.get(&(on.range.start, on.range.end)).copied() {
+            let narrowed = self.bindings[&id].intersection(&covered);
+
+            if !narrowed.is_never() {
+                self.bindings.insert(id, narrowed);
+                return result;
+            }
+        }
+
+        if let Some(example) = remaining.example() {
+            self.report(ErrorInfo::NonExhaustiveMatch {
+                range: on.range.clone(),
+                example,
+            });
+        }
+
+        result
+    }
+
+    /// Records the types of the names bound by a pattern matching values of `ty`.
+    fn bind(&mut self, pattern: &Span<Pattern<'source>>, ty: Type<'source>) {
+        match &pattern.value {
+            Pattern::Binding(_)
+                if let Some(id) = self.resolution.binding_of(&pattern.range)
+                    && matches!(
+                        self.resolution.binding(id).kind,
+                        BindingKind::Parameter | BindingKind::Pattern | BindingKind::Definition
+                    ) =>
+            {
+                self.types.insert(&pattern.range, ty.clone());
+                self.bindings.insert(id, ty);
+            }
+            Pattern::Tuple(components) => {
+                for (index, component) in components.iter().enumerate() {
+                    self.bind(component, ty.component(index, components.len()));
+                }
+            }
+            Pattern::Record(fields) => {
+                for (name, field) in fields {
+                    self.bind(field, ty.field(name.value).unwrap_or_else(Type::any));
+                }
+            }
+            Pattern::Or(alternatives) => {
+                for alternative in alternatives {
+                    self.bind(alternative, ty.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the result of calling a function with an argument, reporting
+    /// arguments outside the domain of the function.
+    fn call(
+        &mut self,
+        (function, function_range): (Type<'source>, Range<u32>),
+        (argument, argument_range): (Type<'source>, Range<u32>),
+    ) -> Type<'source> {
+        let mut result = Type::never();
+        let mut reported = false;
+
+        for shape in function.shapes() {
+            match shape {
+                Shape::Any => result = result.union(&Type::any()),
+                Shape::Function { domain, codomain } => {
+                    if !reported {
+                        reported = !self.expect(&argument, domain, &argument_range);
+                    }
+
+                    result = result.union(codomain);
+                }
+                _ => {}
+            }
+        }
+
+        if result.is_never() && !function.is_never() {
+            self.report(ErrorInfo::NotAFunction {
+                range: function_range,
+                found: function.to_string(),
+            });
+
+            return Type::any();
+        }
+
+        result
+    }
+
+    fn unary(
+        &mut self,
+        operation: UnaryOperation,
+        operand: (Type<'source>, Range<u32>),
+    ) -> Type<'source> {
+        let (expected, result) = match operation {
+            UnaryOperation::Negate => (Type::primitive(Primitive::Number), None),
+            UnaryOperation::Not => (self.boolean(), Some(self.boolean())),
+        };
+
+        self.operation(
+            Some(operation.merge_name()),
+            expected,
+            result,
+            vec![operand],
+        )
+    }
+
+    fn binary(
+        &mut self,
+        operation: BinaryOperation,
+        left: (Type<'source>, Range<u32>),
+        right: (Type<'source>, Range<u32>),
+    ) -> Type<'source> {
+        let number = Type::primitive(Primitive::Number);
+
+        let (expected, result) = match operation {
+            BinaryOperation::Add
+            | BinaryOperation::Subtract
+            | BinaryOperation::Multiply
+            | BinaryOperation::Divide
+            | BinaryOperation::Remainder
+            | BinaryOperation::Power => (number, None),
+            BinaryOperation::LessThan
+            | BinaryOperation::LessThanOrEqual
+            | BinaryOperation::GreaterThan
+            | BinaryOperation::GreaterThanOrEqual => (number, Some(self.boolean())),
+            BinaryOperation::And
+            | BinaryOperation::Or
+            | BinaryOperation::LogicalAnd
+            | BinaryOperation::LogicalOr => (self.boolean(), None),
+            BinaryOperation::Concatenate => (Type::primitive(Primitive::String), None),
+            BinaryOperation::Equal
+            | BinaryOperation::NotEqual
+            | BinaryOperation::Identical
+            | BinaryOperation::NotIdentical => return self.boolean(),
+            _ => return Type::any(),
+        };
+
+        self.operation(operation.merge_name(), expected, result, vec![left, right])
+    }
+
+    /// Returns the result of an operation on operands of the `expected` type, which
+    /// is `result` or the `expected` type itself.
+    ///
+    /// Other operands are passed to the `@merge` definitions named `merge_name`,
+    /// if there are any, as the operand itself or as a record `(left = ..., right = ...)`.
+    /// Otherwise, they are reported.
+    fn operation(
+        &mut self,
+        merge_name: Option<&str>,
+        expected: Type<'source>,
+        result: Option<Type<'source>>,
+        operands: Vec<(Type<'source>, Range<u32>)>,
+    ) -> Type<'source> {
+        let result = result.unwrap_or_else(|| expected.clone());
+
+        if operands.iter().all(|(ty, _)| ty.is_subset(&expected)) {
+            return result;
+        }
+
+        let Some(function) = merge_name.and_then(|name| self.merged(name)) else {
+            for (ty, range) in &operands {
+                self.expect(ty, &expected, range);
+            }
+
+            return result;
+        };
+
+        if !operands.iter().all(|(ty, _)| ty.is_known()) {
+            // The operands might still all be of the expected type.
+            return function
+                .shapes()
+                .iter()
+                .fold(result, |result, shape| match shape {
+                    Shape::Function { codomain, .. } => result.union(codomain),
+                    _ => result.union(&Type::any()),
+                });
+        }
+
+        let range = operands[0].1.start..operands.last().unwrap().1.end;
+        let argument = match <[_; 2]>::try_from(operands) {
+            Ok([(left, _), (right, _)]) => Type::record(vec![("left", left), ("right", right)]),
+            Err(mut operands) => operands.remove(0).0,
+        };
+
+        self.call((function, range.clone()), (argument, range))
+    }
+
+    /// Returns the type of the `@merge` definitions named `name`, if there are any.
+    fn merged(&mut self, name: &str) -> Option<Type<'source>> {
+        let id = self
+            .resolution
+            .bindings()
+            .find(|(id, binding)| binding.name == name && self.resolution.is_merged(*id))?
+            .0;
+
+        Some(self.definition(id))
+    }
+
+    /// Reports a value of type `found` where a value of type `expected` is required
+    /// and returns whether it fits.
+    ///
+    /// It does not fit if none of the values fit, or if some fully known values do not fit.
+    /// Values of unknown type are assumed to fit. If the value is a parameter whose type
+    /// is being inferred, the parameter is narrowed to the expected type instead.
+    fn expect(
+        &mut self,
+        found: &Type<'source>,
+        expected: &Type<'source>,
+        range: &Range<u32>,
+    ) -> bool {
+        if let Some(id) = self.uses.get(&(range.start, range.end)).copied() {
+            let narrowed = self.bindings[&id].intersection(expected);
+
+            if !narrowed.is_never() {
+                self.bindings.insert(id, narrowed);
+                return true;
+            }
+        }
+
+        let rest = found.difference(expected);
+
+        if rest.is_never()
+            || !found.intersection(expected).is_never()
+                && !rest.shapes().iter().any(Shape::is_known)
+        {
+            return true;
+        }
+
+        self.report(ErrorInfo::TypeMismatch {
+            range: range.clone(),
+            expected: expected.to_string(),
+            found: found.to_string(),
+        });
+
+        false
+    }
+
+    /// Returns the type of a value in a domain, like the items of `set {...}`,
+    /// without checking it.
+    fn value(&mut self, expression: &Span<Expression<'source>>) -> Type<'source> {
+        match &expression.value {
+            Expression::Identifier(_) => match self.resolution.binding_of(&expression.range) {
+                Some(id) if let Some(ty) = self.bindings.get(&id) => ty.clone(),
+                Some(id) => self.atom(id).unwrap_or_else(Type::any),
+                None => Type::any(),
+            },
+            Expression::Number(number) => Shape::Literal(Literal::Number(number.clone())).into(),
+            Expression::String(string) => Shape::Literal(Literal::String(string.clone())).into(),
+            Expression::Character(character) => {
+                Shape::Literal(Literal::Character(*character)).into()
+            }
+            Expression::Unary {
+                operation: UnaryOperation::Negate,
+                inner,
+            } if let Expression::Number(number) = &inner.value => {
+                Shape::Literal(Literal::Number(-number)).into()
+            }
+            Expression::Grouped {
+                kind: GroupKind::Parenthesized,
+                expression: items,
+            } => self.group(items, Self::value),
+            _ => Type::any(),
+        }
+    }
+
+    /// Evaluates a domain (the expression after `is` or `in`) to the set of values it denotes.
+    pub fn domain(&mut self, expression: &Span<Expression<'source>>) -> Type<'source> {
+        match &expression.value {
+            Expression::Identifier(name) => {
+                let Some(id) = self.resolution.binding_of(&expression.range) else {
+                    return Type::any();
+                };
+
+                match self.resolution.binding(id).kind {
+                    BindingKind::Builtin => match *name {
+                        "Number" => Type::primitive(Primitive::Number),
+                        "String" => Type::primitive(Primitive::String),
+                        "Character" => Type::primitive(Primitive::Character),
+                        "Boolean" => self.boolean(),
+                        _ => self.atom(id).unwrap_or_else(Type::any),
+                    },
+                    BindingKind::Abstract => self.atom(id).unwrap(),
+                    BindingKind::Definition
+                        if let Some(&[(_, definition)]) =
+                            self.definitions.get(&id).map(Vec::as_slice)
+                            && self.evaluating.insert(id) =>
+                    {
+                        let ty = self.domain(definition);
+                        self.evaluating.remove(&id);
+                        ty
+                    }
+                    _ => Type::any(),
+                }
+            }
+            Expression::Grouped {
+                kind: GroupKind::Set,
+                expression: items,
+            } => items.iter().fold(Type::never(), |ty, item| {
+                let value = self.value(item);
+                ty.union(&value)
+            }),
+            Expression::Grouped {
+                kind: GroupKind::Parenthesized,
+                expression: items,
+            } => self.group(items, Self::domain),
+            Expression::Binary {
+                left,
+                operation: BinaryOperation::BitwiseOr,
+                right,
+            } => {
+                let left = self.domain(left);
+                left.union(&self.domain(right))
+            }
+            _ => Type::any(),
+        }
+    }
+
+    /// Returns the set of values a pattern matches.
+    fn pattern(&self, pattern: &Span<Pattern<'source>>) -> Type<'source> {
+        match &pattern.value {
+            Pattern::Error | Pattern::Wildcard => Type::any(),
+            Pattern::Binding(_) => self
+                .resolution
+                .binding_of(&pattern.range)
+                .and_then(|id| self.atom(id))
+                .unwrap_or_else(Type::any),
+            Pattern::Literal(literal) => Shape::Literal(literal.clone()).into(),
+            Pattern::Tuple(components) => Type::tuple(
+                components
+                    .iter()
+                    .map(|component| self.pattern(component))
+                    .collect(),
+            ),
+            Pattern::Record(fields) => Type::record(
+                fields
+                    .iter()
+                    .map(|(name, field)| (name.value, self.pattern(field)))
+                    .collect(),
+            ),
+            Pattern::Or(alternatives) => {
+                alternatives.iter().fold(Type::never(), |ty, alternative| {
+                    ty.union(&self.pattern(alternative))
+                })
+            }
+        }
+    }
+
+    /// Returns the type of a parenthesized group, a tuple or a record,
+    /// using `component` for the type of each component.
+    fn group(
+        &mut self,
+        items: &[Span<Expression<'source>>],
+        component: fn(&mut Self, &Span<Expression<'source>>) -> Type<'source>,
+    ) -> Type<'source> {
+        let field = |item: &Span<Expression<'source>>| match &item.value {
+            Expression::Binary {
+                left,
+                operation: BinaryOperation::Definition,
+                ..
+            } if let Expression::Identifier(name) = left.value => Some(name),
+            _ => None,
+        };
+
+        match items {
+            [item] if field(item).is_none() => component(self, item),
+            _ if items.iter().all(|item| field(item).is_none()) => {
+                Type::tuple(items.iter().map(|item| component(self, item)).collect())
+            }
+            _ if items.iter().all(|item| field(item).is_some()) => Type::record(
+                items
+                    .iter()
+                    .map(|item| {
+                        let Expression::Binary { right, .. } = &item.value else {
+                            unreachable!()
+                        };
+
+                        (field(item).unwrap(), component(self, right))
+                    })
+                    .collect(),
+            ),
+            _ => Type::any(),
+        }
+    }
+
+    /// Returns the type of just the abstract value `id`, if it is one.
+    fn atom(&self, id: BindingId) -> Option<Type<'source>> {
+        let binding = self.resolution.binding(id);
+
+        match binding.kind {
+            BindingKind::Abstract => {}
+            BindingKind::Builtin if matches!(binding.name, "True" | "False") => {}
+            _ => return None,
+        }
+
+        Some(
+            Shape::Atom {
+                id,
+                name: binding.name,
+            }
+            .into(),
+        )
+    }
+
+    fn builtin(&self, name: &str) -> Type<'source> {
+        self.resolution
+            .bindings()
+            .find(|(_, binding)| binding.kind == BindingKind::Builtin && binding.name == name)
+            .and_then(|(id, _)| self.atom(id))
+            .unwrap_or_else(Type::any)
+    }
+
+    fn boolean(&self) -> Type<'source> {
+        self.builtin("True").union(&self.builtin("False"))
+    }
+
+    fn report(&mut self, error: ErrorInfo) {
+        self.diagnostics.push(error.to_diagnostic());
+    }
+}
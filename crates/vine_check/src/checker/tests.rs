@@ -0,0 +1,310 @@
+#![allow(non_snake_case)]
+
+mod Checker {
+    use std::ops::Range;
+
+    use vine_diagnostics::Severity;
+    use vine_parse::parse_module;
+    use vine_resolve::resolve;
+
+    use crate::{TypeTable, check, infer};
+
+    /// Checks `source` and returns the severity, message, primary label and range of each diagnostic.
+    fn checked(source: &str) -> Vec<(Severity, String, String, Range<u32>)> {
+        let module = parse_module(source).unwrap();
+        let (resolution, diagnostics) = resolve(&module);
+        assert_eq!(diagnostics, []);
+
+        check(&module, &resolution)
+            .into_iter()
+            .map(|diagnostic| {
+                let label = diagnostic.labels[0].clone();
+                (
+                    diagnostic.severity,
+                    diagnostic.message,
+                    label.message,
+                    label.range,
+                )
+            })
+            .collect()
+    }
+
+    /// Infers the types in `source` and returns them with the type of each module-level definition.
+    fn inferred(source: &'static str) -> (TypeTable<'static>, Vec<(&'static str, String)>) {
+        let module = parse_module(source).unwrap();
+        let (resolution, _) = resolve(&module);
+        let (types, diagnostics) = infer(&module, &resolution);
+        assert_eq!(diagnostics, []);
+
+        let definitions = resolution
+            .bindings()
+            .filter_map(|(id, binding)| Some((binding.name, types.definition(id)?.to_string())))
+            .collect();
+
+        (types, definitions)
+    }
+
+    #[test]
+    fn check_module() {
+        assert_eq!(
+            checked(include_str!("../../../../language-design/finite.vn")),
+            []
+        );
+    }
+
+    #[test]
+    fn check_match_exhaustive() {
+        let source = "abstract Zero\nabstract One\nZeroOrOne = set {Zero, One}\n\
+                      f = function value is ZeroOrOne => match value\n    case Zero => One";
+
+        assert_eq!(
+            checked(source),
+            [(
+                Severity::Error,
+                "non-exhaustive match".into(),
+                "`One` is not matched".into(),
+                96..101
+            )]
+        );
+
+        assert_eq!(
+            checked(
+                "f = function b is (left = Boolean, right = Boolean) => match b\n\
+                 \x20   case (left = True) => 1\n\
+                 \x20   case (left = False, right = False) => 2"
+            )[0]
+            .2,
+            "`(left = False, right = True)` is not matched"
+        );
+        assert_eq!(
+            checked("f = function x is Number => match x\n    case 0 => 1\n    case 1 => 0")[0].2,
+            "`2` is not matched"
+        );
+        assert_eq!(
+            checked(
+                "f = function x is Number => match x\n    case 0 => 1\n    case n is Number => n"
+            ),
+            []
+        );
+    }
+
+    #[test]
+    fn check_match_unknown() {
+        // The type of `x` is not known, so the match cannot be checked.
+        assert_eq!(checked("f = function x => match x\n    case 0 => 1"), []);
+    }
+
+    #[test]
+    fn check_match_unreachable() {
+        assert_eq!(
+            checked(
+                "f = function x => match x\n    case _ => 1\n    case 0 => 0\n\
+                 g = function b is Boolean => match b\n    case True or False => 1\n    case True => 0"
+            )
+            .into_iter()
+            .map(|(severity, message, _, range)| (severity, message, range))
+            .collect::<Vec<_>>(),
+            [
+                (Severity::Warning, "unreachable case".into(), 46..57),
+                (Severity::Warning, "unreachable case".into(), 127..141),
+            ]
+        );
+        assert_eq!(
+            checked("f = function x is Number => match x\n    case 'c' => 1\n    case _ => 0")
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn check_call() {
+        let source = "abstract Zero\nabstract One\n\
+                      f = function x is set {Zero, One} => x\n\
+                      a = f Zero\nb = f 2\nc = f (1, 2)\nd = 1 2";
+
+        assert_eq!(
+            checked(source),
+            [
+                (
+                    Severity::Error,
+                    "mismatched types".into(),
+                    "expected `Zero | One`, found `2`".into(),
+                    83..84
+                ),
+                (
+                    Severity::Error,
+                    "mismatched types".into(),
+                    "expected `Zero | One`, found `(1, 2)`".into(),
+                    91..97
+                ),
+                (
+                    Severity::Error,
+                    "call of a value that is not a function".into(),
+                    "this is `1`".into(),
+                    102..103
+                ),
+            ]
+        );
+
+        // Records are open, and unknown arguments are accepted.
+        assert_eq!(
+            checked(
+                "f = function p is (left = Number) => p.left\n\
+                 a = f (left = 1, right = 2)\nb = function x => f x"
+            ),
+            []
+        );
+        assert_eq!(
+            checked("f = function p is (left = Number) => p.left\na = f (left = 'c')")[0].2,
+            "expected `(left = Number)`, found `(left = 'c')`"
+        );
+    }
+
+    #[test]
+    fn check_result() {
+        // The result of `f` is the union of the results of its cases,
+        // so `g` cannot be called with it.
+        let source = "f = function b is Boolean => match b\n    case True => 1\n    case False => 'c'\n\
+                      g = function n is Number => n\nx = g (f True)";
+
+        assert_eq!(checked(source)[0].2, "expected `Number`, found `1 | 'c'`");
+        assert_eq!(
+            checked("f = function n is Number => n\nx = f (if 1 < 2 then 1 else 2)"),
+            []
+        );
+        assert_eq!(
+            checked("x = if 1 then 2 else 3")[0].2,
+            "expected `True | False`, found `1`"
+        );
+    }
+
+    #[test]
+    fn check_operations() {
+        assert_eq!(
+            checked("x = 1 + \"a\"\ny = \"a\" ++ \"b\"\nz = -(1 * 2)"),
+            [(
+                Severity::Error,
+                "mismatched types".into(),
+                "expected `Number`, found `\"a\"`".into(),
+                8..11
+            )]
+        );
+
+        // Without `@merge` definitions, operators only work on numbers.
+        assert_eq!(
+            checked("abstract One\nx = -One")[0].2,
+            "expected `Number`, found `One`"
+        );
+        assert_eq!(
+            checked(
+                "abstract One\n@merge\nadditive_inverse = function value is One => 1\n\
+                 x = -One\ny = -x\nz = -(-One)"
+            ),
+            []
+        );
+    }
+
+    #[test]
+    fn check_field() {
+        assert_eq!(
+            checked("p = (left = 1, right = 2)\nx = p.right\ny = x.left"),
+            [(
+                Severity::Error,
+                "no field `left` on `2`".into(),
+                "unknown field".into(),
+                44..48
+            )]
+        );
+    }
+
+    #[test]
+    fn infer_definitions() {
+        let (_, definitions) = inferred(include_str!("../../../../language-design/finite.vn"));
+
+        assert_eq!(
+            definitions,
+            [
+                ("ZeroOrOne", "Set(Zero | One)".into()),
+                (
+                    "add",
+                    "function _ is (left = Zero, right = Zero) | (left = Zero, right = One) \
+                     | (left = One, right = Zero) | (left = One, right = One) => Zero | One"
+                        .into()
+                ),
+                (
+                    "multiply",
+                    "function _ is (left = Zero, right = Zero) | (left = Zero, right = One) \
+                     | (left = One, right = Zero) | (left = One, right = One) => One | Zero"
+                        .into()
+                ),
+                (
+                    "additive_inverse",
+                    "function _ is Zero | One => One | Zero".into()
+                ),
+                ("multiplicative_inverse", "function _ is One => One".into()),
+                ("x", "One | Zero".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn infer_parameters() {
+        let (_, definitions) = inferred(
+            "f = function x => x + 1\n\
+             g = function b => if b then \"yes\" else \"no\"\n\
+             h = function x => x\n\
+             abstract Zero\n\
+             abstract One\n\
+             not = function v => match v\n    case Zero => One\n    case One => Zero",
+        );
+
+        assert_eq!(
+            definitions,
+            [
+                ("f", "function _ is Number => Number".into()),
+                ("g", "function _ is True | False => \"yes\" | \"no\"".into()),
+                ("h", "function _ is Any => Any".into()),
+                ("not", "function _ is Zero | One => One | Zero".into()),
+            ]
+        );
+        assert_eq!(
+            checked("f = function x => x + 1\ny = f \"a\"")[0].2,
+            "expected `Number`, found `\"a\"`"
+        );
+        assert_eq!(
+            checked("f = function x => {\n    a = x + 1\n    b = x ++ \"a\"\n}")[0].2,
+            "expected `String`, found `Number`",
+            "the first use narrows the type"
+        );
+    }
+
+    #[test]
+    fn type_at() {
+        let source =
+            "abstract One\n@merge\nadditive_inverse = function value is One => 1\nx = -One";
+        let (types, _) = inferred(source);
+        let offset = |pattern: &str| source.rfind(pattern).unwrap() as u32;
+
+        assert_eq!(
+            types
+                .at(offset("One"))
+                .map(|(range, ty)| (range, ty.to_string())),
+            Some((offset("One")..offset("One") + 3, "One".into()))
+        );
+        assert_eq!(types.at(offset("-")).unwrap().1.to_string(), "1");
+        assert_eq!(types.at(offset("x")).unwrap().1.to_string(), "1");
+        assert_eq!(types.at(offset("value")).unwrap().1.to_string(), "One");
+        assert_eq!(
+            types
+                .at(offset("abstract"))
+                .map(|(range, ty)| (range, ty.to_string())),
+            Some((0..12, "One".into()))
+        );
+        assert_eq!(
+            types
+                .get(&(offset("function")..offset("\nx")))
+                .map(ToString::to_string),
+            Some("function _ is One => 1".into())
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use std::ops::Range;
+
+use vine_diagnostics::Diagnostic;
+
+#[derive(Debug, Clone)]
+pub enum ErrorInfo {
+    /// A match whose cases do not cover all values of the matched expression.
+    NonExhaustiveMatch {
+        /// The range of the matched expression.
+        range: Range<u32>,
+
+        /// A value that is not matched, in Vine syntax.
+        example: String,
+    },
+
+    /// A match case that can never be reached.
+    UnreachableCase { range: Range<u32> },
+
+    /// A value that is not in the type it is required to be in, like an argument
+    /// outside the domain of a function. The types are written in Vine syntax.
+    TypeMismatch {
+        range: Range<u32>,
+        expected: String,
+        found: String,
+    },
+
+    /// A call of a value that is not a function.
+    NotAFunction { range: Range<u32>, found: String },
+
+    /// An access of a field on a value that is not a record.
+    NoField {
+        /// The range of the field name.
+        range: Range<u32>,
+        name: String,
+        found: String,
+    },
+}
+
+impl ErrorInfo {
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        match self {
+            Self::NonExhaustiveMatch { range, example } => {
+                Diagnostic::error("non-exhaustive match")
+                    .with_primary(range.clone(), format!("`{example}` is not matched"))
+                    .with_help(format!(
+                        "add a case for `{example}`, or a case `_ => ...` for all remaining values"
+                    ))
+            }
+            Self::UnreachableCase { range } => Diagnostic::warning("unreachable case")
+                .with_primary(range.clone(), "this case never matches")
+                .with_note(
+                    "every value it matches is matched by an earlier case \
+                     or cannot be a value of the matched expression",
+                ),
+            Self::TypeMismatch {
+                range,
+                expected,
+                found,
+            } => Diagnostic::error("mismatched types").with_primary(
+                range.clone(),
+                format!("expected `{expected}`, found `{found}`"),
+            ),
+            Self::NotAFunction { range, found } => {
+                Diagnostic::error("call of a value that is not a function")
+                    .with_primary(range.clone(), format!("this is `{found}`"))
+            }
+            Self::NoField { range, name, found } => {
+                Diagnostic::error(format!("no field `{name}` on `{found}`"))
+                    .with_primary(range.clone(), "unknown field")
+                    .with_note("only records have fields")
+            }
+        }
+    }
+}
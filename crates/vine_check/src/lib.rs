@@ -0,0 +1,50 @@
+//! Semantic checks on resolved modules.
+//!
+//! Types are sets of values ([`Type`]). The sets are denoted by the domains
+//! after `is` and `in`, which may be `set {...}` expressions, records and tuples
+//! of domains, builtin types like `Number` and names defined as such.
+//!
+//! The [`Checker`] computes the type of every expression and reports values
+//! outside the type they are required to be in: arguments outside the domain of
+//! the function, conditions that are not booleans and operands of operators.
+//! Arithmetic operators on values other than numbers call the `@merge`
+//! definitions named by [`BinaryOperation::merge_name`] and
+//! [`UnaryOperation::merge_name`].
+//!
+//! Definitions are not annotated, so their types are inferred from their
+//! right-hand sides. Parameters without a domain are narrowed to the values
+//! that their uses in the body require, like `Number` for `x` in
+//! `function x => x + 1`, or to the values matched by the cases of a `match`
+//! on them. The [`TypeTable`] returned by [`infer`] holds the
+//! type of every expression and binding by its range.
+//!
+//! [`BinaryOperation::merge_name`]: vine_parse::ast::BinaryOperation::merge_name
+//! [`UnaryOperation::merge_name`]: vine_parse::ast::UnaryOperation::merge_name
+
+mod checker;
+mod error;
+mod table;
+mod ty;
+
+pub use checker::*;
+pub use error::*;
+pub use table::*;
+pub use ty::*;
+use vine_diagnostics::Diagnostic;
+use vine_parse::ast::Module;
+use vine_resolve::Resolution;
+
+/// Checks a resolved module.
+pub fn check(module: &Module, resolution: &Resolution) -> Vec<Diagnostic> {
+    infer(module, resolution).1
+}
+
+/// Checks a resolved module and returns the types of its expressions and definitions.
+pub fn infer<'source>(
+    module: &Module<'source>,
+    resolution: &Resolution<'source>,
+) -> (TypeTable<'source>, Vec<Diagnostic>) {
+    let mut checker = Checker::new(resolution);
+    checker.check_module(module);
+    checker.finish()
+}
@@ -0,0 +1,53 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use vine_resolve::BindingId;
+
+use crate::Type;
+
+/// The types the [`Checker`](crate::Checker) computed, by the range of the
+/// expression or the binding they belong to.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TypeTable<'source> {
+    /// The types by the start and end of their range.
+    ranges: BTreeMap<(u32, u32), Type<'source>>,
+
+    /// The types of the module-level definitions.
+    definitions: BTreeMap<BindingId, Type<'source>>,
+}
+
+impl<'source> TypeTable<'source> {
+    /// Returns the type of the expression or binding at exactly `range`.
+    pub fn get(&self, range: &Range<u32>) -> Option<&Type<'source>> {
+        self.ranges.get(&(range.start, range.end))
+    }
+
+    /// Returns the innermost expression or binding containing the byte at `offset`
+    /// and its type, as shown when hovering over it.
+    pub fn at(&self, offset: u32) -> Option<(Range<u32>, &Type<'source>)> {
+        self.ranges
+            .range(..=(offset, u32::MAX))
+            .filter(|((_, end), _)| offset < *end)
+            .min_by_key(|((start, end), _)| end - start)
+            .map(|(&(start, end), ty)| (start..end, ty))
+    }
+
+    /// Returns the type of a module-level definition.
+    pub fn definition(&self, id: BindingId) -> Option<&Type<'source>> {
+        self.definitions.get(&id)
+    }
+
+    /// Returns the types by their range, ordered by start.
+    pub fn iter(&self) -> impl Iterator<Item = (Range<u32>, &Type<'source>)> {
+        self.ranges
+            .iter()
+            .map(|(&(start, end), ty)| (start..end, ty))
+    }
+
+    pub(crate) fn insert(&mut self, range: &Range<u32>, ty: Type<'source>) {
+        self.ranges.insert((range.start, range.end), ty);
+    }
+
+    pub(crate) fn insert_definition(&mut self, id: BindingId, ty: Type<'source>) {
+        self.definitions.insert(id, ty);
+    }
+}
@@ -0,0 +1,534 @@
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+use vine_num::Rational;
+use vine_parse::lower::Literal;
+use vine_resolve::BindingId;
+
+/// A set of values, used as the type of an expression.
+///
+/// A type is a union of [`Shape`]s, and the empty union is the type without
+/// any values. The operations are exact, except that [`Shape::Any`] cannot be
+/// split up: removing anything from it (other than itself) leaves it unchanged.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Type<'source> {
+    shapes: Vec<Shape<'source>>,
+}
+
+/// A set of values that is not a union.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape<'source> {
+    /// Any value. Used where nothing more specific is known.
+    Any,
+
+    /// A value declared with `abstract`.
+    Atom {
+        id: BindingId,
+        name: &'source str,
+    },
+
+    /// All values of a primitive type except the excluded literals.
+    Primitive {
+        primitive: Primitive,
+        excluded: Vec<Literal<'source>>,
+    },
+
+    /// A single literal value.
+    Literal(Literal<'source>),
+
+    Tuple(Vec<Shape<'source>>),
+
+    /// Records with at least these fields. Missing fields can have any value.
+    Record(BTreeMap<&'source str, Shape<'source>>),
+
+    /// Sets, like `set {...}`, whose elements are all in the type.
+    Set(Type<'source>),
+
+    /// Functions that accept at least the values of `domain` and
+    /// return values of `codomain` for them.
+    Function {
+        domain: Type<'source>,
+        codomain: Type<'source>,
+    },
+}
+
+/// A type with infinitely many values.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Primitive {
+    Number,
+    String,
+    Character,
+}
+
+impl Primitive {
+    pub fn of(literal: &Literal) -> Self {
+        match literal {
+            Literal::Number(_) => Self::Number,
+            Literal::String(_) => Self::String,
+            Literal::Character(_) => Self::Character,
+        }
+    }
+
+    /// Returns a value of the type that is not one of `excluded`.
+    fn example(self, excluded: &[Literal]) -> Literal<'static> {
+        (0..)
+            .map(|n: u32| match self {
+                Self::Number => Literal::Number(Rational::from(n as u64)),
+                Self::String => Literal::String((1..=n).map(|_| 'a').collect::<String>().into()),
+                Self::Character => Literal::Character(char::from_u32('a' as u32 + n).unwrap()),
+            })
+            .find(|literal| !excluded.contains(literal))
+            .unwrap()
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Number => "Number",
+            Self::String => "String",
+            Self::Character => "Character",
+        }
+    }
+}
+
+impl<'source> Type<'source> {
+    /// The type without any values.
+    pub const fn never() -> Self {
+        Self { shapes: Vec::new() }
+    }
+
+    pub fn any() -> Self {
+        Self::from(Shape::Any)
+    }
+
+    pub fn primitive(primitive: Primitive) -> Self {
+        Self::from(Shape::Primitive {
+            primitive,
+            excluded: Vec::new(),
+        })
+    }
+
+    /// The tuples whose components are in the given types.
+    pub fn tuple(components: Vec<Self>) -> Self {
+        Self::from_shapes(product(components).into_iter().map(Shape::Tuple))
+    }
+
+    /// The records whose fields are in the given types.
+    pub fn record(fields: Vec<(&'source str, Self)>) -> Self {
+        let (names, types): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
+
+        Self::from_shapes(
+            product(types)
+                .into_iter()
+                .map(|shapes| Shape::Record(names.iter().copied().zip(shapes).collect())),
+        )
+    }
+
+    pub fn function(domain: Self, codomain: Self) -> Self {
+        Self::from(Shape::Function { domain, codomain })
+    }
+
+    pub fn shapes(&self) -> &[Shape<'source>] {
+        &self.shapes
+    }
+
+    pub fn is_never(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    /// Whether the type is fully known, i.e. does not contain [`Shape::Any`].
+    pub fn is_known(&self) -> bool {
+        self.shapes.iter().all(Shape::is_known)
+    }
+
+    /// Whether every value of `self` is in `other`.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_never()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_shapes(self.shapes.iter().chain(&other.shapes).cloned())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::from_shapes(
+            self.shapes
+                .iter()
+                .flat_map(|a| other.shapes.iter().flat_map(|b| a.intersection(b))),
+        )
+    }
+
+    /// Returns the values of `self` that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        other.shapes.iter().fold(self.clone(), |rest, b| {
+            Self::from_shapes(rest.shapes.iter().flat_map(|a| a.difference(b)))
+        })
+    }
+
+    /// Returns a value of the type in Vine syntax.
+    ///
+    /// Only fully known shapes are considered, since a value in [`Shape::Any`] may
+    /// well be in another type, too. Returns [`None`] if there is no such shape.
+    pub fn example(&self) -> Option<String> {
+        self.shapes
+            .iter()
+            .filter(|shape| shape.is_known())
+            .find_map(Shape::example)
+    }
+
+    /// Returns the type of the field `name` of the values.
+    ///
+    /// Returns [`None`] if some values are not records.
+    pub fn field(&self, name: &str) -> Option<Self> {
+        self.shapes
+            .iter()
+            .map(|shape| match shape {
+                Shape::Any => Some(Shape::Any),
+                Shape::Record(fields) => Some(fields.get(name).cloned().unwrap_or(Shape::Any)),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(Self::from_shapes)
+    }
+
+    /// Returns the type of the component `index` of the values that are tuples with `length` components.
+    pub fn component(&self, index: usize, length: usize) -> Self {
+        Self::from_shapes(self.shapes.iter().filter_map(|shape| match shape {
+            Shape::Any => Some(Shape::Any),
+            Shape::Tuple(components) if components.len() == length => {
+                Some(components[index].clone())
+            }
+            _ => None,
+        }))
+    }
+
+    fn from_shapes(shapes: impl IntoIterator<Item = Shape<'source>>) -> Self {
+        let mut unique = Vec::new();
+
+        for shape in shapes {
+            if !unique.contains(&shape) {
+                unique.push(shape);
+            }
+        }
+
+        Self { shapes: unique }
+    }
+}
+
+impl<'source> From<Shape<'source>> for Type<'source> {
+    fn from(shape: Shape<'source>) -> Self {
+        Self {
+            shapes: vec![shape],
+        }
+    }
+}
+
+impl<'source> Shape<'source> {
+    /// Whether the shape is fully known, i.e. does not contain [`Shape::Any`].
+    pub fn is_known(&self) -> bool {
+        match self {
+            Self::Any => false,
+            Self::Atom { .. } | Self::Primitive { .. } | Self::Literal(_) => true,
+            Self::Tuple(components) => components.iter().all(Self::is_known),
+            Self::Record(fields) => fields.values().all(Self::is_known),
+            Self::Set(elements) => elements.is_known(),
+            Self::Function { domain, codomain } => domain.is_known() && codomain.is_known(),
+        }
+    }
+
+    fn intersection(&self, other: &Self) -> Vec<Self> {
+        match (self, other) {
+            (Self::Any, shape) | (shape, Self::Any) => vec![shape.clone()],
+            (Self::Atom { id: a, .. }, Self::Atom { id: b, .. }) if a == b => vec![self.clone()],
+            (
+                Self::Primitive {
+                    primitive: a,
+                    excluded,
+                },
+                Self::Primitive {
+                    primitive: b,
+                    excluded: other_excluded,
+                },
+            ) if a == b => {
+                let mut excluded = excluded.clone();
+                excluded.extend(
+                    other_excluded
+                        .iter()
+                        .filter(|literal| !excluded.contains(literal))
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                );
+
+                vec![Self::Primitive {
+                    primitive: *a,
+                    excluded,
+                }]
+            }
+            (
+                Self::Primitive {
+                    primitive,
+                    excluded,
+                },
+                Self::Literal(literal),
+            )
+            | (
+                Self::Literal(literal),
+                Self::Primitive {
+                    primitive,
+                    excluded,
+                },
+            ) if Primitive::of(literal) == *primitive && !excluded.contains(literal) => {
+                vec![Self::Literal(literal.clone())]
+            }
+            (Self::Literal(a), Self::Literal(b)) if a == b => vec![self.clone()],
+            (Self::Tuple(a), Self::Tuple(b)) if a.len() == b.len() => product(
+                a.iter()
+                    .zip(b)
+                    .map(|(a, b)| Type::from_shapes(a.intersection(b)))
+                    .collect(),
+            )
+            .into_iter()
+            .map(Self::Tuple)
+            .collect(),
+            (Self::Record(a), Self::Record(b)) => {
+                let (names, types): (Vec<_>, Vec<_>) = record_fields(a, b)
+                    .map(|(name, a, b)| (name, Type::from_shapes(a.intersection(b))))
+                    .unzip();
+
+                product(types)
+                    .into_iter()
+                    .map(|shapes| Self::Record(names.iter().copied().zip(shapes).collect()))
+                    .collect()
+            }
+            (Self::Set(a), Self::Set(b)) => vec![Self::Set(a.intersection(b))],
+            // The intersection of two unrelated function types cannot be expressed,
+            // so this keeps `self` unless `other` is more specific.
+            (Self::Function { .. }, Self::Function { .. }) if other.is_subset(self) => {
+                vec![other.clone()]
+            }
+            (Self::Function { .. }, Self::Function { .. }) => vec![self.clone()],
+            _ => Vec::new(),
+        }
+    }
+
+    fn difference(&self, other: &Self) -> Vec<Self> {
+        match (self, other) {
+            (_, Self::Any) => Vec::new(),
+            (Self::Atom { id: a, .. }, Self::Atom { id: b, .. }) if a == b => Vec::new(),
+            (
+                Self::Primitive {
+                    primitive,
+                    excluded,
+                },
+                Self::Literal(literal),
+            ) if Primitive::of(literal) == *primitive && !excluded.contains(literal) => {
+                let mut excluded = excluded.clone();
+                excluded.push(literal.clone());
+
+                vec![Self::Primitive {
+                    primitive: *primitive,
+                    excluded,
+                }]
+            }
+            (
+                Self::Primitive {
+                    primitive: a,
+                    excluded,
+                },
+                Self::Primitive {
+                    primitive: b,
+                    excluded: other_excluded,
+                },
+            ) if a == b => other_excluded
+                .iter()
+                .filter(|literal| !excluded.contains(literal))
+                .cloned()
+                .map(Self::Literal)
+                .collect(),
+            (Self::Literal(_), Self::Primitive { .. } | Self::Literal(_))
+                if !self.intersection(other).is_empty() =>
+            {
+                Vec::new()
+            }
+            // A × B − C × D = (A − C) × B ∪ A × (B − D)
+            (Self::Tuple(a), Self::Tuple(b))
+                if a.len() == b.len() && !self.intersection(other).is_empty() =>
+            {
+                (0..a.len())
+                    .flat_map(|index| {
+                        a[index].difference(&b[index]).into_iter().map(move |rest| {
+                            let mut components = a.clone();
+                            components[index] = rest;
+                            Self::Tuple(components)
+                        })
+                    })
+                    .collect()
+            }
+            (Self::Record(a), Self::Record(b)) if !self.intersection(other).is_empty() => {
+                record_fields(a, b)
+                    .flat_map(|(name, field, other_field)| {
+                        field.difference(other_field).into_iter().map(move |rest| {
+                            let mut fields = a.clone();
+                            fields.insert(name, rest);
+                            Self::Record(fields)
+                        })
+                    })
+                    .collect()
+            }
+            // Like for functions, only removing all of the sets can be expressed.
+            (Self::Set(a), Self::Set(b)) if a.is_subset(b) => Vec::new(),
+            (Self::Function { .. }, Self::Function { .. }) if self.is_subset(other) => Vec::new(),
+            _ => vec![self.clone()],
+        }
+    }
+
+    /// Whether every function of `self` is in `other`. Functions accepting more
+    /// values and returning fewer values are more specific.
+    fn is_subset(&self, other: &Self) -> bool {
+        let (
+            Self::Function { domain, codomain },
+            Self::Function {
+                domain: other_domain,
+                codomain: other_codomain,
+            },
+        ) = (self, other)
+        else {
+            return false;
+        };
+
+        other_domain.is_subset(domain) && codomain.is_subset(other_codomain)
+    }
+
+    /// Returns a value of the shape in Vine syntax, or [`None`] for functions.
+    /// The example of a set is the empty set.
+    fn example(&self) -> Option<String> {
+        Some(match self {
+            Self::Any => "_".into(),
+            Self::Atom { name, .. } => name.to_string(),
+            Self::Primitive {
+                primitive,
+                excluded,
+            } => primitive.example(excluded).to_string(),
+            Self::Literal(literal) => literal.to_string(),
+            Self::Tuple(components) => format!(
+                "({})",
+                components
+                    .iter()
+                    .map(Self::example)
+                    .collect::<Option<Vec<_>>>()?
+                    .join(", ")
+            ),
+            Self::Record(fields) => format!(
+                "({})",
+                fields
+                    .iter()
+                    .map(|(name, field)| Some(format!("{name} = {}", field.example()?)))
+                    .collect::<Option<Vec<_>>>()?
+                    .join(", ")
+            ),
+            Self::Set(_) => "set {}".into(),
+            Self::Function { .. } => return None,
+        })
+    }
+}
+
+/// Returns the fields of two records by name. Fields missing from one of them are [`Shape::Any`].
+fn record_fields<'a, 'source>(
+    a: &'a BTreeMap<&'source str, Shape<'source>>,
+    b: &'a BTreeMap<&'source str, Shape<'source>>,
+) -> impl Iterator<Item = (&'source str, &'a Shape<'source>, &'a Shape<'source>)> {
+    let mut names = a.keys().chain(b.keys()).copied().collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+
+    names.into_iter().map(|name| {
+        (
+            name,
+            a.get(name).unwrap_or(&Shape::Any),
+            b.get(name).unwrap_or(&Shape::Any),
+        )
+    })
+}
+
+/// Returns every combination of one shape of each type.
+fn product<'source>(types: Vec<Type<'source>>) -> Vec<Vec<Shape<'source>>> {
+    types
+        .into_iter()
+        .fold(vec![Vec::new()], |combinations, ty| {
+            combinations
+                .iter()
+                .flat_map(|combination| {
+                    ty.shapes.iter().map(move |shape| {
+                        let mut combination = combination.clone();
+                        combination.push(shape.clone());
+                        combination
+                    })
+                })
+                .collect()
+        })
+}
+
+impl Display for Shape<'_> {
+    /// Writes the shape as a Vine expression that denotes the set of its values.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => f.write_str("Any"),
+            Self::Primitive { primitive, .. } => f.write_str(primitive.name()),
+            Self::Tuple(components) => {
+                f.write_str("(")?;
+
+                for (index, component) in components.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    write!(f, "{component}")?;
+                }
+
+                f.write_str(")")
+            }
+            Self::Record(fields) => {
+                f.write_str("(")?;
+
+                for (index, (name, field)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    write!(f, "{name} = {field}")?;
+                }
+
+                f.write_str(")")
+            }
+            Self::Set(elements) => write!(f, "Set({elements})"),
+            Self::Function { domain, codomain } => {
+                write!(f, "function _ is {domain} => {codomain}")
+            }
+            Self::Atom { name, .. } => f.write_str(name),
+            Self::Literal(literal) => write!(f, "{literal}"),
+        }
+    }
+}
+
+impl Display for Type<'_> {
+    /// Writes the type as a union `A | B`. The empty type is written as `set {}`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.shapes.is_empty() {
+            return f.write_str("set {}");
+        }
+
+        for (index, shape) in self.shapes.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" | ")?;
+            }
+
+            write!(f, "{shape}")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+#![allow(non_snake_case)]
+
+mod Type {
+    use vine_num::Rational;
+    use vine_parse::lower::Literal;
+    use vine_resolve::{BindingKind, PRELUDE, Resolver};
+
+    use super::super::*;
+
+    /// Returns atoms for the builtins `True` and `False`.
+    fn booleans() -> (Type<'static>, Type<'static>) {
+        let resolver = Resolver::new();
+        let atom = |name| {
+            let (id, binding) = resolver
+                .resolution()
+                .bindings()
+                .find(|(_, binding)| binding.name == name)
+                .unwrap();
+
+            assert_eq!(binding.kind, BindingKind::Builtin);
+            Type::from(Shape::Atom {
+                id,
+                name: PRELUDE[id.index()],
+            })
+        };
+
+        (atom("True"), atom("False"))
+    }
+
+    fn number(n: u64) -> Type<'static> {
+        Shape::Literal(Literal::Number(Rational::from(n))).into()
+    }
+
+    #[test]
+    fn union() {
+        let (yes, no) = booleans();
+
+        assert_eq!(yes.union(&no).to_string(), "True | False");
+        assert_eq!(yes.union(&yes), yes);
+        assert_eq!(Type::never().union(&no), no);
+    }
+
+    #[test]
+    fn intersection() {
+        let (yes, no) = booleans();
+        let boolean = yes.union(&no);
+
+        assert_eq!(boolean.intersection(&yes), yes);
+        assert!(yes.intersection(&no).is_never());
+        assert_eq!(Type::any().intersection(&no), no);
+        assert_eq!(
+            Type::primitive(Primitive::Number).intersection(&number(1)),
+            number(1)
+        );
+        assert_eq!(
+            Type::tuple(vec![boolean.clone(), boolean.clone()])
+                .intersection(&Type::tuple(vec![yes.clone(), Type::any()]))
+                .to_string(),
+            "(True, True) | (True, False)"
+        );
+    }
+
+    #[test]
+    fn difference() {
+        let (yes, no) = booleans();
+        let boolean = yes.union(&no);
+
+        assert_eq!(boolean.difference(&yes), no);
+        assert!(boolean.difference(&Type::any()).is_never());
+        assert_eq!(Type::any().difference(&yes), Type::any());
+
+        let pairs = Type::record(vec![("left", boolean.clone()), ("right", boolean.clone())]);
+        let rest = pairs
+            .difference(&Type::record(vec![("left", yes.clone())]))
+            .difference(&Type::record(vec![("right", no.clone())]));
+
+        assert_eq!(rest.to_string(), "(left = False, right = True)");
+        assert!(
+            rest.difference(&Type::record(vec![("left", no), ("right", yes)]))
+                .is_never()
+        );
+    }
+
+    #[test]
+    fn example() {
+        let numbers = Type::primitive(Primitive::Number)
+            .difference(&number(0))
+            .difference(&number(1));
+
+        assert_eq!(numbers.example().as_deref(), Some("2"));
+        assert_eq!(
+            Type::primitive(Primitive::String)
+                .difference(&Shape::Literal(Literal::String("".into())).into())
+                .example()
+                .as_deref(),
+            Some("\"a\"")
+        );
+        assert_eq!(Type::tuple(vec![Type::any(), number(1)]).example(), None);
+        assert_eq!(Type::never().example(), None);
+        assert_eq!(
+            Type::function(number(1), number(2)).example(),
+            None,
+            "functions cannot be written as values"
+        );
+    }
+
+    #[test]
+    fn is_subset() {
+        let (yes, no) = booleans();
+        let boolean = yes.union(&no);
+        let numbers = Type::primitive(Primitive::Number);
+
+        assert!(yes.is_subset(&boolean));
+        assert!(!boolean.is_subset(&yes));
+        assert!(number(1).is_subset(&numbers));
+        assert!(Type::never().is_subset(&yes));
+
+        // Functions accepting more and returning less are more specific.
+        assert!(
+            Type::function(numbers.clone(), number(1))
+                .is_subset(&Type::function(number(2), numbers.clone()))
+        );
+        assert!(
+            !Type::function(number(2), number(1))
+                .is_subset(&Type::function(numbers.clone(), number(1)))
+        );
+        assert!(
+            Type::function(number(1), number(1))
+                .intersection(&numbers)
+                .is_never()
+        );
+
+        // Sets are more specific if their elements are.
+        let set = |elements| Type::from(Shape::Set(elements));
+
+        assert!(set(yes.clone()).is_subset(&set(boolean.clone())));
+        assert!(!set(boolean.clone()).is_subset(&set(yes.clone())));
+        assert_eq!(set(boolean).intersection(&set(no.union(&numbers))), set(no));
+        assert_eq!(set(numbers).example().as_deref(), Some("set {}"));
+    }
+
+    #[test]
+    fn field() {
+        let (yes, no) = booleans();
+        let pairs = Type::record(vec![("left", yes.clone()), ("right", no)])
+            .union(&Type::record(vec![("left", number(1))]));
+
+        assert_eq!(pairs.field("left"), Some(yes.union(&number(1))));
+        assert_eq!(pairs.field("right").unwrap().to_string(), "False | Any");
+        assert_eq!(yes.field("left"), None);
+        assert_eq!(Type::any().field("left"), Some(Type::any()));
+    }
+
+    #[test]
+    fn component() {
+        let (yes, _) = booleans();
+        let tuples = Type::tuple(vec![yes.clone(), number(1)]).union(&Type::tuple(vec![number(2)]));
+
+        assert_eq!(tuples.component(1, 2), number(1));
+        assert_eq!(tuples.component(0, 1), number(2));
+        assert!(tuples.component(0, 3).is_never());
+    }
+}
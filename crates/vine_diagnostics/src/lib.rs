@@ -0,0 +1,119 @@
+//! Diagnostics shared by all stages of the compiler.
+
+mod render;
+mod source;
+
+use std::ops::Range;
+
+pub use render::*;
+pub use source::*;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Extra information, usually attached to another diagnostic.
+    Note,
+
+    /// Something suspicious that does not stop compilation.
+    Warning,
+
+    /// Something that prevents the program from compiling.
+    Error,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LabelStyle {
+    /// The location that caused the diagnostic.
+    Primary,
+
+    /// A related location that helps explain the diagnostic.
+    Secondary,
+}
+
+/// A message attached to a byte range in the source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Label {
+    pub style: LabelStyle,
+    pub range: Range<u32>,
+
+    /// The message; may be empty.
+    pub message: String,
+}
+
+/// A message about the source, like a syntax error or an unused variable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+
+    /// The main message, like "expected `)`, found `]`".
+    pub message: String,
+
+    /// The labelled locations. Usually one primary and
+    /// any number of secondary labels.
+    pub labels: Vec<Label>,
+
+    /// Additional notes printed after the source snippets.
+    pub notes: Vec<String>,
+
+    /// A suggestion on how to fix the problem.
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            labels: Vec::new(),
+            notes: Vec::new(),
+            help: None,
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    pub fn with_primary(mut self, range: Range<u32>, message: impl Into<String>) -> Self {
+        self.labels.push(Label {
+            style: LabelStyle::Primary,
+            range,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_secondary(mut self, range: Range<u32>, message: impl Into<String>) -> Self {
+        self.labels.push(Label {
+            style: LabelStyle::Secondary,
+            range,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Returns the range of the first primary label.
+    pub fn primary_range(&self) -> Option<Range<u32>> {
+        self.labels
+            .iter()
+            .find(|label| label.style == LabelStyle::Primary)
+            .map(|label| label.range.clone())
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
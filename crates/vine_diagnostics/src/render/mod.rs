@@ -0,0 +1,320 @@
+#[cfg(test)]
+mod tests;
+
+use std::{
+    env,
+    fmt::Write,
+    io::{self, IsTerminal},
+    ops::Range,
+};
+
+use crate::{Diagnostic, Label, LabelStyle, Severity, SourceFile};
+
+/// How diagnostics are rendered.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable text with source snippets.
+    Plain,
+
+    /// Like [`Format::Plain`], but with ANSI colours.
+    Colored,
+
+    /// One JSON object per diagnostic, for tooling.
+    Json,
+}
+
+impl Format {
+    /// Returns [`Format::Colored`] if stderr is a terminal and
+    /// `NO_COLOR` is not set, [`Format::Plain`] otherwise.
+    pub fn for_stderr() -> Self {
+        if io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none() {
+            Self::Colored
+        } else {
+            Self::Plain
+        }
+    }
+
+    /// Renders `diagnostic`, whose ranges are relative to the start of `file`.
+    pub fn render(self, diagnostic: &Diagnostic, file: &SourceFile) -> String {
+        match self {
+            Self::Plain => render_text(diagnostic, file, false),
+            Self::Colored => render_text(diagnostic, file, true),
+            Self::Json => render_json(diagnostic, file),
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const CYAN: &str = "\x1b[1;36m";
+const BLUE: &str = "\x1b[1;34m";
+
+/// The width a tab is expanded to in source snippets.
+const TAB_WIDTH: usize = 4;
+
+impl Severity {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Note => "note",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+
+    const fn color(self) -> &'static str {
+        match self {
+            Self::Note => CYAN,
+            Self::Warning => YELLOW,
+            Self::Error => RED,
+        }
+    }
+}
+
+/// A byte offset resolved to a line.
+struct Location<'source> {
+    /// The zero-based line index.
+    line: usize,
+
+    /// The zero-based column in characters.
+    column: usize,
+
+    /// The text of the line, without the line break.
+    text: &'source str,
+
+    /// The byte offset of the start of the line.
+    line_start: usize,
+}
+
+fn locate(file: &SourceFile, offset: u32) -> Location<'_> {
+    let position = file.position(offset);
+    let text = file.line_text(position.line).unwrap();
+
+    Location {
+        line: position.line as usize,
+        column: text[..position.utf8_column as usize].chars().count(),
+        text,
+        line_start: file.line_range(position.line).unwrap().start as usize,
+    }
+}
+
+/// Returns the display width of `text`, expanding tabs.
+fn display_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+        .sum()
+}
+
+fn render_text(diagnostic: &Diagnostic, file: &SourceFile, colored: bool) -> String {
+    let paint = |color: &'static str| if colored { color } else { "" };
+    let reset = paint(RESET);
+    let severity_color = paint(diagnostic.severity.color());
+
+    let mut output = String::new();
+
+    _ = writeln!(
+        output,
+        "{severity_color}{}{reset}{}: {}{reset}",
+        diagnostic.severity.as_str(),
+        paint(BOLD),
+        diagnostic.message
+    );
+
+    // Group the labels by the line they start on.
+    let mut labels = diagnostic
+        .labels
+        .iter()
+        .map(|label| (locate(file, label.range.start), label))
+        .collect::<Vec<_>>();
+    labels.sort_by_key(|(location, label)| (location.line, label.range.start));
+
+    let gutter_width = labels
+        .last()
+        .map_or(1, |(location, _)| (location.line + 1).to_string().len());
+    let gutter = format!("{}{:gutter_width$} |{reset}", paint(BLUE), "");
+
+    if let Some(range) = diagnostic.primary_range() {
+        let location = locate(file, range.start);
+
+        _ = writeln!(
+            output,
+            "{}{:gutter_width$}--> {reset}{}:{}:{}",
+            paint(BLUE),
+            "",
+            file.name(),
+            location.line + 1,
+            location.column + 1
+        );
+    }
+
+    if !labels.is_empty() {
+        _ = writeln!(output, "{gutter}");
+    }
+
+    let mut previous_line = None;
+
+    for (location, label) in &labels {
+        if previous_line != Some(location.line) {
+            if previous_line.is_some_and(|previous| previous + 1 < location.line) {
+                _ = writeln!(output, "{}...{reset}", paint(BLUE));
+            }
+
+            _ = writeln!(
+                output,
+                "{}{:>gutter_width$} |{reset} {}",
+                paint(BLUE),
+                location.line + 1,
+                location.text.replace('\t', &" ".repeat(TAB_WIDTH))
+            );
+            previous_line = Some(location.line);
+        }
+
+        let (marker, color) = match label.style {
+            LabelStyle::Primary => ('^', severity_color),
+            LabelStyle::Secondary => ('-', paint(BLUE)),
+        };
+
+        let padding = display_width(&location.text[..underline_start(location, label)]);
+        let width = underline_width(location, &label.range).max(1);
+
+        _ = write!(
+            output,
+            "{gutter} {:padding$}{color}{}",
+            "",
+            marker.to_string().repeat(width)
+        );
+
+        if label.message.is_empty() {
+            _ = writeln!(output, "{reset}");
+        } else {
+            _ = writeln!(output, " {}{reset}", label.message);
+        }
+    }
+
+    if !labels.is_empty() && (!diagnostic.notes.is_empty() || diagnostic.help.is_some()) {
+        _ = writeln!(output, "{gutter}");
+    }
+
+    for note in &diagnostic.notes {
+        _ = writeln!(
+            output,
+            "{}{:gutter_width$} = {reset}{}note{reset}: {note}",
+            paint(BLUE),
+            "",
+            paint(BOLD)
+        );
+    }
+
+    if let Some(help) = &diagnostic.help {
+        _ = writeln!(
+            output,
+            "{}{:gutter_width$} = {reset}{}help{reset}: {help}",
+            paint(BLUE),
+            "",
+            paint(BOLD)
+        );
+    }
+
+    output
+}
+
+/// Returns the byte offset of the label start within its line. A start in the
+/// line break or past the end of the file is placed after the last character.
+fn underline_start(location: &Location, label: &Label) -> usize {
+    column_of(location, label.range.start)
+}
+
+/// Returns the display width of the part of `range` on the line of `location`.
+fn underline_width(location: &Location, range: &Range<u32>) -> usize {
+    let start = column_of(location, range.start);
+    let end = (range.end as usize)
+        .saturating_sub(location.line_start)
+        .clamp(start, location.text.len());
+
+    display_width(&location.text[start..end])
+}
+
+/// Returns the byte offset of `offset` within the line of `location`,
+/// clamped to the text of the line.
+fn column_of(location: &Location, offset: u32) -> usize {
+    (offset as usize)
+        .saturating_sub(location.line_start)
+        .min(location.text.len())
+}
+
+fn render_json(diagnostic: &Diagnostic, file: &SourceFile) -> String {
+    let mut output = String::new();
+
+    _ = write!(
+        output,
+        r#"{{"severity":{},"message":{},"file":{},"labels":["#,
+        json_string(diagnostic.severity.as_str()),
+        json_string(&diagnostic.message),
+        json_string(file.name())
+    );
+
+    for (index, label) in diagnostic.labels.iter().enumerate() {
+        let location = locate(file, label.range.start);
+
+        if index > 0 {
+            output.push(',');
+        }
+
+        _ = write!(
+            output,
+            r#"{{"style":{},"start":{},"end":{},"line":{},"column":{},"message":{}}}"#,
+            json_string(match label.style {
+                LabelStyle::Primary => "primary",
+                LabelStyle::Secondary => "secondary",
+            }),
+            label.range.start,
+            label.range.end,
+            location.line + 1,
+            location.column + 1,
+            json_string(&label.message)
+        );
+    }
+
+    output.push_str(r#"],"notes":["#);
+
+    for (index, note) in diagnostic.notes.iter().enumerate() {
+        if index > 0 {
+            output.push(',');
+        }
+
+        output.push_str(&json_string(note));
+    }
+
+    _ = write!(
+        output,
+        r#"],"help":{}}}"#,
+        diagnostic
+            .help
+            .as_deref()
+            .map_or_else(|| "null".into(), json_string)
+    );
+
+    output
+}
+
+/// Encodes `string` as a JSON string literal.
+fn json_string(string: &str) -> String {
+    let mut output = String::with_capacity(string.len() + 2);
+    output.push('"');
+
+    for c in string.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => _ = write!(output, "\\u{:04x}", c as u32),
+            c => output.push(c),
+        }
+    }
+
+    output.push('"');
+    output
+}
@@ -0,0 +1,88 @@
+#![allow(non_snake_case)]
+
+mod Format {
+    use super::super::*;
+    use crate::SourceFile;
+
+    #[test]
+    fn render_plain() {
+        let diagnostic = Diagnostic::error("unclosed delimiter; expected `)`")
+            .with_primary(14..14, "")
+            .with_secondary(4..5, "unclosed delimiter")
+            .with_help("add `)`");
+
+        assert_eq!(
+            Format::Plain.render(
+                &diagnostic,
+                &SourceFile::new("main.vn", "x = (1,\n  2, 3\n")
+            ),
+            "error: unclosed delimiter; expected `)`
+ --> main.vn:2:7
+  |
+1 | x = (1,
+  |     - unclosed delimiter
+2 |   2, 3
+  |       ^
+  |
+  = help: add `)`
+"
+        );
+    }
+
+    #[test]
+    fn render_columns() {
+        let diagnostic = Diagnostic::warning("unused").with_primary(9..12, "here");
+
+        // Columns count characters, tabs are expanded.
+        assert_eq!(
+            Format::Plain.render(&diagnostic, &SourceFile::new("a.vn", "\tä = 1\n\tfoo\n")),
+            "warning: unused
+ --> a.vn:2:2
+  |
+2 |     foo
+  |     ^^^ here
+"
+        );
+    }
+
+    #[test]
+    fn render_line_break_and_end_of_file() {
+        let file = SourceFile::new("a.vn", "ab\r\ncd");
+
+        // Labels in a line break or past the end are placed after the last character.
+        for (range, expected) in [
+            (3..4, "1:3\n  |\n1 | ab\n  |   ^\n"),
+            (3..3, "1:3\n  |\n1 | ab\n  |   ^\n"),
+            (7..9, "2:3\n  |\n2 | cd\n  |   ^\n"),
+            (100..101, "2:3\n  |\n2 | cd\n  |   ^\n"),
+        ] {
+            let diagnostic = Diagnostic::error("bad").with_primary(range, "");
+
+            assert_eq!(
+                Format::Plain.render(&diagnostic, &file),
+                format!("error: bad\n --> a.vn:{expected}")
+            );
+        }
+    }
+
+    #[test]
+    fn render_colored() {
+        let diagnostic = Diagnostic::error("bad").with_primary(0..1, "");
+        let output = Format::Colored.render(&diagnostic, &SourceFile::new("a.vn", "x"));
+
+        assert!(output.starts_with("\x1b[1;31merror"));
+        assert!(output.contains("\x1b[1;31m^\x1b[0m"));
+    }
+
+    #[test]
+    fn render_json() {
+        let diagnostic = Diagnostic::error("expected `\"`")
+            .with_primary(2..3, "here")
+            .with_note("a note");
+
+        assert_eq!(
+            Format::Json.render(&diagnostic, &SourceFile::new("a.vn", "a\nbc")),
+            r#"{"severity":"error","message":"expected `\"`","file":"a.vn","labels":[{"style":"primary","start":2,"end":3,"line":2,"column":1,"message":"here"}],"notes":["a note"],"help":null}"#
+        );
+    }
+}
@@ -0,0 +1,236 @@
+#[cfg(test)]
+mod tests;
+
+use std::ops::Range;
+
+/// A position in a [`SourceFile`]. All fields are zero-based.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: u32,
+
+    /// The column in UTF-8 code units (bytes).
+    pub utf8_column: u32,
+
+    /// The column in UTF-16 code units, as used by the language server protocol.
+    pub utf16_column: u32,
+}
+
+/// A named source text with an index of its line starts.
+///
+/// Offsets passed to and returned from a [`SourceFile`] are relative to the start of the file,
+/// which is what the lexer produces. Use [`SourceFile::start`] to convert them to offsets
+/// in a [`SourceMap`].
+#[derive(Clone, Debug)]
+pub struct SourceFile {
+    name: String,
+    source: String,
+
+    /// The global offset of the file in its [`SourceMap`].
+    start: u32,
+
+    /// The byte offset of the start of each line. The first line always starts at zero.
+    line_starts: Vec<u32>,
+}
+
+impl SourceFile {
+    /// Creates a new file and indexes its lines.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source does not fit into `u32` offsets.
+    pub fn new(name: impl Into<String>, source: impl Into<String>) -> Self {
+        let source = source.into();
+        assert!(source.len() <= u32::MAX as usize, "source file too large");
+
+        let line_starts = [0]
+            .into_iter()
+            .chain(
+                source
+                    .match_indices('\n')
+                    .map(|(index, _)| index as u32 + 1),
+            )
+            .collect();
+
+        Self {
+            name: name.into(),
+            source,
+            start: 0,
+            line_starts,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The global offset of the file in its [`SourceMap`], or zero for standalone files.
+    pub const fn start(&self) -> u32 {
+        self.start
+    }
+
+    pub fn len(&self) -> u32 {
+        self.source.len() as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.source.is_empty()
+    }
+
+    pub fn line_count(&self) -> u32 {
+        self.line_starts.len() as u32
+    }
+
+    /// Returns the zero-based line containing `offset`.
+    /// Offsets past the end are clamped to the end of the file.
+    pub fn line_index(&self, offset: u32) -> u32 {
+        let offset = offset.min(self.len());
+
+        match self.line_starts.binary_search(&offset) {
+            Ok(line) => line as u32,
+            Err(next_line) => next_line as u32 - 1,
+        }
+    }
+
+    /// Returns the byte range of `line`, excluding its line break.
+    pub fn line_range(&self, line: u32) -> Option<Range<u32>> {
+        let start = *self.line_starts.get(line as usize)?;
+        let end = self
+            .line_starts
+            .get(line as usize + 1)
+            .map_or(self.len(), |next| next - 1);
+        let end = if end > start && self.source.as_bytes()[end as usize - 1] == b'\r' {
+            end - 1
+        } else {
+            end
+        };
+
+        Some(start..end)
+    }
+
+    /// Returns the text of `line`, excluding its line break.
+    pub fn line_text(&self, line: u32) -> Option<&str> {
+        let range = self.line_range(line)?;
+        Some(&self.source[range.start as usize..range.end as usize])
+    }
+
+    /// Converts an offset into a [`Position`].
+    /// Offsets past the end are clamped to the end of the file.
+    ///
+    /// An offset inside a line break is placed after the last character of its line.
+    pub fn position(&self, offset: u32) -> Position {
+        let line = self.line_index(offset);
+        let range = self.line_range(line).unwrap();
+        let offset = offset.clamp(range.start, range.end);
+
+        Position {
+            line,
+            utf8_column: offset - range.start,
+            utf16_column: self.source[range.start as usize..offset as usize]
+                .encode_utf16()
+                .count() as u32,
+        }
+    }
+
+    /// Converts a line and UTF-8 column back into an offset.
+    ///
+    /// Returns [`None`] if the position is not on a character boundary within the line.
+    pub fn offset(&self, line: u32, utf8_column: u32) -> Option<u32> {
+        let range = self.line_range(line)?;
+        let offset = range.start.checked_add(utf8_column)?;
+
+        (offset <= range.end && self.source.is_char_boundary(offset as usize)).then_some(offset)
+    }
+
+    /// Converts a line and UTF-16 column back into an offset.
+    ///
+    /// Returns [`None`] if the position is past the end of the line or
+    /// between the two halves of a surrogate pair.
+    pub fn offset_utf16(&self, line: u32, utf16_column: u32) -> Option<u32> {
+        let range = self.line_range(line)?;
+        let mut column = 0;
+
+        for (index, c) in self.source[range.start as usize..range.end as usize].char_indices() {
+            if column == utf16_column {
+                return Some(range.start + index as u32);
+            } else if column > utf16_column {
+                return None;
+            }
+
+            column += c.len_utf16() as u32;
+        }
+
+        (column == utf16_column).then_some(range.end)
+    }
+}
+
+/// Identifies a [`SourceFile`] in a [`SourceMap`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileId(u32);
+
+/// A collection of source files sharing one global offset space.
+///
+/// Each file occupies the global range `start..start + len`. Files are separated
+/// by one unused offset, so the end of one file is never the start of the next.
+#[derive(Clone, Debug, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file and returns its id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the global offsets overflow `u32`.
+    pub fn add(&mut self, mut file: SourceFile) -> FileId {
+        file.start = self.files.last().map_or(0, |last| {
+            last.start
+                .checked_add(last.len())
+                .and_then(|end| end.checked_add(1))
+                .expect("source map too large")
+        });
+        file.start
+            .checked_add(file.len())
+            .expect("source map too large");
+
+        self.files.push(file);
+        FileId(self.files.len() as u32 - 1)
+    }
+
+    pub fn file(&self, id: FileId) -> &SourceFile {
+        &self.files[id.0 as usize]
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = (FileId, &SourceFile)> {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| (FileId(index as u32), file))
+    }
+
+    /// Converts an offset relative to the file `id` into a global offset.
+    pub fn global_offset(&self, id: FileId, offset: u32) -> u32 {
+        self.file(id).start + offset
+    }
+
+    /// Finds the file containing the global offset and
+    /// returns its id and the offset relative to it.
+    pub fn lookup(&self, global_offset: u32) -> Option<(FileId, u32)> {
+        let index = self
+            .files
+            .partition_point(|file| file.start <= global_offset)
+            .checked_sub(1)?;
+        let file = &self.files[index];
+        let offset = global_offset - file.start;
+
+        (offset <= file.len()).then_some((FileId(index as u32), offset))
+    }
+}
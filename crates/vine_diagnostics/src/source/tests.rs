@@ -0,0 +1,86 @@
+#![allow(non_snake_case)]
+
+mod SourceFile {
+    use super::super::*;
+
+    fn at(line: u32, utf8_column: u32, utf16_column: u32) -> Position {
+        Position {
+            line,
+            utf8_column,
+            utf16_column,
+        }
+    }
+
+    #[test]
+    fn line_index() {
+        let file = SourceFile::new("a.vn", "ab\ncd\r\n\nx");
+
+        assert_eq!(file.line_count(), 4);
+        assert_eq!(file.line_index(0), 0);
+        assert_eq!(file.line_index(2), 0);
+        assert_eq!(file.line_index(3), 1);
+        assert_eq!(file.line_index(8), 3);
+        assert_eq!(file.line_index(100), 3);
+    }
+
+    #[test]
+    fn line_text() {
+        let file = SourceFile::new("a.vn", "ab\ncd\r\n\nx");
+
+        assert_eq!(file.line_text(0), Some("ab"));
+        assert_eq!(file.line_text(1), Some("cd"));
+        assert_eq!(file.line_text(2), Some(""));
+        assert_eq!(file.line_text(3), Some("x"));
+        assert_eq!(file.line_text(4), None);
+    }
+
+    #[test]
+    fn position() {
+        // `ä` is two UTF-8 and one UTF-16 code unit, `𝕏` is four and two.
+        let file = SourceFile::new("a.vn", "x\nä𝕏y\r\n");
+
+        assert_eq!(file.position(0), at(0, 0, 0));
+        assert_eq!(file.position(2), at(1, 0, 0));
+        assert_eq!(file.position(4), at(1, 2, 1));
+        assert_eq!(file.position(8), at(1, 6, 3));
+        assert_eq!(file.position(9), at(1, 7, 4));
+        assert_eq!(file.position(10), at(1, 7, 4));
+        assert_eq!(file.position(11), at(2, 0, 0));
+    }
+
+    #[test]
+    fn offset() {
+        let file = SourceFile::new("a.vn", "x\nä𝕏y\r\n");
+
+        assert_eq!(file.offset(1, 2), Some(4));
+        assert_eq!(file.offset(1, 1), None);
+        assert_eq!(file.offset(1, 7), Some(9));
+        assert_eq!(file.offset(1, 8), None);
+        assert_eq!(file.offset(5, 0), None);
+
+        assert_eq!(file.offset_utf16(1, 1), Some(4));
+        assert_eq!(file.offset_utf16(1, 2), None);
+        assert_eq!(file.offset_utf16(1, 3), Some(8));
+        assert_eq!(file.offset_utf16(1, 4), Some(9));
+        assert_eq!(file.offset_utf16(1, 5), None);
+    }
+}
+
+mod SourceMap {
+    use super::super::*;
+
+    #[test]
+    fn lookup() {
+        let mut map = SourceMap::new();
+        let a = map.add(SourceFile::new("a.vn", "abc"));
+        let b = map.add(SourceFile::new("b.vn", "de"));
+
+        assert_eq!(map.file(b).start(), 4);
+        assert_eq!(map.global_offset(b, 1), 5);
+        assert_eq!(map.lookup(0), Some((a, 0)));
+        assert_eq!(map.lookup(3), Some((a, 3)));
+        assert_eq!(map.lookup(4), Some((b, 0)));
+        assert_eq!(map.lookup(6), Some((b, 2)));
+        assert_eq!(map.lookup(7), None);
+    }
+}
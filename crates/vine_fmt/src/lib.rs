@@ -0,0 +1,296 @@
+//! The Vine code formatter.
+//!
+//! The formatter prints the [`SyntaxTree`] of a source file in a canonical layout:
+//! `match` cases, multi-line `if`/`then`/`else` branches and the items of multi-line
+//! groups are put on their own lines and indented by four spaces. Comments are kept,
+//! and so is a single blank line between items. Formatting is idempotent.
+
+#[cfg(test)]
+mod tests;
+
+use vine_diagnostics::Diagnostic;
+use vine_lex::{filter::FilteredTokenKind, tokens::Token};
+use vine_parse::cst::{NodeKind, SyntaxElement, SyntaxNode, SyntaxTree};
+
+const INDENT: &str = "    ";
+
+/// Formats a source file.
+///
+/// Source with syntax errors is not formatted; the errors are returned instead.
+pub fn format(source: &str) -> Result<String, Vec<Diagnostic>> {
+    let (tree, diagnostics) = SyntaxTree::parse(source);
+
+    if diagnostics.is_empty() {
+        Ok(format_tree(&tree))
+    } else {
+        Err(diagnostics)
+    }
+}
+
+/// Formats a syntax tree.
+///
+/// The line endings of the output are those of the first line break in the tree.
+/// Nodes of trees with syntax errors that miss some of their children are
+/// written verbatim.
+pub fn format_tree(tree: &SyntaxTree) -> String {
+    let source = tree.to_string();
+
+    let mut formatter = Formatter {
+        output: String::with_capacity(source.len()),
+        line_ending: match source.find('\n') {
+            Some(index) if source[..index].ends_with('\r') => "\r\n",
+            _ => "\n",
+        },
+        line_indent: 0,
+        break_pending: false,
+    };
+
+    formatter.node(tree.root());
+    formatter.finish()
+}
+
+/// What is written before an element of a node.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Separator {
+    /// The element is not written at all.
+    Drop,
+    None,
+    Space,
+
+    /// A line break, followed by the indentation of the node plus the given levels.
+    Line(usize),
+}
+
+struct Formatter {
+    output: String,
+    line_ending: &'static str,
+
+    /// The indentation level of the current line.
+    line_indent: usize,
+
+    /// Whether a line comment was written, so the next element must start a new line.
+    break_pending: bool,
+}
+
+impl Formatter {
+    fn node(&mut self, node: SyntaxNode) {
+        let Some(layout) = layout(node) else {
+            self.output.push_str(&node.green().to_string());
+            return;
+        };
+
+        let base = self.line_indent;
+        let mut index = 0;
+        let mut line_breaks = 0;
+
+        for element in node.children() {
+            match element {
+                SyntaxElement::Token(token) if matches!(token.token(), Token::Whitespace(_)) => {
+                    line_breaks += token.text().matches('\n').count();
+                }
+                SyntaxElement::Token(token) if let Token::Comment(comment) = token.token() => {
+                    // Comments on their own line are indented like the surrounding lines.
+                    let levels = |separator: &Separator| match separator {
+                        Separator::Line(levels) => Some(*levels),
+                        _ => None,
+                    };
+                    let indent = base
+                        + layout
+                            .get(index)
+                            .and_then(levels)
+                            .max(layout[..index].iter().rev().find_map(levels))
+                            .unwrap_or(0);
+
+                    self.comment(comment, indent, line_breaks);
+                    line_breaks = 0;
+                }
+                element => {
+                    let separator = layout[index];
+                    index += 1;
+
+                    match separator {
+                        Separator::Drop => continue,
+                        Separator::Line(levels) => self.line_break(base + levels, line_breaks > 1),
+                        // A line comment cannot be followed by anything on the same line.
+                        _ if self.break_pending => self.line_break(base + 1, false),
+                        Separator::Space => self.output.push(' '),
+                        Separator::None => {}
+                    }
+
+                    line_breaks = 0;
+
+                    match element {
+                        SyntaxElement::Node(node) => self.node(node),
+                        SyntaxElement::Token(token) => self.output.push_str(token.text()),
+                    }
+                }
+            }
+        }
+
+        self.line_indent = base;
+    }
+
+    fn comment(&mut self, comment: &str, indent: usize, line_breaks: usize) {
+        if line_breaks > 0 || self.break_pending {
+            self.line_break(indent, line_breaks > 1);
+        } else if !self.output.is_empty() {
+            self.output.push(' ');
+        }
+
+        self.output.push_str(comment);
+        self.break_pending = !comment.starts_with("/*");
+    }
+
+    /// Starts a new line with the given indentation level, optionally after a blank line.
+    /// Nothing is written at the start of the file.
+    fn line_break(&mut self, indent: usize, blank: bool) {
+        self.break_pending = false;
+
+        if self.output.is_empty() {
+            return;
+        }
+
+        self.output
+            .truncate(self.output.trim_end_matches(' ').len());
+        self.output.push_str(self.line_ending);
+
+        if blank {
+            self.output.push_str(self.line_ending);
+        }
+
+        for _ in 0..indent {
+            self.output.push_str(INDENT);
+        }
+
+        self.line_indent = indent;
+    }
+
+    fn finish(mut self) -> String {
+        self.output.truncate(self.output.trim_end().len());
+
+        if !self.output.is_empty() {
+            self.output.push_str(self.line_ending);
+        }
+
+        self.output
+    }
+}
+
+/// Returns the [`Separator`] for each element of `node` that is not trivia, or
+/// [`None`] if the node misses elements that its kind has without syntax errors.
+fn layout(node: SyntaxNode) -> Option<Vec<Separator>> {
+    let elements = node
+        .children()
+        .filter(|element| match element {
+            SyntaxElement::Node(_) => true,
+            SyntaxElement::Token(token) => !token.green().is_trivia(),
+        })
+        .collect::<Vec<_>>();
+
+    let text = |index: usize| match elements[index] {
+        SyntaxElement::Token(token) => token.text(),
+        SyntaxElement::Node(_) => "",
+    };
+
+    let mut layout = vec![Separator::Space; elements.len()];
+
+    match node.kind() {
+        NodeKind::Module => layout.fill(Separator::Line(0)),
+        NodeKind::Grouped | NodeKind::AttributeArguments => {
+            let multi_line = contains_line_break(node) && node.child_nodes().next().is_some();
+
+            for (index, element) in elements.iter().enumerate() {
+                layout[index] = match element {
+                    SyntaxElement::Node(_) if multi_line => Separator::Line(1),
+                    SyntaxElement::Token(_) if multi_line && text(index) == "," => Separator::Drop,
+                    SyntaxElement::Token(_) if multi_line && index == elements.len() - 1 => {
+                        Separator::Line(0)
+                    }
+                    _ if index == 0 => Separator::None,
+                    // The brace of `set {`.
+                    _ if index == 1 && text(0) == "set" => Separator::Space,
+                    SyntaxElement::Node(_) if text(index - 1) == "," => Separator::Space,
+                    _ => Separator::None,
+                };
+            }
+        }
+        // `- -x` must not become `--x`.
+        NodeKind::Unary
+            if node
+                .child_nodes()
+                .next()
+                .is_some_and(|child| child.kind() == NodeKind::Unary) => {}
+        NodeKind::Unary | NodeKind::Attribute => layout.fill(Separator::None),
+        NodeKind::Binary => {
+            let operator = (1..elements.len().checked_sub(1)?)
+                .map(text)
+                .collect::<String>();
+
+            if matches!(operator.as_str(), "." | "::") {
+                layout.fill(Separator::None);
+            }
+        }
+        NodeKind::Call if !has_trivia_before(node, 1) => *layout.get_mut(1)? = Separator::None,
+        NodeKind::If if contains_line_break(node) => {
+            for (index, separator) in layout.iter_mut().enumerate() {
+                if matches!(text(index), "then" | "else") {
+                    *separator = Separator::Line(1);
+                }
+            }
+        }
+        NodeKind::Match => {
+            // The cases.
+            layout.get_mut(2..)?.fill(Separator::Line(1));
+        }
+        NodeKind::Attributed if contains_line_break(node) => {
+            *layout.get_mut(1)? = Separator::Line(0);
+        }
+        _ => {}
+    }
+
+    if let Some(first) = layout.first_mut()
+        && *first == Separator::Space
+    {
+        *first = Separator::None;
+    }
+
+    // Raw tokens that the token filter combines, like the `=` and `>` of `=>`, stay together.
+    for index in 1..elements.len() {
+        if let (SyntaxElement::Token(previous), SyntaxElement::Token(token)) =
+            (elements[index - 1], elements[index])
+            && previous.range().end == token.range().start
+            && FilteredTokenKind::try_from_trivial(previous.token()).is_none()
+            && FilteredTokenKind::try_from_trivial(token.token()).is_none()
+        {
+            layout[index] = Separator::None;
+        }
+    }
+
+    Some(layout)
+}
+
+/// Whether the trivia directly in `node` contains a line break.
+fn contains_line_break(node: SyntaxNode) -> bool {
+    node.children().any(|element| {
+        matches!(element, SyntaxElement::Token(token) if token.green().is_trivia() && token.text().contains('\n'))
+    })
+}
+
+/// Whether there is trivia before the significant element at `index`.
+fn has_trivia_before(node: SyntaxNode, index: usize) -> bool {
+    let mut significant = 0;
+    let mut trivia = false;
+
+    for element in node.children() {
+        match element {
+            SyntaxElement::Token(token) if token.green().is_trivia() => trivia = true,
+            _ if significant == index => return trivia,
+            _ => {
+                significant += 1;
+                trivia = false;
+            }
+        }
+    }
+
+    false
+}
@@ -0,0 +1,104 @@
+#![allow(non_snake_case)]
+
+mod Formatter {
+    use super::super::*;
+
+    const FINITE: &str = include_str!("../../../language-design/finite.vn");
+
+    /// Formats `input` and checks that formatting the output again does not change it.
+    fn formatted(input: &str) -> String {
+        let output = format(input).unwrap();
+        assert_eq!(format(&output).unwrap(), output, "not idempotent");
+        output
+    }
+
+    #[test]
+    fn format_finite() {
+        assert_eq!(formatted(FINITE), FINITE);
+    }
+
+    #[test]
+    fn format_spacing() {
+        assert_eq!(
+            formatted("a=b+  c*-d\nx   `max`y\nf(x)\nf   x\na . b\na..b\na==b"),
+            "a = b + c * -d\nx `max` y\nf(x)\nf x\na.b\na .. b\na == b\n"
+        );
+        assert_eq!(
+            formatted("@infix( left,3 )\nmax=function a=>b\n@doc   abstract T"),
+            "@infix(left, 3)\nmax = function a => b\n@doc abstract T\n"
+        );
+    }
+
+    #[test]
+    fn format_groups() {
+        assert_eq!(
+            formatted("x = ( a,b ,c )\ny = set{ 1, 2 }\nz = [ ]"),
+            "x = (a, b, c)\ny = set {1, 2}\nz = []\n"
+        );
+        assert_eq!(
+            formatted("x = (a,\n      b = [1,\n 2])"),
+            "x = (\n    a\n    b = [\n        1\n        2\n    ]\n)\n"
+        );
+    }
+
+    #[test]
+    fn format_match() {
+        assert_eq!(
+            formatted(
+                "f = function x is Number => match x\ncase 0 => a\n        case n => match n\n  case 1 => b"
+            ),
+            "f = function x is Number => match x\n    case 0 => a\n    case n => match n\n        case 1 => b\n"
+        );
+    }
+
+    #[test]
+    fn format_if() {
+        assert_eq!(formatted("if a  then b  else c"), "if a then b else c\n");
+        assert_eq!(
+            formatted("x = if a\nthen b\n        else (c,\nd)"),
+            "x = if a\n    then b\n    else (\n        c\n        d\n    )\n"
+        );
+    }
+
+    #[test]
+    fn format_comments() {
+        assert_eq!(
+            formatted(
+                "// header\n\n\n\na = 1 # trailing\n   /* block */ b = (\n  // inside\n  c // after c\n  // last\n)\n\n\n\nd\n// end\n"
+            ),
+            "// header\n\na = 1 # trailing\n/* block */\nb = (\n    // inside\n    c // after c\n    // last\n)\n\nd\n// end\n"
+        );
+        assert_eq!(formatted("a + // why\n b"), "a + // why\n    b\n");
+    }
+
+    #[test]
+    fn format_line_endings() {
+        assert_eq!(formatted("a\r\n\r\n\r\nb  "), "a\r\n\r\nb\r\n");
+        assert_eq!(formatted(""), "");
+        assert_eq!(formatted("\n \n"), "");
+    }
+
+    #[test]
+    fn format_invalid() {
+        assert!(format("a = (").is_err());
+    }
+
+    #[test]
+    fn format_tree_with_errors() {
+        let significant = |text: &str| text.split_whitespace().collect::<String>();
+
+        for source in [
+            "match x\n  case",
+            "a = (b +\nc = ]",
+            "`",
+            "@ ) ) x",
+            "f(\n",
+            "- -",
+        ] {
+            let (tree, diagnostics) = SyntaxTree::parse(source);
+
+            assert!(!diagnostics.is_empty());
+            assert_eq!(significant(&format_tree(&tree)), significant(source));
+        }
+    }
+}
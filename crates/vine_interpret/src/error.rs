@@ -0,0 +1,149 @@
+use std::ops::Range;
+
+use vine_diagnostics::Diagnostic;
+
+/// An error that stops the evaluation. The values are written in Vine syntax.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuntimeError {
+    /// A part of the program that had an error when it was lowered.
+    Error {
+        range: Range<u32>,
+    },
+
+    /// An identifier that is not defined.
+    Undefined {
+        name: String,
+        range: Range<u32>,
+    },
+
+    /// A module-level definition that depends on its own value.
+    Cycle {
+        name: String,
+        range: Range<u32>,
+    },
+
+    /// A match without a matching case, or a definition whose pattern
+    /// does not match its value.
+    NoMatch {
+        value: String,
+        range: Range<u32>,
+    },
+
+    /// An argument that is not accepted by the parameter of the function.
+    OutsideDomain {
+        argument: String,
+        range: Range<u32>,
+    },
+
+    /// A call of a value that is not a function.
+    NotAFunction {
+        found: String,
+        range: Range<u32>,
+    },
+
+    /// A condition or operand that must be `True` or `False`.
+    NotABoolean {
+        found: String,
+        range: Range<u32>,
+    },
+
+    /// A value after `is` or `in` that does not denote a set of values.
+    NotADomain {
+        found: String,
+        range: Range<u32>,
+    },
+
+    /// An access of a field that the value does not have.
+    NoField {
+        name: String,
+        found: String,
+        range: Range<u32>,
+    },
+
+    /// An operator applied to operands it is not defined for.
+    UnsupportedOperation {
+        operator: String,
+        operands: String,
+        range: Range<u32>,
+    },
+
+    DivisionByZero {
+        range: Range<u32>,
+    },
+
+    /// A call nested in more than [`MAX_CALL_DEPTH`] other calls.
+    ///
+    /// [`MAX_CALL_DEPTH`]: crate::MAX_CALL_DEPTH
+    TooDeep {
+        range: Range<u32>,
+    },
+}
+
+impl RuntimeError {
+    pub fn range(&self) -> Range<u32> {
+        match self {
+            Self::Error { range }
+            | Self::Undefined { range, .. }
+            | Self::Cycle { range, .. }
+            | Self::NoMatch { range, .. }
+            | Self::OutsideDomain { range, .. }
+            | Self::NotAFunction { range, .. }
+            | Self::NotABoolean { range, .. }
+            | Self::NotADomain { range, .. }
+            | Self::NoField { range, .. }
+            | Self::UnsupportedOperation { range, .. }
+            | Self::DivisionByZero { range }
+            | Self::TooDeep { range } => range.clone(),
+        }
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let range = self.range();
+
+        match self {
+            Self::Error { .. } => Diagnostic::error("evaluated a part of the program with errors")
+                .with_primary(range, "")
+                .with_note("the error has been reported before running the program"),
+            Self::Undefined { name, .. } => {
+                Diagnostic::error(format!("`{name}` is not defined")).with_primary(range, "")
+            }
+            Self::Cycle { name, .. } => {
+                Diagnostic::error(format!("`{name}` depends on its own value"))
+                    .with_primary(range, "used here while it is evaluated")
+            }
+            Self::NoMatch { value, .. } => Diagnostic::error("no pattern matched the value")
+                .with_primary(range, format!("this is `{value}`")),
+            Self::OutsideDomain { argument, .. } => {
+                Diagnostic::error("argument outside the domain of the function")
+                    .with_primary(range, format!("this is `{argument}`"))
+            }
+            Self::NotAFunction { found, .. } => {
+                Diagnostic::error("called a value that is not a function")
+                    .with_primary(range, format!("this is `{found}`"))
+            }
+            Self::NotABoolean { found, .. } => Diagnostic::error("expected `True` or `False`")
+                .with_primary(range, format!("this is `{found}`")),
+            Self::NotADomain { found, .. } => Diagnostic::error("expected a domain")
+                .with_primary(range, format!("this is `{found}`"))
+                .with_note(
+                    "domains are sets, builtin types, abstract values, tuples and records \
+                     of domains and functions returning `True` or `False`",
+                ),
+            Self::NoField { name, found, .. } => Diagnostic::error(format!("no field `{name}`"))
+                .with_primary(range, format!("`{found}` has no field `{name}`")),
+            Self::UnsupportedOperation {
+                operator, operands, ..
+            } => Diagnostic::error(format!("`{operator}` is not defined for {operands}"))
+                .with_primary(range, ""),
+            Self::DivisionByZero { .. } => {
+                Diagnostic::error("division by zero").with_primary(range, "")
+            }
+            Self::TooDeep { .. } => Diagnostic::error("too many nested calls")
+                .with_primary(range, "")
+                .with_note(format!(
+                    "calls can be nested at most {} times",
+                    crate::MAX_CALL_DEPTH
+                )),
+        }
+    }
+}
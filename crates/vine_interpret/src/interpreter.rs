@@ -0,0 +1,864 @@
+use std::{cell::RefCell, collections::BTreeMap, ops::Range, rc::Rc};
+
+use parser_tools::Span;
+use vine_num::{Natural, Rational};
+use vine_parse::{
+    BUILTIN_OPERATORS,
+    ast::{BinaryOperation, UnaryOperation},
+    lower::{Core, Pattern},
+};
+
+use crate::{error::RuntimeError, value::*};
+
+/// The maximum number of nested function calls, reported as
+/// [`RuntimeError::TooDeep`] instead of overflowing the stack.
+pub const MAX_CALL_DEPTH: usize = 10_000;
+
+/// The size of the stack that evaluating [`MAX_CALL_DEPTH`] nested calls needs,
+/// with room to spare for nested expressions in the functions.
+pub const STACK_SIZE: usize = 1 << 30;
+
+/// Evaluates lowered expressions against the module-level definitions,
+/// which can be added one by one.
+pub struct Interpreter<'a> {
+    globals: BTreeMap<&'a str, Global<'a>>,
+
+    /// The number of atoms created so far.
+    atoms: u32,
+
+    /// The number of function calls being evaluated.
+    depth: usize,
+}
+
+struct Global<'a> {
+    kind: GlobalKind<'a>,
+    state: State<'a>,
+}
+
+#[derive(Clone)]
+enum GlobalKind<'a> {
+    /// An atom or a builtin, which patterns match instead of binding the name.
+    Abstract,
+
+    /// A definition binding the name with its pattern.
+    Definition {
+        pattern: &'a Span<Pattern<'a>>,
+        value: &'a Span<Core<'a>>,
+    },
+
+    /// The values of the `@merge` definitions of the name.
+    Merged(Vec<&'a Span<Core<'a>>>),
+}
+
+enum State<'a> {
+    Unevaluated,
+    Evaluating,
+    Evaluated(Value<'a>),
+}
+
+/// The local bindings visible to an expression.
+#[derive(Clone, Default)]
+pub(crate) struct Environment<'a>(Option<Rc<Scope<'a>>>);
+
+struct Scope<'a> {
+    bindings: RefCell<Vec<Binding<'a>>>,
+    parent: Environment<'a>,
+}
+
+struct Binding<'a> {
+    name: &'a str,
+    value: Value<'a>,
+
+    /// Whether the name was declared with `abstract`.
+    is_abstract: bool,
+}
+
+type Result<'a> = core::result::Result<Value<'a>, RuntimeError>;
+
+impl<'a> Environment<'a> {
+    fn child(&self) -> Self {
+        Self(Some(Rc::new(Scope {
+            bindings: RefCell::new(Vec::new()),
+            parent: self.clone(),
+        })))
+    }
+
+    /// Returns the value of the innermost binding of `name` and whether it is abstract.
+    fn get(&self, name: &str) -> Option<(Value<'a>, bool)> {
+        let mut environment = self;
+
+        while let Some(scope) = &environment.0 {
+            if let Some(binding) = scope
+                .bindings
+                .borrow()
+                .iter()
+                .rev()
+                .find(|binding| binding.name == name)
+            {
+                return Some((binding.value.clone(), binding.is_abstract));
+            }
+
+            environment = &scope.parent;
+        }
+
+        None
+    }
+
+    /// Adds bindings to the innermost scope.
+    fn define(&self, bindings: impl IntoIterator<Item = Binding<'a>>) {
+        self.0
+            .as_ref()
+            .expect("the environment has a scope")
+            .bindings
+            .borrow_mut()
+            .extend(bindings);
+    }
+}
+
+impl Default for Interpreter<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Interpreter<'a> {
+    /// Creates an interpreter that knows only the builtins.
+    pub fn new() -> Self {
+        let mut interpreter = Self {
+            globals: BTreeMap::new(),
+            atoms: 0,
+            depth: 0,
+        };
+
+        for name in ["True", "False"] {
+            let atom = interpreter.atom(name);
+            interpreter.constant(name, atom);
+        }
+
+        for builtin in [
+            Builtin::Number,
+            Builtin::String,
+            Builtin::Character,
+            Builtin::Boolean,
+        ] {
+            interpreter.constant(builtin.name(), Value::Builtin(builtin));
+        }
+
+        interpreter
+    }
+
+    /// Whether the module-level item is a definition or an abstract declaration,
+    /// which [`Interpreter::define`] adds instead of evaluating them.
+    pub fn is_definition(item: &Span<Core>) -> bool {
+        matches!(
+            Core::unattributed(item).value,
+            Core::Definition { .. } | Core::Abstract { .. }
+        )
+    }
+
+    /// Adds a module-level definition or abstract declaration, replacing previous
+    /// definitions of the same names. `@merge` definitions are added to the previous
+    /// `@merge` definitions of the name instead. Other items are ignored.
+    pub fn define(&mut self, item: &'a Span<Core<'a>>) {
+        let merge = Core::attributes(item).any(|attribute| attribute.value.name.value == "merge");
+
+        match &Core::unattributed(item).value {
+            Core::Definition { pattern, value } => {
+                if merge && let Pattern::Binding(name) = pattern.value {
+                    let mut values = match self.globals.remove(name) {
+                        Some(Global {
+                            kind: GlobalKind::Merged(values),
+                            ..
+                        }) => values,
+                        _ => Vec::new(),
+                    };
+                    values.push(value);
+
+                    self.globals.insert(
+                        name,
+                        Global {
+                            kind: GlobalKind::Merged(values),
+                            state: State::Unevaluated,
+                        },
+                    );
+                } else {
+                    for name in Pattern::bound_names(pattern) {
+                        self.globals.insert(
+                            name.value,
+                            Global {
+                                kind: GlobalKind::Definition { pattern, value },
+                                state: State::Unevaluated,
+                            },
+                        );
+                    }
+                }
+            }
+            Core::Abstract { name } => {
+                let atom = self.atom(name.value);
+                self.constant(name.value, atom);
+            }
+            _ => {}
+        }
+    }
+
+    /// Evaluates a module-level definition again on its next use,
+    /// like after a definition it refers to was replaced.
+    pub fn forget(&mut self, name: &str) {
+        if let Some(global) = self.globals.get_mut(name)
+            && !matches!(global.kind, GlobalKind::Abstract)
+        {
+            global.state = State::Unevaluated;
+        }
+    }
+
+    /// Returns the value of a module-level definition.
+    pub fn global(&mut self, name: &str) -> Option<Result<'a>> {
+        let range = match &self.globals.get(name)?.kind {
+            GlobalKind::Definition { value, .. } => value.range.clone(),
+            GlobalKind::Merged(values) => values[0].range.clone(),
+            GlobalKind::Abstract => 0..0,
+        };
+
+        self.global_value(name, range)
+    }
+
+    /// Evaluates an expression at module level.
+    pub fn evaluate(&mut self, expression: &'a Span<Core<'a>>) -> Result<'a> {
+        self.expression(expression, &Environment::default().child())
+    }
+
+    fn expression(
+        &mut self,
+        expression: &'a Span<Core<'a>>,
+        environment: &Environment<'a>,
+    ) -> Result<'a> {
+        let range = expression.range.clone();
+
+        Ok(match &expression.value {
+            Core::Error => return Err(RuntimeError::Error { range }),
+            Core::Literal(literal) => Value::from_literal(literal),
+            Core::Identifier(name) => {
+                if let Some((value, _)) = environment.get(name) {
+                    value
+                } else if let Some(value) = self.global_value(name, range.clone()) {
+                    value?
+                } else {
+                    return Err(RuntimeError::Undefined {
+                        name: name.to_string(),
+                        range,
+                    });
+                }
+            }
+            Core::Tuple(items) => Value::Tuple(self.expressions(items, environment)?.into()),
+            Core::Record(fields) => Value::record(
+                fields
+                    .iter()
+                    .map(|(name, value)| Ok((name.value, self.expression(value, environment)?)))
+                    .collect::<core::result::Result<_, _>>()?,
+            ),
+            Core::Set(elements) => Value::set(self.expressions(elements, environment)?),
+            Core::List(items) => Value::List(self.expressions(items, environment)?.into()),
+            Core::Block(items) => {
+                let scope = environment.child();
+                let mut value = Value::Tuple(Rc::new([]));
+
+                for item in items {
+                    value = self.expression(item, &scope)?;
+                }
+
+                value
+            }
+            Core::Definition { pattern, value } => {
+                let value = self.expression(value, environment)?;
+                let mut bindings = Vec::new();
+
+                if !self.matches(pattern, &value, environment, &mut bindings)? {
+                    return Err(RuntimeError::NoMatch {
+                        value: value.to_string(),
+                        range: pattern.range.clone(),
+                    });
+                }
+
+                environment.define(bindings);
+                value
+            }
+            Core::Unary { operation, operand } => {
+                let value = self.expression(operand, environment)?;
+
+                match (operation, &value) {
+                    (UnaryOperation::Negate, Value::Number(number)) => Value::Number(-number),
+                    (UnaryOperation::Not, value) if let Some(value) = value.as_boolean() => {
+                        Value::boolean(!value)
+                    }
+                    _ => {
+                        match self.overload(operation.merge_name(), value.clone(), range.clone()) {
+                            Some(result) => result?,
+                            None => {
+                                return Err(RuntimeError::UnsupportedOperation {
+                                    operator: match operation {
+                                        UnaryOperation::Negate => "-",
+                                        UnaryOperation::Not => "!",
+                                    }
+                                    .into(),
+                                    operands: format!("`{value}`"),
+                                    range,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            Core::Binary {
+                left,
+                operation:
+                    operation @ (BinaryOperation::And
+                    | BinaryOperation::LogicalAnd
+                    | BinaryOperation::Or
+                    | BinaryOperation::LogicalOr),
+                right,
+            } => {
+                let is_and = matches!(
+                    operation,
+                    BinaryOperation::And | BinaryOperation::LogicalAnd
+                );
+
+                // `a and b` is `False` if `a` is, and `a or b` is `True` if `a` is.
+                if self.condition(left, environment)? != is_and {
+                    Value::boolean(!is_and)
+                } else {
+                    Value::boolean(self.condition(right, environment)?)
+                }
+            }
+            Core::Binary {
+                left,
+                operation,
+                right,
+            } => {
+                let left = self.expression(left, environment)?;
+                let right = self.expression(right, environment)?;
+                self.binary(*operation, left, right, range)?
+            }
+            Core::Field { record, name } => {
+                let record = self.expression(record, environment)?;
+
+                match record.field(name.value) {
+                    Some(value) => value.clone(),
+                    None => {
+                        return Err(RuntimeError::NoField {
+                            name: name.value.to_string(),
+                            found: record.to_string(),
+                            range: name.range.clone(),
+                        });
+                    }
+                }
+            }
+            Core::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                if self.condition(condition, environment)? {
+                    self.expression(then, environment)?
+                } else {
+                    self.expression(otherwise, environment)?
+                }
+            }
+            Core::Match { on, cases } => {
+                let value = self.expression(on, environment)?;
+
+                for case in cases {
+                    let mut bindings = Vec::new();
+
+                    if !self.matches(&case.value.pattern, &value, environment, &mut bindings)? {
+                        continue;
+                    }
+
+                    if let Some(domain) = &case.value.domain
+                        && !self.in_domain(domain, &value, environment)?
+                    {
+                        continue;
+                    }
+
+                    let scope = environment.child();
+                    scope.define(bindings);
+                    return self.expression(&case.value.body, &scope);
+                }
+
+                return Err(RuntimeError::NoMatch {
+                    value: value.to_string(),
+                    range: on.range.clone(),
+                });
+            }
+            Core::Call { function, argument } => {
+                let function_value = self.expression(function, environment)?;
+                let argument_value = self.expression(argument, environment)?;
+                self.call(
+                    &function_value,
+                    argument_value,
+                    function.range.clone(),
+                    argument.range.clone(),
+                )?
+            }
+            Core::Function {
+                parameter,
+                domain,
+                body,
+            } => Value::Closure(Rc::new(Closure {
+                parameter,
+                domain: domain.as_deref(),
+                body,
+                range,
+                environment: environment.clone(),
+            })),
+            Core::Abstract { name } => {
+                let atom = self.atom(name.value);
+                environment.define([Binding {
+                    name: name.value,
+                    value: atom.clone(),
+                    is_abstract: true,
+                }]);
+                atom
+            }
+            Core::Attributed { expression, .. } => self.expression(expression, environment)?,
+        })
+    }
+
+    fn expressions(
+        &mut self,
+        expressions: &'a [Span<Core<'a>>],
+        environment: &Environment<'a>,
+    ) -> core::result::Result<Vec<Value<'a>>, RuntimeError> {
+        expressions
+            .iter()
+            .map(|expression| self.expression(expression, environment))
+            .collect()
+    }
+
+    /// Evaluates an expression that must be `True` or `False`.
+    fn condition(
+        &mut self,
+        expression: &'a Span<Core<'a>>,
+        environment: &Environment<'a>,
+    ) -> core::result::Result<bool, RuntimeError> {
+        let value = self.expression(expression, environment)?;
+
+        value.as_boolean().ok_or_else(|| RuntimeError::NotABoolean {
+            found: value.to_string(),
+            range: expression.range.clone(),
+        })
+    }
+
+    fn binary(
+        &mut self,
+        operation: BinaryOperation,
+        left: Value<'a>,
+        right: Value<'a>,
+        range: Range<u32>,
+    ) -> Result<'a> {
+        use BinaryOperation::*;
+
+        Ok(match (operation, &left, &right) {
+            (Equal, _, _) => Value::boolean(left == right),
+            (NotEqual, _, _) => Value::boolean(left != right),
+            (Add, Value::Number(left), Value::Number(right)) => Value::Number(left + right),
+            (Subtract, Value::Number(left), Value::Number(right)) => Value::Number(left - right),
+            (Multiply, Value::Number(left), Value::Number(right)) => Value::Number(left * right),
+            (Divide, Value::Number(left), Value::Number(right)) => Value::Number(
+                left.checked_div(right)
+                    .ok_or(RuntimeError::DivisionByZero { range })?,
+            ),
+            (Remainder, Value::Number(left), Value::Number(right))
+                if left.is_integer() && right.is_integer() =>
+            {
+                if right.is_zero() {
+                    return Err(RuntimeError::DivisionByZero { range });
+                }
+
+                // The remainder has the sign of the dividend, like in `7 - 2 * 3`.
+                let (_, remainder) = left.numerator().div_rem(right.numerator());
+                Value::Number(Rational::new(left.is_negative(), remainder, Natural::one()))
+            }
+            (Power, Value::Number(base), Value::Number(exponent))
+                if exponent.is_integer()
+                    && let Some(magnitude) = exponent
+                        .numerator()
+                        .to_u64()
+                        .and_then(|magnitude| u32::try_from(magnitude).ok()) =>
+            {
+                let power = Rational::new(
+                    base.is_negative() && magnitude % 2 == 1,
+                    base.numerator().pow(magnitude),
+                    base.denominator().pow(magnitude),
+                );
+
+                if exponent.is_negative() {
+                    Value::Number(
+                        Rational::from(1)
+                            .checked_div(&power)
+                            .ok_or(RuntimeError::DivisionByZero { range })?,
+                    )
+                } else {
+                    Value::Number(power)
+                }
+            }
+            (LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual, _, _)
+                if let Some(ordering) = compare(&left, &right) =>
+            {
+                Value::boolean(match operation {
+                    LessThan => ordering.is_lt(),
+                    LessThanOrEqual => ordering.is_le(),
+                    GreaterThan => ordering.is_gt(),
+                    _ => ordering.is_ge(),
+                })
+            }
+            (Concatenate, Value::String(left), Value::String(right)) => {
+                Value::String(format!("{left}{right}").into())
+            }
+            (Concatenate, Value::List(left), Value::List(right)) => {
+                Value::List(left.iter().chain(right.iter()).cloned().collect())
+            }
+            _ => {
+                let argument =
+                    Value::record(vec![("left", left.clone()), ("right", right.clone())]);
+
+                match operation
+                    .merge_name()
+                    .and_then(|name| self.overload(name, argument, range.clone()))
+                {
+                    Some(result) => result?,
+                    None => {
+                        return Err(RuntimeError::UnsupportedOperation {
+                            operator: BUILTIN_OPERATORS
+                                .iter()
+                                .find(|operator| operator.operation == operation)
+                                .map_or_else(
+                                    || format!("{operation:?}"),
+                                    |operator| operator.token.to_string(),
+                                ),
+                            operands: format!("`{left}` and `{right}`"),
+                            range,
+                        });
+                    }
+                }
+            }
+        })
+    }
+
+    /// Calls the `@merge` definitions named `name`, if there are any.
+    fn overload(
+        &mut self,
+        name: &str,
+        argument: Value<'a>,
+        range: Range<u32>,
+    ) -> Option<Result<'a>> {
+        let Some(Global {
+            kind: GlobalKind::Merged(_),
+            ..
+        }) = self.globals.get(name)
+        else {
+            return None;
+        };
+
+        Some(
+            self.global_value(name, range.clone())?
+                .and_then(|function| self.call(&function, argument, range.clone(), range)),
+        )
+    }
+
+    fn call(
+        &mut self,
+        function: &Value<'a>,
+        argument: Value<'a>,
+        function_range: Range<u32>,
+        argument_range: Range<u32>,
+    ) -> Result<'a> {
+        let closures = match function {
+            Value::Closure(closure) => vec![closure.clone()],
+            Value::Merged(closures) => closures.to_vec(),
+            _ => {
+                return Err(RuntimeError::NotAFunction {
+                    found: function.to_string(),
+                    range: function_range,
+                });
+            }
+        };
+
+        for closure in closures {
+            let mut bindings = Vec::new();
+
+            if !self.matches(
+                closure.parameter,
+                &argument,
+                &closure.environment,
+                &mut bindings,
+            )? {
+                continue;
+            }
+
+            if let Some(domain) = closure.domain
+                && !self.in_domain(domain, &argument, &closure.environment)?
+            {
+                continue;
+            }
+
+            if self.depth == MAX_CALL_DEPTH {
+                return Err(RuntimeError::TooDeep {
+                    range: function_range.start..argument_range.end,
+                });
+            }
+
+            let scope = closure.environment.child();
+            scope.define(bindings);
+
+            self.depth += 1;
+            let result = self.expression(closure.body, &scope);
+            self.depth -= 1;
+
+            return result;
+        }
+
+        Err(RuntimeError::OutsideDomain {
+            argument: argument.to_string(),
+            range: argument_range,
+        })
+    }
+
+    /// Matches a value against a pattern, adding the bound names to `bindings`.
+    fn matches(
+        &mut self,
+        pattern: &'a Span<Pattern<'a>>,
+        value: &Value<'a>,
+        environment: &Environment<'a>,
+        bindings: &mut Vec<Binding<'a>>,
+    ) -> core::result::Result<bool, RuntimeError> {
+        Ok(match &pattern.value {
+            Pattern::Error => {
+                return Err(RuntimeError::Error {
+                    range: pattern.range.clone(),
+                });
+            }
+            Pattern::Wildcard => true,
+            Pattern::Binding(name) => match self.abstract_value(name, environment) {
+                Some(atom) => atom == *value,
+                None => {
+                    bindings.push(Binding {
+                        name,
+                        value: value.clone(),
+                        is_abstract: false,
+                    });
+                    true
+                }
+            },
+            Pattern::Literal(literal) => value.is_literal(literal),
+            Pattern::Tuple(items) => match value {
+                Value::Tuple(values) if values.len() == items.len() => {
+                    for (item, value) in items.iter().zip(values.iter()) {
+                        if !self.matches(item, value, environment, bindings)? {
+                            return Ok(false);
+                        }
+                    }
+
+                    true
+                }
+                _ => false,
+            },
+            Pattern::Record(fields) => {
+                for (name, field) in fields {
+                    match value.field(name.value) {
+                        Some(value) if self.matches(field, value, environment, bindings)? => {}
+                        _ => return Ok(false),
+                    }
+                }
+
+                true
+            }
+            Pattern::Or(alternatives) => {
+                let length = bindings.len();
+
+                for alternative in alternatives {
+                    if self.matches(alternative, value, environment, bindings)? {
+                        return Ok(true);
+                    }
+
+                    bindings.truncate(length);
+                }
+
+                false
+            }
+        })
+    }
+
+    /// Returns the value of `name` if it refers to an atom or builtin, which
+    /// a pattern `name` matches instead of binding the name.
+    fn abstract_value(&self, name: &str, environment: &Environment<'a>) -> Option<Value<'a>> {
+        match environment.get(name) {
+            Some((value, true)) => Some(value),
+            Some((_, false)) => None,
+            None => match self.globals.get(name) {
+                Some(Global {
+                    kind: GlobalKind::Abstract,
+                    state: State::Evaluated(value),
+                }) => Some(value.clone()),
+                _ => None,
+            },
+        }
+    }
+
+    /// Evaluates a domain and returns whether the value is in it.
+    fn in_domain(
+        &mut self,
+        domain: &'a Span<Core<'a>>,
+        value: &Value<'a>,
+        environment: &Environment<'a>,
+    ) -> core::result::Result<bool, RuntimeError> {
+        let domain_value = self.expression(domain, environment)?;
+        self.contains(&domain_value, value, domain.range.clone())
+    }
+
+    /// Whether the value is in the domain. See [`RuntimeError::NotADomain`].
+    fn contains(
+        &mut self,
+        domain: &Value<'a>,
+        value: &Value<'a>,
+        range: Range<u32>,
+    ) -> core::result::Result<bool, RuntimeError> {
+        Ok(match domain {
+            Value::Set(elements) => elements.contains(value),
+            Value::Builtin(builtin) => builtin.contains(value),
+            Value::Atom(_) | Value::Number(_) | Value::String(_) | Value::Character(_) => {
+                domain == value
+            }
+            Value::Tuple(domains) => match value {
+                Value::Tuple(values) if values.len() == domains.len() => {
+                    for (domain, value) in domains.iter().zip(values.iter()) {
+                        if !self.contains(domain, value, range.clone())? {
+                            return Ok(false);
+                        }
+                    }
+
+                    true
+                }
+                _ => false,
+            },
+            Value::Record(domains) => match value {
+                Value::Record(values)
+                    if values.len() == domains.len()
+                        && values
+                            .iter()
+                            .zip(domains.iter())
+                            .all(|((value, _), (domain, _))| value == domain) =>
+                {
+                    for ((_, domain), (_, value)) in domains.iter().zip(values.iter()) {
+                        if !self.contains(domain, value, range.clone())? {
+                            return Ok(false);
+                        }
+                    }
+
+                    true
+                }
+                _ => false,
+            },
+            Value::Closure(_) | Value::Merged(_) => {
+                let result = self.call(domain, value.clone(), range.clone(), range.clone())?;
+
+                result
+                    .as_boolean()
+                    .ok_or_else(|| RuntimeError::NotABoolean {
+                        found: result.to_string(),
+                        range,
+                    })?
+            }
+            Value::List(_) => {
+                return Err(RuntimeError::NotADomain {
+                    found: domain.to_string(),
+                    range,
+                });
+            }
+        })
+    }
+
+    /// Returns the value of a module-level definition, evaluating it on first use.
+    fn global_value(&mut self, name: &str, range: Range<u32>) -> Option<Result<'a>> {
+        let global = self.globals.get_mut(name)?;
+
+        match &global.state {
+            State::Evaluated(value) => return Some(Ok(value.clone())),
+            State::Evaluating => {
+                return Some(Err(RuntimeError::Cycle {
+                    name: name.to_string(),
+                    range,
+                }));
+            }
+            State::Unevaluated => global.state = State::Evaluating,
+        }
+
+        let result = match global.kind.clone() {
+            GlobalKind::Abstract => unreachable!("abstract globals are always evaluated"),
+            GlobalKind::Definition { pattern, value } => self.evaluate(value).and_then(|value| {
+                let mut bindings = Vec::new();
+
+                if self.matches(pattern, &value, &Environment::default(), &mut bindings)? {
+                    Ok(bindings
+                        .into_iter()
+                        .find(|binding| binding.name == name)
+                        .expect("the pattern binds the name")
+                        .value)
+                } else {
+                    Err(RuntimeError::NoMatch {
+                        value: value.to_string(),
+                        range: pattern.range.clone(),
+                    })
+                }
+            }),
+            GlobalKind::Merged(values) => values
+                .into_iter()
+                .map(|value| match self.evaluate(value)? {
+                    Value::Closure(closure) => Ok(vec![closure]),
+                    Value::Merged(closures) => Ok(closures.to_vec()),
+                    other => Err(RuntimeError::NotAFunction {
+                        found: other.to_string(),
+                        range: value.range.clone(),
+                    }),
+                })
+                .collect::<core::result::Result<Vec<_>, _>>()
+                .map(|closures| Value::Merged(closures.concat().into())),
+        };
+
+        self.globals.get_mut(name).unwrap().state = match &result {
+            Ok(value) => State::Evaluated(value.clone()),
+            Err(_) => State::Unevaluated,
+        };
+
+        Some(result)
+    }
+
+    /// Creates a new atom.
+    fn atom(&mut self, name: &'a str) -> Value<'a> {
+        let atom = Atom {
+            id: self.atoms,
+            name,
+        };
+        self.atoms += 1;
+        Value::Atom(atom)
+    }
+
+    fn constant(&mut self, name: &'a str, value: Value<'a>) {
+        self.globals.insert(
+            name,
+            Global {
+                kind: GlobalKind::Abstract,
+                state: State::Evaluated(value),
+            },
+        );
+    }
+}
+
+/// Compares numbers, strings and characters.
+fn compare(left: &Value, right: &Value) -> Option<core::cmp::Ordering> {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => Some(left.cmp(right)),
+        (Value::String(left), Value::String(right)) => Some(left.cmp(right)),
+        (Value::Character(left), Value::Character(right)) => Some(left.cmp(right)),
+        _ => None,
+    }
+}
@@ -0,0 +1,57 @@
+//! A tree-walking interpreter for the core language of
+//! [`vine_parse::lower`].
+//!
+//! Module-level definitions are evaluated when they are first used, so they
+//! may refer to each other in any order. Definitions with a `@merge` attribute
+//! are combined into one function that calls the first definition whose
+//! parameter accepts the argument. Arithmetic operators on values other than
+//! numbers call the `@merge` definitions named by
+//! [`BinaryOperation::merge_name`] and [`UnaryOperation::merge_name`].
+//!
+//! [`BinaryOperation::merge_name`]: vine_parse::ast::BinaryOperation::merge_name
+//! [`UnaryOperation::merge_name`]: vine_parse::ast::UnaryOperation::merge_name
+
+mod error;
+mod interpreter;
+#[cfg(test)]
+mod tests;
+mod value;
+
+use std::{panic, thread};
+
+pub use error::*;
+pub use interpreter::*;
+use parser_tools::Span;
+pub use value::*;
+use vine_parse::lower::Core;
+
+/// Calls `f` on a thread with a stack of [`STACK_SIZE`], so that evaluating
+/// the maximum number of nested calls in `f` cannot overflow the stack.
+pub fn with_stack<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    thread::scope(|scope| {
+        thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn_scoped(scope, f)
+            .expect("cannot spawn a thread for the interpreter")
+            .join()
+            .unwrap_or_else(|payload| panic::resume_unwind(payload))
+    })
+}
+
+/// Runs the items of a lowered module and returns the values of the
+/// module-level expressions that are not definitions, in order.
+///
+/// Deeply nested calls need a large stack; see [`with_stack`].
+pub fn run<'a>(items: &'a [Span<Core<'a>>]) -> Result<Vec<Value<'a>>, RuntimeError> {
+    let mut interpreter = Interpreter::new();
+
+    for item in items {
+        interpreter.define(item);
+    }
+
+    items
+        .iter()
+        .filter(|item| !Interpreter::is_definition(item))
+        .map(|item| interpreter.evaluate(item))
+        .collect()
+}
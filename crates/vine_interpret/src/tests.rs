@@ -0,0 +1,162 @@
+#![allow(non_snake_case)]
+
+mod Interpreter {
+    use parser_tools::Span;
+    use vine_parse::{
+        lower::{Core, lower_module},
+        parse_module,
+    };
+
+    use super::super::*;
+
+    const FINITE: &str = include_str!("../../../language-design/finite.vn");
+
+    /// Lowers a module, leaking it so the values can refer to it.
+    fn lowered(source: &'static str) -> &'static [Span<Core<'static>>] {
+        let module = Box::leak(Box::new(parse_module(source).unwrap()));
+        let (items, diagnostics) = lower_module(module);
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+        items.leak()
+    }
+
+    /// Runs a module and returns the values of its expressions in Vine syntax.
+    fn ran(source: &'static str) -> Result<Vec<String>, RuntimeError> {
+        run(lowered(source)).map(|values| values.iter().map(ToString::to_string).collect())
+    }
+
+    fn finite(expressions: &str) -> Vec<String> {
+        let source = format!("{FINITE}\n{expressions}").leak();
+        ran(source).unwrap()
+    }
+
+    #[test]
+    fn run_finite() {
+        assert_eq!(
+            finite(
+                "y = -Zero\nx\nOne + One\nOne + Zero\nOne * One\nZero * One\ny\nmultiplicative_inverse One\nZeroOrOne"
+            ),
+            [
+                "Zero",
+                "Zero",
+                "One",
+                "One",
+                "Zero",
+                "One",
+                "One",
+                "set {Zero, One}"
+            ]
+        );
+    }
+
+    #[test]
+    fn run_finite_errors() {
+        let source = format!("{FINITE}\nmultiplicative_inverse Zero").leak();
+        assert!(matches!(
+            ran(source),
+            Err(RuntimeError::OutsideDomain { argument, .. }) if argument == "Zero"
+        ));
+
+        let source = format!("{FINITE}\nOne + 1").leak();
+        assert!(matches!(
+            ran(source),
+            Err(RuntimeError::OutsideDomain { argument, .. }) if argument == "(left = One, right = 1)"
+        ));
+    }
+
+    #[test]
+    fn run_numbers() {
+        assert_eq!(
+            ran("(1 + 2 * 3, 7 / 2, -7 % 3, 2 ** -2, 1 < 2 and 2 <= 1, \"a\" ++ \"b\")").unwrap(),
+            ["(7, 7/2, -1, 1/4, False, \"ab\")"]
+        );
+        assert_eq!(
+            ran("1 / 0"),
+            Err(RuntimeError::DivisionByZero { range: 0..5 })
+        );
+    }
+
+    #[test]
+    fn run_structures() {
+        assert_eq!(
+            ran("p = (left = 1, right = (2, 'c'))\np.right\np.left\nset {1, 2, 1}\n[1] ++ [2]\n(b = 1, a = 2) == (a = 2, b = 1)\nset {1, 2} == set {2, 1}").unwrap(),
+            ["(2, 'c')", "1", "set {1, 2}", "[1, 2]", "True", "True"]
+        );
+        assert!(matches!(
+            ran("(a = 1).b"),
+            Err(RuntimeError::NoField { name, .. }) if name == "b"
+        ));
+    }
+
+    #[test]
+    fn run_functions() {
+        assert_eq!(
+            ran("add = function (a, b) => a + b\nadd (1, 2)\ncurry = function a => function b => a - b\ncurry 5 3\nfactorial = function n is Number => if n == 0 then 1 else n * factorial (n - 1)\nfactorial 5").unwrap(),
+            ["3", "2", "120"]
+        );
+        assert!(matches!(
+            ran("f = function x is Number => x\nf 'c'"),
+            Err(RuntimeError::OutsideDomain { argument, .. }) if argument == "'c'"
+        ));
+        assert!(matches!(
+            ran("1 2"),
+            Err(RuntimeError::NotAFunction { found, .. }) if found == "1"
+        ));
+    }
+
+    #[test]
+    fn run_deep_recursion() {
+        with_stack(|| {
+            assert_eq!(
+                ran("f = function n => if n == 0 then 0 else f (n - 1)\nf 5000").unwrap(),
+                ["0"]
+            );
+            assert!(matches!(
+                ran("f = function n => if n == 0 then 0 else f (n - 1)\nf 20000"),
+                Err(RuntimeError::TooDeep { .. })
+            ));
+        });
+    }
+
+    #[test]
+    fn run_match() {
+        assert_eq!(
+            ran("abstract A\nf = function p => match p\n    case (a, A) or (A, a) => a\n    case (x = _, y = True) => A\n    case n is Number => n\nf (1, A)\nf (A, 2)\nf (x = 0, y = True)\nf 3\nif False then 1").unwrap(),
+            ["1", "2", "A", "3", "()"]
+        );
+        assert!(matches!(
+            ran("match 1\n    case 2 => 3"),
+            Err(RuntimeError::NoMatch { value, .. }) if value == "1"
+        ));
+    }
+
+    #[test]
+    fn run_cycles() {
+        assert!(matches!(
+            ran("a = b\nb = a\na"),
+            Err(RuntimeError::Cycle { name, .. }) if name == "a"
+        ));
+        assert!(matches!(
+            ran("y"),
+            Err(RuntimeError::Undefined { name, .. }) if name == "y"
+        ));
+    }
+
+    #[test]
+    fn define() {
+        let mut interpreter = Interpreter::new();
+
+        for item in lowered(FINITE) {
+            interpreter.define(item);
+        }
+
+        assert_eq!(
+            interpreter.global("x").unwrap().unwrap().to_string(),
+            "Zero"
+        );
+        assert!(interpreter.global("y").is_none());
+
+        let items = lowered("x = One\nx + x");
+        interpreter.define(&items[0]);
+        assert_eq!(interpreter.evaluate(&items[1]).unwrap().to_string(), "Zero");
+    }
+}
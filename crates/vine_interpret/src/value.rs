@@ -0,0 +1,252 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Debug, Display, Formatter},
+    ops::Range,
+    rc::Rc,
+};
+
+use parser_tools::Span;
+use vine_num::Rational;
+use vine_parse::lower::{Core, Literal, Pattern};
+
+use crate::interpreter::Environment;
+
+/// A runtime value.
+#[derive(Clone, Debug)]
+pub enum Value<'a> {
+    Number(Rational),
+    String(Rc<str>),
+    Character(char),
+    Atom(Atom<'a>),
+    Tuple(Rc<[Value<'a>]>),
+
+    /// A record, with its fields sorted by name.
+    Record(Rc<[(&'a str, Value<'a>)]>),
+
+    /// A set, with every element once.
+    Set(Rc<[Value<'a>]>),
+    List(Rc<[Value<'a>]>),
+    Closure(Rc<Closure<'a>>),
+
+    /// The combined `@merge` definitions of a name.
+    Merged(Rc<[Rc<Closure<'a>>]>),
+
+    /// A builtin type, used as a domain.
+    Builtin(Builtin),
+}
+
+/// A value declared with `abstract`, or `True` or `False`. Atoms are only equal
+/// to themselves, even if another atom has the same name.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Atom<'a> {
+    pub id: u32,
+    pub name: &'a str,
+}
+
+/// A function value: a function expression with the environment it was created in.
+pub struct Closure<'a> {
+    pub parameter: &'a Span<Pattern<'a>>,
+    pub domain: Option<&'a Span<Core<'a>>>,
+    pub body: &'a Span<Core<'a>>,
+    pub range: Range<u32>,
+    pub(crate) environment: Environment<'a>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Builtin {
+    Number,
+    String,
+    Character,
+    Boolean,
+}
+
+impl<'a> Value<'a> {
+    pub const TRUE: Self = Self::Atom(Atom {
+        id: 0,
+        name: "True",
+    });
+    pub const FALSE: Self = Self::Atom(Atom {
+        id: 1,
+        name: "False",
+    });
+
+    pub const fn boolean(value: bool) -> Self {
+        if value { Self::TRUE } else { Self::FALSE }
+    }
+
+    /// Returns the boolean this value is, if it is `True` or `False`.
+    pub fn as_boolean(&self) -> Option<bool> {
+        match self {
+            _ if *self == Self::TRUE => Some(true),
+            _ if *self == Self::FALSE => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Creates a record, sorting the fields by name.
+    pub fn record(mut fields: Vec<(&'a str, Value<'a>)>) -> Self {
+        fields.sort_by_key(|(name, _)| *name);
+        Self::Record(fields.into())
+    }
+
+    /// Creates a set, removing duplicate elements.
+    pub fn set(elements: impl IntoIterator<Item = Value<'a>>) -> Self {
+        let mut distinct = Vec::<Value>::new();
+
+        for element in elements {
+            if !distinct.contains(&element) {
+                distinct.push(element);
+            }
+        }
+
+        Self::Set(distinct.into())
+    }
+
+    /// Returns the field of a record.
+    pub fn field(&self, name: &str) -> Option<&Value<'a>> {
+        match self {
+            Self::Record(fields) => fields
+                .iter()
+                .find(|(field, _)| *field == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Whether the value is equal to the literal.
+    pub fn is_literal(&self, literal: &Literal) -> bool {
+        match (self, literal) {
+            (Self::Number(left), Literal::Number(right)) => left == right,
+            (Self::String(left), Literal::String(right)) => **left == **right,
+            (Self::Character(left), Literal::Character(right)) => left == right,
+            _ => false,
+        }
+    }
+
+    pub fn from_literal(literal: &Literal) -> Self {
+        match literal {
+            Literal::Number(number) => Self::Number(number.clone()),
+            Literal::String(string) => Self::String(string.as_ref().into()),
+            Literal::Character(character) => Self::Character(*character),
+        }
+    }
+}
+
+impl PartialEq for Value<'_> {
+    /// Compares values structurally, sets regardless of order and
+    /// functions by identity.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Number(left), Self::Number(right)) => left == right,
+            (Self::String(left), Self::String(right)) => left == right,
+            (Self::Character(left), Self::Character(right)) => left == right,
+            (Self::Atom(left), Self::Atom(right)) => left == right,
+            (Self::Tuple(left), Self::Tuple(right)) | (Self::List(left), Self::List(right)) => {
+                left == right
+            }
+            (Self::Record(left), Self::Record(right)) => left == right,
+            (Self::Set(left), Self::Set(right)) => {
+                left.len() == right.len() && left.iter().all(|element| right.contains(element))
+            }
+            (Self::Closure(left), Self::Closure(right)) => Rc::ptr_eq(left, right),
+            (Self::Merged(left), Self::Merged(right)) => Rc::ptr_eq(left, right),
+            (Self::Builtin(left), Self::Builtin(right)) => left == right,
+            _ => false,
+        }
+    }
+}
+
+impl Debug for Closure<'_> {
+    /// Writes only the range, since the environment may contain the closure itself.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Closure({:?})", self.range)
+    }
+}
+
+impl Builtin {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "Number" => Self::Number,
+            "String" => Self::String,
+            "Character" => Self::Character,
+            "Boolean" => Self::Boolean,
+            _ => return None,
+        })
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Number => "Number",
+            Self::String => "String",
+            Self::Character => "Character",
+            Self::Boolean => "Boolean",
+        }
+    }
+
+    /// Whether the value is of this type.
+    pub fn contains(self, value: &Value) -> bool {
+        match (self, value) {
+            (Self::Number, Value::Number(_))
+            | (Self::String, Value::String(_))
+            | (Self::Character, Value::Character(_)) => true,
+            (Self::Boolean, value) => value.as_boolean().is_some(),
+            _ => false,
+        }
+    }
+}
+
+impl Display for Value<'_> {
+    /// Writes the value in Vine syntax. Functions are written as `function`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{}", Literal::Number(number.clone())),
+            Self::String(string) => write!(f, "{}", Literal::String(Cow::Borrowed(string))),
+            Self::Character(character) => write!(f, "{}", Literal::Character(*character)),
+            Self::Atom(atom) => f.write_str(atom.name),
+            Self::Tuple(items) => {
+                f.write_str("(")?;
+                write_list(f, items.iter())?;
+
+                if items.len() == 1 {
+                    f.write_str(",")?;
+                }
+
+                f.write_str(")")
+            }
+            Self::Record(fields) => {
+                f.write_str("(")?;
+                write_list(
+                    f,
+                    fields
+                        .iter()
+                        .map(|(name, value)| format!("{name} = {value}")),
+                )?;
+                f.write_str(")")
+            }
+            Self::Set(elements) => {
+                f.write_str("set {")?;
+                write_list(f, elements.iter())?;
+                f.write_str("}")
+            }
+            Self::List(items) => {
+                f.write_str("[")?;
+                write_list(f, items.iter())?;
+                f.write_str("]")
+            }
+            Self::Closure(_) | Self::Merged(_) => f.write_str("function"),
+            Self::Builtin(builtin) => f.write_str(builtin.name()),
+        }
+    }
+}
+
+fn write_list(f: &mut Formatter<'_>, items: impl Iterator<Item = impl Display>) -> fmt::Result {
+    for (index, item) in items.enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+
+        write!(f, "{item}")?;
+    }
+
+    Ok(())
+}
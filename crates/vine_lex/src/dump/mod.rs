@@ -0,0 +1,62 @@
+//! Readable listings of tokens, for debugging and snapshot tests.
+//!
+//! Every token is on its own line as `<start>..<end> <kind> <source>`,
+//! with the source written as a Rust string literal.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{format, string::String};
+use core::{fmt::Write, ops::Range};
+
+use parser_tools::Spanify;
+
+use crate::{Lexer, lex};
+
+/// Lists the tokens produced by the [`Lexer`], including whitespace and comments.
+pub fn raw_tokens(input: &str) -> String {
+    let mut output = String::new();
+
+    for token in Spanify::new(Lexer::new(input)) {
+        write_token(
+            &mut output,
+            input,
+            token.range,
+            &format!("{:?}", token.value),
+        );
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Lists the tokens produced by the [`TokenFilter`](crate::filter::TokenFilter).
+/// Tokens preceded by a line break are marked with `line_break_before`.
+pub fn filtered_tokens(input: &str) -> String {
+    let mut output = String::new();
+
+    for token in lex(input) {
+        write_token(
+            &mut output,
+            input,
+            token.range,
+            &format!("{:?}", token.value.kind),
+        );
+
+        if token.value.line_break_before {
+            output.push_str(" line_break_before");
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Writes the range, the name of the variant in `debug` and the source of a token.
+fn write_token(output: &mut String, input: &str, range: Range<u32>, debug: &str) {
+    let kind = debug.split_once('(').map_or(debug, |(kind, _)| kind);
+    let source = &input[range.start as usize..range.end as usize];
+
+    let _ = write!(output, "{}..{} {kind} {source:?}", range.start, range.end);
+}
@@ -0,0 +1,41 @@
+#![allow(non_snake_case)]
+
+mod Lexer {
+    use super::super::*;
+
+    #[test]
+    fn raw_tokens_listing() {
+        assert_eq!(
+            raw_tokens("a = 'b' // c\n  1"),
+            "\
+0..1 IdentifierOrKeyword \"a\"
+1..2 Whitespace \" \"
+2..3 Equals \"=\"
+3..4 Whitespace \" \"
+4..7 Character \"'b'\"
+7..8 Whitespace \" \"
+8..12 Comment \"// c\"
+12..15 Whitespace \"\\n  \"
+15..16 Number \"1\"
+"
+        );
+    }
+}
+
+mod TokenFilter {
+    use super::super::*;
+
+    #[test]
+    fn filtered_tokens_listing() {
+        assert_eq!(
+            filtered_tokens("x == y // c\n  case \"s\""),
+            "\
+0..1 Identifier \"x\"
+2..4 EqualsEquals \"==\"
+5..6 Identifier \"y\"
+14..18 Case \"case\" line_break_before
+19..22 String \"\\\"s\\\"\"
+"
+        );
+    }
+}
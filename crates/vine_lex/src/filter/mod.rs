@@ -1,3 +1,5 @@
+#[cfg(test)]
+mod tests;
 mod tokens;
 
 pub use tokens::*;
@@ -179,7 +181,7 @@ impl<'source, Tokens: Iterator<Item = Token<'source>>> Iterator for TokenFilter<
                             }
                         }
                         _ => Span {
-                            value: FilteredTokenKind::ExclamationMarkEqualsEquals,
+                            value: FilteredTokenKind::ExclamationMarkEquals,
                             range: start..end,
                         },
                     }
@@ -441,7 +443,7 @@ impl<'source, Tokens: Iterator<Item = Token<'source>>> Iterator for TokenFilter<
                     self.tokens.next();
 
                     Span {
-                        value: FilteredTokenKind::AmpersandAmpersandEquals,
+                        value: FilteredTokenKind::AmpersandEquals,
                         range: start..end,
                     }
                 }
@@ -504,6 +506,27 @@ impl<'source, Tokens: Iterator<Item = Token<'source>>> Iterator for TokenFilter<
                     range: start..end,
                 },
             },
+            Span {
+                value: Token::Percent,
+                range: Range { start, end },
+            } => match self.tokens.peek() {
+                Some(Span {
+                    value: Token::Equals,
+                    range: Range { end, .. },
+                }) => {
+                    let end = *end;
+                    self.tokens.next();
+
+                    Span {
+                        value: FilteredTokenKind::PercentEquals,
+                        range: start..end,
+                    }
+                }
+                _ => Span {
+                    value: FilteredTokenKind::Percent,
+                    range: start..end,
+                },
+            },
             token => unreachable!("{token:?} is not filterable"),
         };
 
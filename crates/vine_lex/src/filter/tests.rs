@@ -0,0 +1,30 @@
+#![allow(non_snake_case)]
+
+mod TokenFilter {
+    use alloc::vec::Vec;
+
+    use crate::{filter::FilteredTokenKind, lex};
+
+    #[test]
+    fn next_compound_operators() {
+        let operators = [
+            "==", "===", "=>", "!", "!=", "!==", "+", "++", "+=", "-", "--", "-=", "->", "*", "**",
+            "*=", "/", "/=", "|", "||", "|=", "||=", ".", "..", "..=", "&", "&&", "&=", "&&=", ":",
+            "::", ":=", "^", "^=", "%", "%=", "<", "<=", "<-", ">", ">=", "$",
+        ];
+
+        for operator in operators {
+            let kinds = lex(operator)
+                .map(|token| token.value.kind)
+                .collect::<Vec<_>>();
+
+            assert_eq!(kinds.len(), 1, "`{operator}` is not a single token");
+            assert_eq!(alloc::format!("{}", kinds[0]), operator);
+        }
+
+        assert_eq!(
+            lex("= =").map(|token| token.value.kind).collect::<Vec<_>>(),
+            [FilteredTokenKind::Equals, FilteredTokenKind::Equals]
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use crate::tokens::Token;
+use core::fmt::{self, Display, Formatter};
+
+use crate::tokens::{CharacterSource, NumberSource, StringSource, Token};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct FilteredToken<'source> {
@@ -17,11 +19,18 @@ pub enum FilteredTokenKind<'source> {
     /// An invalid token
     Invalid(&'source str),
 
-    /// A unicode character `'x'`.
-    Character(char),
+    /// An identifier in backticks used as an infix operator, like `` `max` ``.
+    /// The source includes the backticks.
+    InfixIdentifier(&'source str),
+
+    /// A unicode character `'x'`. Escape sequences are decoded by the parser.
+    Character(CharacterSource<'source>),
 
-    // TODO: change that to fraction
-    Number(u64),
+    /// A string `"..."`. Escape sequences are decoded by the parser.
+    String(StringSource<'source>),
+
+    /// A number. The value is parsed by the parser.
+    Number(NumberSource<'source>),
 
     /// `;`
     Semicolon,
@@ -262,6 +271,9 @@ pub enum FilteredTokenKind<'source> {
 
     /// Keyword `and`
     And,
+
+    /// Keyword `abstract`
+    Abstract,
 }
 
 impl<'source> FilteredTokenKind<'source> {
@@ -295,12 +307,16 @@ impl<'source> FilteredTokenKind<'source> {
             Token::IdentifierOrKeyword("while") => Some(Self::While),
             Token::IdentifierOrKeyword("or") => Some(Self::Or),
             Token::IdentifierOrKeyword("and") => Some(Self::And),
+            Token::IdentifierOrKeyword("abstract") => Some(Self::Abstract),
             Token::IdentifierOrKeyword(identifier) => Some(Self::Identifier(identifier)),
             Token::Invalid(invalid) => Some(Self::Invalid(invalid)),
-            Token::Character(source) => Some(Self::Character(source.parse())),
-            Token::Number(n) => Some(Self::Number(n.parse())),
+            Token::InfixIdentifier(source) => Some(Self::InfixIdentifier(source)),
+            Token::Character(source) => Some(Self::Character(*source)),
+            Token::String(source) => Some(Self::String(*source)),
+            Token::Number(n) => Some(Self::Number(*n)),
             Token::Semicolon => Some(Self::Semicolon),
             Token::At => Some(Self::At),
+            Token::DollarSign => Some(Self::DollarSign),
             Token::Comma => Some(Self::Comma),
             Token::OpeningParenthesis => Some(Self::OpeningParenthesis),
             Token::ClosingParenthesis => Some(Self::ClosingParenthesis),
@@ -316,3 +332,98 @@ impl<'source> FilteredTokenKind<'source> {
         }
     }
 }
+
+impl Display for FilteredTokenKind<'_> {
+    /// Writes the token as it appears in the source.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Identifier(source) | Self::Invalid(source) | Self::InfixIdentifier(source) => {
+                source
+            }
+            Self::Character(source) => source.as_str(),
+            Self::String(source) => source.as_str(),
+            Self::Number(source) => source.as_str(),
+            Self::Semicolon => ";",
+            Self::At => "@",
+            Self::Caret => "^",
+            Self::CaretEquals => "^=",
+            Self::Comma => ",",
+            Self::Equals => "=",
+            Self::EqualsEquals => "==",
+            Self::EqualsEqualsEquals => "===",
+            Self::EqualsGreaterThan => "=>",
+            Self::LessThan => "<",
+            Self::LessThanEquals => "<=",
+            Self::LessThanMinus => "<-",
+            Self::GreaterThan => ">",
+            Self::GreaterThanEquals => ">=",
+            Self::OpeningParenthesis => "(",
+            Self::ClosingParenthesis => ")",
+            Self::OpeningBrace => "{",
+            Self::ClosingBrace => "}",
+            Self::OpeningBracket => "[",
+            Self::ClosingBracket => "]",
+            Self::Plus => "+",
+            Self::PlusPlus => "++",
+            Self::PlusEquals => "+=",
+            Self::Minus => "-",
+            Self::MinusMinus => "--",
+            Self::MinusEquals => "-=",
+            Self::MinusGreaterThan => "->",
+            Self::Star => "*",
+            Self::StarStar => "**",
+            Self::StarEquals => "*=",
+            Self::Slash => "/",
+            Self::SlashEquals => "/=",
+            Self::Bar => "|",
+            Self::BarBar => "||",
+            Self::BarEquals => "|=",
+            Self::BarBarEquals => "||=",
+            Self::Period => ".",
+            Self::PeriodPeriod => "..",
+            Self::PeriodPeriodEquals => "..=",
+            Self::Ampersand => "&",
+            Self::AmpersandEquals => "&=",
+            Self::AmpersandAmpersand => "&&",
+            Self::AmpersandAmpersandEquals => "&&=",
+            Self::ExclamationMark => "!",
+            Self::ExclamationMarkEquals => "!=",
+            Self::ExclamationMarkEqualsEquals => "!==",
+            Self::DollarSign => "$",
+            Self::Percent => "%",
+            Self::PercentEquals => "%=",
+            Self::Paragraph => "§",
+            Self::QuestionMark => "?",
+            Self::Tilde => "~",
+            Self::Backtick => "`",
+            Self::Colon => ":",
+            Self::ColonEquals => ":=",
+            Self::ColonColon => "::",
+            Self::Function => "function",
+            Self::Match => "match",
+            Self::Case => "case",
+            Self::If => "if",
+            Self::Then => "then",
+            Self::Else => "else",
+            Self::Return => "return",
+            Self::Set => "set",
+            Self::Block => "block",
+            Self::Leave => "leave",
+            Self::Public => "public",
+            Self::Is => "is",
+            Self::In => "in",
+            Self::Enum => "enum",
+            Self::Type => "type",
+            Self::Alias => "alias",
+            Self::BigSelf => "Self",
+            Self::Private => "private",
+            Self::Module => "module",
+            Self::For => "for",
+            Self::Loop => "loop",
+            Self::While => "while",
+            Self::Or => "or",
+            Self::And => "and",
+            Self::Abstract => "abstract",
+        })
+    }
+}
@@ -5,7 +5,7 @@ use core::str;
 
 use parser_tools::PeekableChars;
 
-use crate::tokens::{CharacterSource, NumberSource, Token, WhitespaceSource};
+use crate::tokens::{CharacterSource, NumberSource, Radix, StringSource, Token, WhitespaceSource};
 
 fn is_identifier_start(c: char) -> bool {
     c.is_alphabetic() || c == '_'
@@ -92,7 +92,22 @@ impl<'source> Iterator for Lexer<'source> {
             '§' => Token::Paragraph,
             '?' => Token::QuestionMark,
             '~' => Token::Tilde,
-            '`' => Token::Backtick,
+            '`' => {
+                let rest = self.chars.as_str();
+                let name_length = rest
+                    .find(|c| !is_identifier_continuation(c))
+                    .unwrap_or(rest.len());
+
+                if rest.starts_with(is_identifier_start) && rest[name_length..].starts_with('`') {
+                    for _ in rest[..=name_length].chars() {
+                        self.chars.next();
+                    }
+
+                    Token::InfixIdentifier(unsafe { span!() })
+                } else {
+                    Token::Backtick
+                }
+            }
             ':' => Token::Colon,
             '+' => Token::Plus,
             '-' => Token::Minus,
@@ -180,19 +195,70 @@ impl<'source> Iterator for Lexer<'source> {
 
                 Token::Comment(unsafe { span!() })
             }
+            '"' => loop {
+                match self.chars.next() {
+                    Some('"') => {
+                        break Token::String(unsafe { StringSource::new_unchecked(span!()) });
+                    }
+                    Some('\\') => {
+                        // Skip the escaped character, so that `\"` does not terminate the string.
+                        // The escape sequence itself is validated when decoding the string.
+                        if self.chars.next().is_none() {
+                            break Token::Invalid(unsafe { span!() });
+                        }
+                    }
+                    Some(_) => {}
+                    None => break Token::Invalid(unsafe { span!() }),
+                }
+            },
             '\'' => {
                 match self.chars.next() {
-                    Some('\\') => todo!("escape in char literal"),
-                    Some('\'') | None => return Some(Token::Invalid(unsafe { span!() })),
+                    Some('\\') => {
+                        // The escape sequence is validated when decoding the character.
+                        if let Some('u') = self.chars.next()
+                            && let Some('{') = self.chars.peek()
+                        {
+                            while let Some(c) = self.chars.peek()
+                                && !matches!(c, '}' | '\'' | '\r' | '\n')
+                            {
+                                self.chars.next();
+                            }
+
+                            if let Some('}') = self.chars.peek() {
+                                self.chars.next();
+                            }
+                        }
+                    }
+                    Some('\'' | '\r' | '\n') | None => {
+                        return Some(Token::Invalid(unsafe { span!() }));
+                    }
                     Some(_) => {}
                 };
 
-                match self.chars.next() {
+                match self.chars.peek() {
                     Some('\'') => {
+                        self.chars.next();
+
                         Token::Character(unsafe { CharacterSource::new_unchecked(span!()) })
                     }
-                    // TODO: maybe capture everything in '...'
-                    _ => return Some(Token::Invalid(unsafe { span!() })),
+                    _ => {
+                        // Capture everything up to the closing quote on the same line,
+                        // so that `'abc'` becomes a single invalid token.
+                        loop {
+                            match self.chars.peek() {
+                                Some('\'') => {
+                                    self.chars.next();
+                                    break;
+                                }
+                                Some('\r' | '\n') | None => break,
+                                Some(_) => {
+                                    self.chars.next();
+                                }
+                            }
+                        }
+
+                        Token::Invalid(unsafe { span!() })
+                    }
                 }
             }
             c if is_identifier_start(c) => {
@@ -211,12 +277,43 @@ impl<'source> Iterator for Lexer<'source> {
 
                 Token::Whitespace(unsafe { WhitespaceSource::new_unchecked(span!()) })
             }
+            '0' if let Some(radix) = self.chars.peek().and_then(Radix::from_prefix) => {
+                self.chars.next();
+
+                let mut has_digits = false;
+                let mut is_valid = true;
+
+                // Consume the whole word, so that `0xZZ` becomes a single invalid token.
+                while let Some(c) = self.chars.peek()
+                    && is_identifier_continuation(c)
+                {
+                    self.chars.next();
+
+                    match c {
+                        '_' => {}
+                        c if c.is_digit(radix.value()) => has_digits = true,
+                        _ => is_valid = false,
+                    }
+                }
+
+                if has_digits && is_valid {
+                    Token::Number(unsafe { NumberSource::new_unchecked(span!()) })
+                } else {
+                    Token::Invalid(unsafe { span!() })
+                }
+            }
             '0'..='9' => {
                 while let Some('0'..='9' | '_') = self.chars.peek() {
                     self.chars.next();
                 }
 
-                if let Some('.') = self.chars.peek() {
+                // Only treat `.` as a decimal point if a digit follows,
+                // so that `1..5` still lexes as a range.
+                let mut lookahead = self.chars.as_str().chars();
+
+                if lookahead.next() == Some('.')
+                    && lookahead.next().is_some_and(|c| c.is_ascii_digit())
+                {
                     self.chars.next();
 
                     while let Some('0'..='9' | '_') = self.chars.peek() {
@@ -224,7 +321,33 @@ impl<'source> Iterator for Lexer<'source> {
                     }
                 }
 
-                Token::Number(unsafe { NumberSource::new_unchecked(span!()) })
+                let mut is_valid = true;
+
+                if let Some('e' | 'E') = self.chars.peek() {
+                    self.chars.next();
+
+                    if let Some('+' | '-') = self.chars.peek() {
+                        self.chars.next();
+                    }
+
+                    // The exponent needs at least one digit.
+                    is_valid = matches!(self.chars.peek(), Some('0'..='9'));
+
+                    while let Some('0'..='9' | '_') = self.chars.peek() {
+                        self.chars.next();
+                    }
+                }
+
+                if !is_valid || self.chars.peek().is_some_and(is_identifier_continuation) {
+                    // Something like `12abc` or `1__e` is malformed as a whole.
+                    while self.chars.peek().is_some_and(is_identifier_continuation) {
+                        self.chars.next();
+                    }
+
+                    Token::Invalid(unsafe { span!() })
+                } else {
+                    Token::Number(unsafe { NumberSource::new_unchecked(span!()) })
+                }
             }
             _ => {
                 while !is_token_start(self.chars.peek()) {
@@ -1,14 +1,14 @@
 #![allow(non_snake_case)]
 
 mod Lexer {
-    use crate::tokens::CharacterSource;
+    use crate::tokens::{CharacterSource, StringSource};
 
     use super::super::*;
 
     #[test]
     fn next() {
         let mut lexer =
-            Lexer::new(" \n\r\t iäß_a347 123_456_789__\0'ß';@^,=<>(){}[]+-*/|.&!$%§?~`:");
+            Lexer::new(" \n\r\t iäß_a347 123_456_789__\0'ß'\"s\";@^,=<>(){}[]+-*/|.&!$%§?~`:");
 
         assert_eq!(
             lexer.next(),
@@ -36,7 +36,12 @@ mod Lexer {
                 CharacterSource::new_unchecked("'ß'")
             }))
         );
-        // TODO: test string
+        assert_eq!(
+            lexer.next(),
+            Some(Token::String(unsafe {
+                StringSource::new_unchecked("\"s\"")
+            }))
+        );
         assert_eq!(lexer.next(), Some(Token::Semicolon));
         assert_eq!(lexer.next(), Some(Token::At));
         assert_eq!(lexer.next(), Some(Token::Caret));
@@ -133,4 +138,174 @@ mod Lexer {
         );
         assert_eq!(lexer.next(), None);
     }
+
+    #[test]
+    fn next_string() {
+        let mut lexer = Lexer::new("\"a \\\" b\"\"multi\r\nline\"");
+
+        assert_eq!(
+            lexer.next(),
+            Some(Token::String(unsafe {
+                StringSource::new_unchecked("\"a \\\" b\"")
+            }))
+        );
+        assert_eq!(
+            lexer.next(),
+            Some(Token::String(unsafe {
+                StringSource::new_unchecked("\"multi\r\nline\"")
+            }))
+        );
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[test]
+    fn next_infix_identifier() {
+        let mut lexer = Lexer::new("`max_2``1` `a b`");
+
+        assert_eq!(lexer.next(), Some(Token::InfixIdentifier("`max_2`")));
+        assert_eq!(lexer.next(), Some(Token::Backtick));
+        assert_eq!(
+            lexer.next(),
+            Some(Token::Number(unsafe { NumberSource::new_unchecked("1") }))
+        );
+        assert_eq!(lexer.next(), Some(Token::Backtick));
+        assert_eq!(
+            lexer.next(),
+            Some(Token::Whitespace(unsafe {
+                WhitespaceSource::new_unchecked(" ")
+            }))
+        );
+        assert_eq!(lexer.next(), Some(Token::Backtick));
+        assert_eq!(lexer.next(), Some(Token::IdentifierOrKeyword("a")));
+        assert_eq!(
+            lexer.next(),
+            Some(Token::Whitespace(unsafe {
+                WhitespaceSource::new_unchecked(" ")
+            }))
+        );
+        assert_eq!(lexer.next(), Some(Token::IdentifierOrKeyword("b")));
+        assert_eq!(lexer.next(), Some(Token::Backtick));
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[test]
+    fn next_string_unterminated() {
+        let mut lexer = Lexer::new("\"abc\\\"");
+
+        assert_eq!(lexer.next(), Some(Token::Invalid("\"abc\\\"")));
+        assert_eq!(lexer.next(), None);
+
+        let mut lexer = Lexer::new("\"abc\\");
+
+        assert_eq!(lexer.next(), Some(Token::Invalid("\"abc\\")));
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[test]
+    fn next_number() {
+        let mut lexer = Lexer::new("0.5 1..5 12abc 3.x");
+
+        assert_eq!(
+            lexer.next(),
+            Some(Token::Number(unsafe { NumberSource::new_unchecked("0.5") }))
+        );
+        lexer.next();
+        assert_eq!(
+            lexer.next(),
+            Some(Token::Number(unsafe { NumberSource::new_unchecked("1") }))
+        );
+        assert_eq!(lexer.next(), Some(Token::Period));
+        assert_eq!(lexer.next(), Some(Token::Period));
+        assert_eq!(
+            lexer.next(),
+            Some(Token::Number(unsafe { NumberSource::new_unchecked("5") }))
+        );
+        lexer.next();
+        assert_eq!(lexer.next(), Some(Token::Invalid("12abc")));
+        lexer.next();
+        assert_eq!(
+            lexer.next(),
+            Some(Token::Number(unsafe { NumberSource::new_unchecked("3") }))
+        );
+        assert_eq!(lexer.next(), Some(Token::Period));
+        assert_eq!(lexer.next(), Some(Token::IdentifierOrKeyword("x")));
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[test]
+    fn next_number_radix_and_exponent() {
+        let mut lexer =
+            Lexer::new("0xFF 0o7_7 0b10 0XFF 0O7 0B1 6.02e23 1e-9 0xZZ 0x 0b12 0XG 1__e 2e+");
+
+        for source in [
+            "0xFF", "0o7_7", "0b10", "0XFF", "0O7", "0B1", "6.02e23", "1e-9",
+        ] {
+            assert_eq!(
+                lexer.next(),
+                Some(Token::Number(unsafe {
+                    NumberSource::new_unchecked(source)
+                }))
+            );
+            lexer.next();
+        }
+
+        for source in ["0xZZ", "0x", "0b12", "0XG", "1__e"] {
+            assert_eq!(lexer.next(), Some(Token::Invalid(source)));
+            lexer.next();
+        }
+
+        assert_eq!(lexer.next(), Some(Token::Invalid("2e+")));
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[test]
+    fn next_character() {
+        let mut lexer = Lexer::new(r"'\n''\'''\\''\0''\u{1F600}''ä'");
+
+        for source in [r"'\n'", r"'\''", r"'\\'", r"'\0'", r"'\u{1F600}'", "'ä'"] {
+            assert_eq!(
+                lexer.next(),
+                Some(Token::Character(unsafe {
+                    CharacterSource::new_unchecked(source)
+                }))
+            );
+        }
+
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[test]
+    fn next_character_invalid() {
+        let mut lexer = Lexer::new("'abc' ''\n'a\n'\\u{12\n");
+
+        assert_eq!(lexer.next(), Some(Token::Invalid("'abc'")));
+        assert_eq!(
+            lexer.next(),
+            Some(Token::Whitespace(unsafe {
+                WhitespaceSource::new_unchecked(" ")
+            }))
+        );
+        assert_eq!(lexer.next(), Some(Token::Invalid("''")));
+        assert_eq!(
+            lexer.next(),
+            Some(Token::Whitespace(unsafe {
+                WhitespaceSource::new_unchecked("\n")
+            }))
+        );
+        assert_eq!(lexer.next(), Some(Token::Invalid("'a")));
+        assert_eq!(
+            lexer.next(),
+            Some(Token::Whitespace(unsafe {
+                WhitespaceSource::new_unchecked("\n")
+            }))
+        );
+        assert_eq!(lexer.next(), Some(Token::Invalid("'\\u{12")));
+        assert_eq!(
+            lexer.next(),
+            Some(Token::Whitespace(unsafe {
+                WhitespaceSource::new_unchecked("\n")
+            }))
+        );
+        assert_eq!(lexer.next(), None);
+    }
 }
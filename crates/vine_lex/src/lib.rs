@@ -1,6 +1,9 @@
 #![no_std]
 #![feature(str_from_raw_parts)]
 
+extern crate alloc;
+
+pub mod dump;
 pub mod filter;
 mod lexer;
 pub mod tokens;
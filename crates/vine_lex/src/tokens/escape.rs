@@ -0,0 +1,103 @@
+use core::{ops::Range, str::CharIndices};
+
+/// An error that occurred while decoding an escape sequence
+/// in a string or character literal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EscapeError {
+    /// What went wrong.
+    pub kind: EscapeErrorKind,
+
+    /// The byte range of the offending escape sequence,
+    /// relative to the start of the literal.
+    pub range: Range<u32>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EscapeErrorKind {
+    /// A `\` followed by a character that does not start an escape sequence.
+    UnknownEscape(char),
+
+    /// A `\` without any character after it.
+    IncompleteEscape,
+
+    /// A `\u` that is not followed by `{`, one to six hexadecimal digits and `}`.
+    MalformedUnicodeEscape,
+
+    /// A `\u{...}` escape whose value is not a unicode scalar value.
+    InvalidCodePoint(u32),
+}
+
+/// Decodes an escape sequence. `start` is the offset of the `\`,
+/// which must already be consumed from `chars`.
+///
+/// Supported escape sequences are `\n`, `\r`, `\t`, `\0`, `\\`, `\"`, `\'` and `\u{...}`.
+pub(crate) fn unescape(start: usize, chars: &mut CharIndices) -> Result<char, EscapeError> {
+    let error = |kind, end: usize| EscapeError {
+        kind,
+        range: start as u32..end as u32,
+    };
+
+    Ok(match chars.next() {
+        Some((_, 'n')) => '\n',
+        Some((_, 'r')) => '\r',
+        Some((_, 't')) => '\t',
+        Some((_, '0')) => '\0',
+        Some((_, '\\')) => '\\',
+        Some((_, '"')) => '"',
+        Some((_, '\'')) => '\'',
+        Some((_, 'u')) => {
+            if !matches!(chars.clone().next(), Some((_, '{'))) {
+                return Err(error(
+                    EscapeErrorKind::MalformedUnicodeEscape,
+                    chars.offset(),
+                ));
+            }
+
+            chars.next();
+
+            let mut code_point = 0_u32;
+            let mut digits = 0;
+
+            while let Some((_, digit)) = chars.clone().next()
+                && let Some(value) = digit.to_digit(16)
+            {
+                chars.next();
+
+                if digits == 6 {
+                    return Err(error(
+                        EscapeErrorKind::MalformedUnicodeEscape,
+                        chars.offset(),
+                    ));
+                }
+
+                code_point = code_point << 4 | value;
+                digits += 1;
+            }
+
+            match chars.clone().next() {
+                Some((_, '}')) if digits > 0 => {
+                    chars.next();
+                }
+                _ => {
+                    return Err(error(
+                        EscapeErrorKind::MalformedUnicodeEscape,
+                        chars.offset(),
+                    ));
+                }
+            }
+
+            char::from_u32(code_point).ok_or_else(|| {
+                error(
+                    EscapeErrorKind::InvalidCodePoint(code_point),
+                    chars.offset(),
+                )
+            })?
+        }
+        Some((_, c)) => {
+            return Err(error(EscapeErrorKind::UnknownEscape(c), chars.offset()));
+        }
+        None => {
+            return Err(error(EscapeErrorKind::IncompleteEscape, chars.offset()));
+        }
+    })
+}
@@ -1,6 +1,12 @@
+mod escape;
+
+use alloc::{borrow::Cow, string::String};
 use core::hint::unreachable_unchecked;
 
+use escape::unescape;
+pub use escape::{EscapeError, EscapeErrorKind};
 use parser_tools::TokenLength;
+use vine_num::{Natural, Rational};
 
 #[cfg(test)]
 mod tests;
@@ -13,6 +19,10 @@ pub enum Token<'source> {
     /// An identifer or a keyword.
     IdentifierOrKeyword(&'source str),
 
+    /// An identifier in backticks, like `` `max` ``, used as an infix operator.
+    /// The source includes the backticks.
+    InfixIdentifier(&'source str),
+
     /// A number
     Number(NumberSource<'source>),
 
@@ -25,6 +35,7 @@ pub enum Token<'source> {
     /// A line comment or a block comment.
     Comment(&'source str),
 
+    /// A string `"..."`.
     String(StringSource<'source>),
 
     /// `;`
@@ -116,7 +127,9 @@ impl TokenLength for Token<'_> {
     fn length(&self) -> u32 {
         match self {
             Self::Whitespace(source) => source.as_str().len() as u32,
-            Self::IdentifierOrKeyword(source) => source.len() as u32,
+            Self::IdentifierOrKeyword(source) | Self::InfixIdentifier(source) => {
+                source.len() as u32
+            }
             Self::Number(source) => source.as_str().len() as u32,
             Self::Invalid(source) => source.len() as u32,
             Self::Character(source) => source.as_str().len() as u32,
@@ -161,6 +174,9 @@ macro_rules! impl_source_for {
                 self.0
             }
 
+            /// # Safety
+            ///
+            /// `input` must be a complete token of this kind, as produced by the lexer.
             pub const unsafe fn new_unchecked(input: &'source str) -> Self {
                 Self(input)
             }
@@ -173,31 +189,130 @@ pub struct NumberSource<'source>(&'source str);
 
 impl_source_for!(NumberSource);
 
+/// The base of a number literal.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Radix {
+    /// `0b...` or `0B...`
+    Binary,
+
+    /// `0o...` or `0O...`
+    Octal,
+
+    /// No prefix.
+    Decimal,
+
+    /// `0x...` or `0X...`
+    Hexadecimal,
+}
+
+impl Radix {
+    /// Returns the radix denoted by the character after a leading `0`, if any.
+    /// The prefix may be lowercase or uppercase.
+    pub const fn from_prefix(c: char) -> Option<Self> {
+        match c {
+            'b' | 'B' => Some(Self::Binary),
+            'o' | 'O' => Some(Self::Octal),
+            'x' | 'X' => Some(Self::Hexadecimal),
+            _ => None,
+        }
+    }
+
+    pub const fn value(self) -> u32 {
+        match self {
+            Self::Binary => 2,
+            Self::Octal => 8,
+            Self::Decimal => 10,
+            Self::Hexadecimal => 16,
+        }
+    }
+}
+
+/// The largest absolute exponent allowed in scientific notation.
+pub const MAX_EXPONENT: u32 = u16::MAX as u32;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NumberError {
+    /// The exponent is larger than [`MAX_EXPONENT`].
+    ExponentTooLarge,
+}
+
 impl NumberSource<'_> {
-    pub fn parse(self) -> u64 {
+    pub fn radix(self) -> Radix {
+        match self.0.as_bytes() {
+            [b'0', prefix, ..] => Radix::from_prefix(*prefix as char).unwrap_or(Radix::Decimal),
+            _ => Radix::Decimal,
+        }
+    }
+
+    /// Parses the literal into an exact rational, so `0.1` is exactly `1/10`
+    /// and `1e-9` is exactly `1/1000000000`.
+    pub fn parse(self) -> Result<Rational, NumberError> {
+        let radix = self.radix();
+
+        if radix != Radix::Decimal {
+            let mut value = Natural::ZERO;
+
+            for c in self.0[2..].chars() {
+                match c.to_digit(radix.value()) {
+                    Some(digit) => value.mul_add_small(radix.value(), digit),
+                    None if c == '_' => {}
+                    None => unsafe { unreachable_unchecked() },
+                }
+            }
+
+            return Ok(Rational::from(value));
+        }
+
+        let mut numerator = Natural::ZERO;
+        let mut denominator = Natural::one();
+        let mut is_fraction = false;
         let mut bytes = self.as_str().bytes();
 
-        let mut number = match bytes.next() {
-            Some(start @ b'0'..=b'9') => (start - b'0') as u64,
-            _ => unsafe { unreachable_unchecked() },
-        };
+        for byte in bytes.by_ref() {
+            match byte {
+                digit @ b'0'..=b'9' => {
+                    numerator.mul_add_small(10, (digit - b'0') as u32);
 
-        loop {
-            match bytes.next() {
-                Some(n @ b'0'..=b'9') => {
-                    // TODO: refine this.
+                    if is_fraction {
+                        denominator.mul_add_small(10, 0);
+                    }
+                }
+                b'_' => {}
+                b'.' => is_fraction = true,
+                b'e' | b'E' => break,
+                _ => unsafe { unreachable_unchecked() },
+            }
+        }
 
-                    number = number
-                        .checked_add((n - b'0') as u64)
-                        .expect("number to big")
+        let mut exponent = 0_u32;
+        let mut is_negative_exponent = false;
+
+        for byte in bytes {
+            match byte {
+                digit @ b'0'..=b'9' => {
+                    exponent = exponent
+                        .saturating_mul(10)
+                        .saturating_add((digit - b'0') as u32);
                 }
-                Some(b'_') => {}
-                Some(b'.') => todo!("decimals not implemented"),
-                _ => break,
+                b'-' => is_negative_exponent = true,
+                b'+' | b'_' => {}
+                _ => unsafe { unreachable_unchecked() },
             }
         }
 
-        number
+        if exponent > MAX_EXPONENT {
+            return Err(NumberError::ExponentTooLarge);
+        }
+
+        let scale = Natural::from(10_u32).pow(exponent);
+
+        if is_negative_exponent {
+            denominator = &denominator * &scale;
+        } else {
+            numerator = &numerator * &scale;
+        }
+
+        Ok(Rational::new(false, numerator, denominator))
     }
 }
 
@@ -207,8 +322,18 @@ pub struct CharacterSource<'source>(&'source str);
 impl_source_for!(CharacterSource);
 
 impl CharacterSource<'_> {
-    pub fn parse(&self) -> char {
-        todo!("parse characters")
+    /// Decodes the character literal.
+    pub fn parse(self) -> Result<char, EscapeError> {
+        // Iterate over the whole literal (minus the closing quote)
+        // so that error ranges are relative to the start of the literal.
+        let mut chars = self.0[..self.0.len() - 1].char_indices();
+        chars.next();
+
+        match chars.next() {
+            Some((offset, '\\')) => unescape(offset, &mut chars),
+            Some((_, c)) => Ok(c),
+            None => unsafe { unreachable_unchecked() },
+        }
     }
 }
 
@@ -219,7 +344,7 @@ impl_source_for!(WhitespaceSource);
 
 impl WhitespaceSource<'_> {
     pub fn contains_a_line_break(self) -> bool {
-        self.0.contains(|c| c == '\n' || c == '\r')
+        self.0.contains(['\n', '\r'])
     }
 }
 
@@ -227,3 +352,36 @@ impl WhitespaceSource<'_> {
 pub struct StringSource<'source>(&'source str);
 
 impl_source_for!(StringSource);
+
+impl<'source> StringSource<'source> {
+    /// Returns the contents between the quotes, without decoding escape sequences.
+    pub fn contents(self) -> &'source str {
+        &self.0[1..self.0.len() - 1]
+    }
+
+    /// Decodes the string literal. The result borrows from the source
+    /// if the literal contains no escape sequences.
+    pub fn parse(self) -> Result<Cow<'source, str>, EscapeError> {
+        let contents = self.contents();
+
+        if !contents.contains('\\') {
+            return Ok(Cow::Borrowed(contents));
+        }
+
+        let mut string = String::with_capacity(contents.len());
+
+        // Iterate over the whole literal (minus the closing quote)
+        // so that error ranges are relative to the start of the literal.
+        let mut chars = self.0[..self.0.len() - 1].char_indices();
+        chars.next();
+
+        while let Some((offset, c)) = chars.next() {
+            string.push(match c {
+                '\\' => unescape(offset, &mut chars)?,
+                c => c,
+            });
+        }
+
+        Ok(Cow::Owned(string))
+    }
+}
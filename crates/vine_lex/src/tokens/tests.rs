@@ -0,0 +1,187 @@
+#![allow(non_snake_case)]
+
+mod NumberSource {
+    use alloc::string::ToString;
+
+    use vine_num::{Natural, Rational};
+
+    use super::super::*;
+
+    fn parse(source: &str) -> Result<Rational, NumberError> {
+        unsafe { NumberSource::new_unchecked(source) }.parse()
+    }
+
+    #[test]
+    fn parse_decimal() {
+        assert_eq!(parse("1_000"), Ok(Rational::from(1000_u64)));
+        assert_eq!(
+            parse("0.1"),
+            Ok(Rational::new(false, Natural::one(), Natural::from(10_u32)))
+        );
+        assert_eq!(
+            parse("2.50"),
+            Ok(Rational::new(
+                false,
+                Natural::from(5_u32),
+                Natural::from(2_u32)
+            ))
+        );
+        assert_eq!(
+            parse("123456789012345678901234567890").map(|n| n.to_string()),
+            Ok("123456789012345678901234567890".into())
+        );
+    }
+
+    #[test]
+    fn parse_exponent() {
+        assert_eq!(
+            parse("6.02e23").map(|n| n.to_string()),
+            Ok("602000000000000000000000".into())
+        );
+        assert_eq!(
+            parse("1e-9").map(|n| n.to_string()),
+            Ok("1/1000000000".into())
+        );
+        assert_eq!(parse("25E+1_0"), parse("250000000000"));
+        assert_eq!(parse("1e65536"), Err(NumberError::ExponentTooLarge));
+    }
+
+    #[test]
+    fn parse_radix() {
+        assert_eq!(parse("0xFF_ff"), Ok(Rational::from(0xffff_u64)));
+        assert_eq!(parse("0o17"), Ok(Rational::from(0o17_u64)));
+        assert_eq!(parse("0b1010_0101"), Ok(Rational::from(0b1010_0101_u64)));
+        assert_eq!(parse("0XFF"), Ok(Rational::from(0xff_u64)));
+        assert_eq!(parse("0O17"), Ok(Rational::from(0o17_u64)));
+        assert_eq!(parse("0B11"), Ok(Rational::from(0b11_u64)));
+        assert_eq!(
+            parse("0x1_0000_0000_0000_0000").map(|n| n.to_string()),
+            Ok("18446744073709551616".into())
+        );
+    }
+
+    #[test]
+    fn radix() {
+        for (source, radix) in [
+            ("0b1", Radix::Binary),
+            ("0o1", Radix::Octal),
+            ("0.5", Radix::Decimal),
+            ("0", Radix::Decimal),
+            ("0xa", Radix::Hexadecimal),
+            ("0B1", Radix::Binary),
+            ("0O1", Radix::Octal),
+            ("0XA", Radix::Hexadecimal),
+        ] {
+            assert_eq!(
+                unsafe { NumberSource::new_unchecked(source) }.radix(),
+                radix
+            );
+        }
+    }
+}
+
+mod CharacterSource {
+    use super::super::*;
+
+    #[test]
+    fn parse() {
+        for (source, expected) in [
+            ("'a'", 'a'),
+            ("'ß'", 'ß'),
+            (r"'\n'", '\n'),
+            (r"'\''", '\''),
+            (r"'\\'", '\\'),
+            (r"'\0'", '\0'),
+            (r"'\u{1F600}'", '😀'),
+        ] {
+            assert_eq!(
+                unsafe { CharacterSource::new_unchecked(source) }.parse(),
+                Ok(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn parse_invalid_escapes() {
+        assert_eq!(
+            unsafe { CharacterSource::new_unchecked(r"'\x'") }.parse(),
+            Err(EscapeError {
+                kind: EscapeErrorKind::UnknownEscape('x'),
+                range: 1..3,
+            })
+        );
+        assert_eq!(
+            unsafe { CharacterSource::new_unchecked(r"'\u{110000}'") }.parse(),
+            Err(EscapeError {
+                kind: EscapeErrorKind::InvalidCodePoint(0x110000),
+                range: 1..11,
+            })
+        );
+        assert_eq!(
+            unsafe { CharacterSource::new_unchecked(r"'\u{41'") }.parse(),
+            Err(EscapeError {
+                kind: EscapeErrorKind::MalformedUnicodeEscape,
+                range: 1..6,
+            })
+        );
+    }
+}
+
+mod StringSource {
+    use alloc::borrow::Cow;
+
+    use super::super::*;
+
+    #[test]
+    fn parse_without_escapes() {
+        let source = unsafe { StringSource::new_unchecked("\"hello\nworld\"") };
+
+        assert_eq!(source.parse(), Ok(Cow::Borrowed("hello\nworld")));
+    }
+
+    #[test]
+    fn parse_escapes() {
+        let source =
+            unsafe { StringSource::new_unchecked(r#""a\nb\tc\\d\"e\'f\0g\u{1F600}\u{e4}\r""#) };
+
+        assert_eq!(
+            source.parse(),
+            Ok(Cow::Owned::<str>("a\nb\tc\\d\"e'f\0g😀ä\r".into()))
+        );
+    }
+
+    #[test]
+    fn parse_invalid_escapes() {
+        let source = unsafe { StringSource::new_unchecked(r#""ab\q""#) };
+
+        assert_eq!(
+            source.parse(),
+            Err(EscapeError {
+                kind: EscapeErrorKind::UnknownEscape('q'),
+                range: 3..5,
+            })
+        );
+
+        let source = unsafe { StringSource::new_unchecked(r#""\u{D800}""#) };
+
+        assert_eq!(
+            source.parse(),
+            Err(EscapeError {
+                kind: EscapeErrorKind::InvalidCodePoint(0xD800),
+                range: 1..9,
+            })
+        );
+
+        for malformed in [r#""\u""#, r#""\u{}""#, r#""\u{1234567}""#, r#""\u{12""#] {
+            let source = unsafe { StringSource::new_unchecked(malformed) };
+
+            assert!(matches!(
+                source.parse(),
+                Err(EscapeError {
+                    kind: EscapeErrorKind::MalformedUnicodeEscape,
+                    ..
+                })
+            ));
+        }
+    }
+}
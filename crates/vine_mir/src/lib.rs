@@ -1 +1,21 @@
+//! The mid-level intermediate representation.
+//!
+//! A [`Program`] is lowered from the core items of a resolved module with [`lower`].
+//! Its functions are control flow graphs of [`Block`]s, whose statements assign [`Rvalue`]s to locals.
+//! Names are resolved to locals, [`Global`]s and [`Atom`]s; matches, ifs and
+//! short-circuiting operators are lowered to tests and branches; and functions
+//! capture the locals of enclosing functions explicitly.
+//!
+//! Programs can be printed with [`Display`](core::fmt::Display) and checked
+//! for consistency with [`Program::validate`].
+
+mod lower;
+mod print;
+#[cfg(test)]
+mod tests;
 pub mod tree;
+mod validate;
+
+pub use lower::*;
+pub use tree::*;
+pub use validate::*;
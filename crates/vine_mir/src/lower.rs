@@ -0,0 +1,848 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use parser_tools::Span;
+use vine_parse::{
+    ast::BinaryOperation,
+    lower::{ARGUMENT, Case, Core, Literal, Pattern},
+};
+use vine_resolve::{BindingId, BindingKind, Resolution};
+
+use crate::tree::*;
+
+/// Lowers the items of a resolved module, lowered to the core language
+/// with [`lower_module`](vine_parse::lower::lower_module), to a [`Program`].
+///
+/// Parts of the module with errors lower to [`Failure::Error`].
+pub fn lower<'source>(
+    items: &[Span<Core<'source>>],
+    resolution: &Resolution<'source>,
+) -> Program<'source> {
+    let mut lowerer = Lowerer::new(resolution);
+    lowerer.lower_module(items);
+    lowerer.program
+}
+
+struct Lowerer<'a, 'source> {
+    resolution: &'a Resolution<'source>,
+    program: Program<'source>,
+    atoms: BTreeMap<BindingId, AtomId>,
+    globals: BTreeMap<BindingId, GlobalId>,
+
+    /// The functions being lowered, innermost last.
+    builders: Vec<Builder<'source>>,
+}
+
+/// A module-level item, with the global it defines.
+enum Item<'e, 'source> {
+    /// A definition of the global bound at `binding` by `pattern`.
+    Definition {
+        global: GlobalId,
+        pattern: &'e Span<Pattern<'source>>,
+        binding: Range<u32>,
+        value: &'e Span<Core<'source>>,
+    },
+    Expression(&'e Span<Core<'source>>),
+}
+
+/// A function being lowered.
+struct Builder<'source> {
+    function: Function<'source>,
+
+    /// The block statements are appended to.
+    current: BlockId,
+
+    /// The locals of the bindings in the function, including captured ones.
+    locals: BTreeMap<BindingId, Local>,
+
+    /// The captured bindings, in the order of [`Function::captures`].
+    captured: Vec<BindingId>,
+}
+
+impl<'source> Builder<'source> {
+    fn new(name: Option<&'source str>, range: Range<u32>) -> Self {
+        Self {
+            function: Function {
+                name,
+                parameter: None,
+                captures: Vec::new(),
+                locals: Vec::new(),
+                blocks: vec![Block {
+                    statements: Vec::new(),
+                    terminator: Terminator::Fail {
+                        failure: Failure::Error,
+                        range: range.clone(),
+                    },
+                }],
+                range,
+            },
+            current: BlockId(0),
+            locals: BTreeMap::new(),
+            captured: Vec::new(),
+        }
+    }
+}
+
+impl<'a, 'source> Lowerer<'a, 'source> {
+    fn new(resolution: &'a Resolution<'source>) -> Self {
+        let mut lowerer = Self {
+            resolution,
+            program: Program::default(),
+            atoms: BTreeMap::new(),
+            globals: BTreeMap::new(),
+            builders: Vec::new(),
+        };
+
+        let builtins = ["True", "False"].map(|name| {
+            resolution
+                .bindings()
+                .find(|(_, binding)| binding.kind == BindingKind::Builtin && binding.name == name)
+                .unwrap()
+        });
+
+        let abstracts = resolution
+            .bindings()
+            .filter(|(_, binding)| binding.kind == BindingKind::Abstract);
+
+        for (id, binding) in builtins.into_iter().chain(abstracts) {
+            lowerer
+                .atoms
+                .insert(id, AtomId(lowerer.program.atoms.len() as u32));
+            lowerer.program.atoms.push(Atom {
+                name: binding.name,
+                binding: id,
+            });
+        }
+
+        lowerer
+    }
+
+    fn lower_module(&mut self, module: &[Span<Core<'source>>]) {
+        let mut items = Vec::new();
+
+        for item in module {
+            match &Core::unattributed(item).value {
+                Core::Definition { pattern, value } => {
+                    for Span {
+                        value: name,
+                        range: binding,
+                    } in Pattern::bound_names(pattern)
+                    {
+                        let Some(id) = self.resolution.binding_of(&binding) else {
+                            continue;
+                        };
+
+                        let global = *self.globals.entry(id).or_insert_with(|| {
+                            self.program.globals.push(Global {
+                                name,
+                                binding: id,
+                                initializers: Vec::new(),
+                            });
+                            GlobalId(self.program.globals.len() as u32 - 1)
+                        });
+
+                        items.push(Item::Definition {
+                            global,
+                            pattern,
+                            binding,
+                            value,
+                        });
+                    }
+                }
+                Core::Abstract { .. } => {}
+                _ => items.push(Item::Expression(item)),
+            }
+        }
+
+        // The globals are all known now, so the initializers can refer to them.
+        for item in items {
+            let (global, pattern, binding, value) = match item {
+                Item::Definition {
+                    global,
+                    pattern,
+                    binding,
+                    value,
+                } => (global, pattern, binding, value),
+                Item::Expression(expression) => {
+                    let function = self.thunk(None, expression, |_, operand| operand);
+                    self.program.expressions.push(function);
+                    continue;
+                }
+            };
+
+            let name = self.program.global(global).name;
+            let function = self.thunk(Some(name), value, |lowerer, operand| {
+                if let Pattern::Binding(_) = pattern.value {
+                    return operand;
+                }
+
+                // A destructuring definition: each name has its own initializer
+                // matching the value against the whole pattern.
+                let fail = lowerer.new_block();
+                lowerer.pattern(pattern, operand, fail);
+                let id = lowerer.resolution.binding_of(&binding).unwrap();
+                let value = Operand::Local(lowerer.builder().locals[&id]);
+                let done = lowerer.current();
+                lowerer.switch_to(fail);
+                lowerer.terminate(Terminator::Fail {
+                    failure: Failure::NoMatch,
+                    range: pattern.range.clone(),
+                });
+                lowerer.switch_to(done);
+                value
+            });
+
+            self.program.globals[global.index()]
+                .initializers
+                .push(function);
+        }
+    }
+
+    /// Lowers an expression to a function without parameter returning `finish` of its value.
+    fn thunk(
+        &mut self,
+        name: Option<&'source str>,
+        expression: &Span<Core<'source>>,
+        finish: impl FnOnce(&mut Self, Operand<'source>) -> Operand<'source>,
+    ) -> FunctionId {
+        self.builders
+            .push(Builder::new(name, expression.range.clone()));
+
+        let operand = self.expression(expression);
+        let operand = finish(self, operand);
+        self.terminate(Terminator::Return(operand));
+        self.finish_function()
+    }
+
+    fn expression(&mut self, expression: &Span<Core<'source>>) -> Operand<'source> {
+        let range = expression.range.clone();
+
+        match &expression.value {
+            Core::Error => self.fail(Failure::Error, range),
+            Core::Literal(literal) => Operand::Constant(constant(literal)),
+            Core::Identifier(ARGUMENT) => match self.builder().function.parameter {
+                Some(parameter) => Operand::Local(parameter),
+                None => self.fail(Failure::Error, range),
+            },
+            Core::Identifier(name) => match self.resolution.binding_of(&range) {
+                Some(id) => self.reference(id, name, range),
+                None => self.fail(Failure::Error, range),
+            },
+            Core::Tuple(items) => {
+                let operands = items.iter().map(|item| self.expression(item)).collect();
+                self.assign(Rvalue::Tuple(operands), range)
+            }
+            Core::Record(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, value)| (name.value, self.expression(value)))
+                    .collect();
+
+                self.assign(Rvalue::Record(fields), range)
+            }
+            Core::Set(items) => {
+                let operands = items.iter().map(|item| self.expression(item)).collect();
+                self.assign(Rvalue::Set(operands), range)
+            }
+            Core::List(items) => {
+                let operands = items.iter().map(|item| self.expression(item)).collect();
+                self.assign(Rvalue::List(operands), range)
+            }
+            Core::Block(items) => {
+                let mut operand = None;
+
+                for item in items {
+                    operand = Some(self.expression(item));
+                }
+
+                operand.unwrap_or_else(|| self.assign(Rvalue::Tuple(Vec::new()), range))
+            }
+            Core::Definition { pattern, value } => {
+                let value = self.expression(value);
+                let fail = self.new_block();
+                self.pattern(pattern, value, fail);
+
+                let done = self.current();
+                self.switch_to(fail);
+                self.terminate(Terminator::Fail {
+                    failure: Failure::NoMatch,
+                    range: pattern.range.clone(),
+                });
+                self.switch_to(done);
+                self.assign(Rvalue::Tuple(Vec::new()), range)
+            }
+            Core::Unary { operation, operand } => {
+                let operand = self.expression(operand);
+                let overload = self.overload(operation.merge_name());
+
+                self.assign(
+                    Rvalue::Unary {
+                        operation: *operation,
+                        operand,
+                        overload,
+                    },
+                    range,
+                )
+            }
+            Core::Field { record, name } => {
+                let operand = self.expression(record);
+                self.assign(
+                    Rvalue::Field {
+                        operand,
+                        name: name.value,
+                    },
+                    range,
+                )
+            }
+            Core::Binary {
+                left,
+                operation:
+                    operation @ (BinaryOperation::And
+                    | BinaryOperation::LogicalAnd
+                    | BinaryOperation::Or
+                    | BinaryOperation::LogicalOr),
+                right,
+            } => {
+                // The right operand is only evaluated if the left one does not decide the result.
+                let is_and = matches!(
+                    operation,
+                    BinaryOperation::And | BinaryOperation::LogicalAnd
+                );
+                let condition = self.expression(left);
+                let result = self.temporary();
+                let evaluate = self.new_block();
+                let decided = self.new_block();
+                let join = self.new_block();
+
+                self.terminate(Terminator::Branch {
+                    condition,
+                    then: if is_and { evaluate } else { decided },
+                    otherwise: if is_and { decided } else { evaluate },
+                });
+
+                self.switch_to(evaluate);
+                let value = self.expression(right);
+                self.assign_to(result, Rvalue::Use(value), right.range.clone());
+                self.terminate(Terminator::Goto(join));
+
+                self.switch_to(decided);
+                let atom = if is_and {
+                    Program::FALSE
+                } else {
+                    Program::TRUE
+                };
+                self.assign_to(
+                    result,
+                    Rvalue::Use(Operand::Constant(Value::Atom(atom))),
+                    left.range.clone(),
+                );
+                self.terminate(Terminator::Goto(join));
+
+                self.switch_to(join);
+                Operand::Local(result)
+            }
+            Core::Binary {
+                left,
+                operation,
+                right,
+            } => {
+                let left = self.expression(left);
+                let right = self.expression(right);
+                let overload = operation.merge_name().and_then(|name| self.overload(name));
+
+                self.assign(
+                    Rvalue::Binary {
+                        operation: *operation,
+                        left,
+                        right,
+                        overload,
+                    },
+                    range,
+                )
+            }
+            Core::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                let condition = self.expression(condition);
+                let result = self.temporary();
+                let then_block = self.new_block();
+                let otherwise_block = self.new_block();
+                let join = self.new_block();
+
+                self.terminate(Terminator::Branch {
+                    condition,
+                    then: then_block,
+                    otherwise: otherwise_block,
+                });
+
+                for (block, branch) in [(then_block, then), (otherwise_block, otherwise)] {
+                    self.switch_to(block);
+                    let value = self.expression(branch);
+                    self.assign_to(result, Rvalue::Use(value), branch.range.clone());
+                    self.terminate(Terminator::Goto(join));
+                }
+
+                self.switch_to(join);
+                Operand::Local(result)
+            }
+            Core::Match { on, cases } => {
+                let value = self.expression(on);
+                let value = self.materialize(value, on.range.clone());
+                let result = self.temporary();
+                let join = self.new_block();
+
+                for case in cases {
+                    let next = self.new_block();
+                    self.case(&case.value, value.clone(), next);
+
+                    let body = self.expression(&case.value.body);
+                    self.assign_to(result, Rvalue::Use(body), case.range.clone());
+                    self.terminate(Terminator::Goto(join));
+                    self.switch_to(next);
+                }
+
+                self.terminate(Terminator::Fail {
+                    failure: Failure::NoMatch,
+                    range: on.range.clone(),
+                });
+                self.switch_to(join);
+                Operand::Local(result)
+            }
+            Core::Call { function, argument } => {
+                let function = self.expression(function);
+                let argument = self.expression(argument);
+                self.assign(Rvalue::Call { function, argument }, range)
+            }
+            Core::Function {
+                parameter: pattern,
+                domain,
+                body,
+            } => {
+                let name = self.builder().function.name;
+                self.builders.push(Builder::new(name, range.clone()));
+
+                let parameter = self.temporary();
+                self.builder_mut().function.parameter = Some(parameter);
+
+                // Only functions whose parameter is tested have a block for
+                // arguments outside the domain.
+                let outside =
+                    (domain.is_some() || self.can_fail(pattern)).then(|| self.new_block());
+
+                // Irrefutable patterns never continue in the block they are given.
+                let fail = outside.unwrap_or(self.current());
+                self.pattern(pattern, Operand::Local(parameter), fail);
+
+                if let Some(domain) = domain {
+                    self.domain(Operand::Local(parameter), domain, fail);
+                }
+
+                let value = self.expression(body);
+                self.terminate(Terminator::Return(value));
+
+                if let Some(outside) = outside {
+                    self.switch_to(outside);
+                    self.terminate(Terminator::Fail {
+                        failure: Failure::OutsideDomain,
+                        range: pattern.range.clone(),
+                    });
+                }
+
+                let captured = self.builder().captured.clone();
+                let function = self.finish_function();
+                let captures = captured
+                    .into_iter()
+                    .map(|id| Operand::Local(self.local(id).unwrap()))
+                    .collect();
+
+                self.assign(Rvalue::Closure { function, captures }, range)
+            }
+            Core::Abstract { .. } => self.assign(Rvalue::Tuple(Vec::new()), range),
+            Core::Attributed {
+                expression: inner, ..
+            } => self.expression(inner),
+        }
+    }
+
+    /// Lowers the tests of a match case, continuing in `next` if it does not match.
+    fn case(&mut self, case: &Case<'source>, value: Operand<'source>, next: BlockId) {
+        self.pattern(&case.pattern, value.clone(), next);
+
+        if let Some(domain) = &case.domain {
+            self.domain(value, domain, next);
+        }
+    }
+
+    /// Tests whether `value` is in a domain, continuing in `fail` if not.
+    fn domain(&mut self, value: Operand<'source>, domain: &Span<Core<'source>>, fail: BlockId) {
+        let domain_value = self.expression(domain);
+        let condition = self.assign(
+            Rvalue::In {
+                operand: value,
+                domain: domain_value,
+            },
+            domain.range.clone(),
+        );
+        self.branch_or(condition, fail);
+    }
+
+    /// Lowers the tests of a pattern against `value` and binds its names,
+    /// continuing in `fail` if it does not match.
+    fn pattern(
+        &mut self,
+        pattern: &Span<Pattern<'source>>,
+        value: Operand<'source>,
+        fail: BlockId,
+    ) {
+        let range = pattern.range.clone();
+
+        match &pattern.value {
+            Pattern::Error => {
+                self.fail(Failure::Error, range);
+            }
+            // The parameter of a function with alternatives is the argument itself.
+            Pattern::Wildcard | Pattern::Binding(ARGUMENT) => {}
+            Pattern::Binding(name) => {
+                let Some(id) = self.resolution.binding_of(&range) else {
+                    return;
+                };
+
+                match self.resolution.binding(id).kind {
+                    BindingKind::Abstract | BindingKind::Builtin => {
+                        // A constant, matched by equality.
+                        let Operand::Constant(constant) = self.reference(id, name, range.clone())
+                        else {
+                            self.fail(Failure::Error, range);
+                            return;
+                        };
+
+                        self.test(value, Test::Equal(constant), fail, range);
+                    }
+                    _ => {
+                        let local = self.bind(id, name);
+                        self.assign_to(local, Rvalue::Use(value), range);
+                    }
+                }
+            }
+            Pattern::Literal(literal) => {
+                self.test(value, Test::Equal(constant(literal)), fail, range);
+            }
+            Pattern::Tuple(components) => {
+                let value = self.materialize(value, range.clone());
+                self.test(
+                    value.clone(),
+                    Test::Tuple(components.len()),
+                    fail,
+                    range.clone(),
+                );
+
+                for (index, component) in components.iter().enumerate() {
+                    let operand = self.assign(
+                        Rvalue::Component {
+                            operand: value.clone(),
+                            index,
+                        },
+                        component.range.clone(),
+                    );
+                    self.pattern(component, operand, fail);
+                }
+            }
+            Pattern::Record(fields) => {
+                let value = self.materialize(value, range.clone());
+                let names = fields.iter().map(|(name, _)| name.value).collect();
+                self.test(value.clone(), Test::Record(names), fail, range);
+
+                for (name, field) in fields {
+                    let operand = self.assign(
+                        Rvalue::Field {
+                            operand: value.clone(),
+                            name: name.value,
+                        },
+                        field.range.clone(),
+                    );
+                    self.pattern(field, operand, fail);
+                }
+            }
+            Pattern::Or(alternatives) => {
+                let value = self.materialize(value, range);
+                let join = self.new_block();
+
+                for (index, alternative) in alternatives.iter().enumerate() {
+                    let next = if index + 1 == alternatives.len() {
+                        fail
+                    } else {
+                        self.new_block()
+                    };
+
+                    self.pattern(alternative, value.clone(), next);
+                    self.terminate(Terminator::Goto(join));
+                    self.switch_to(next);
+                }
+
+                self.switch_to(join);
+            }
+        }
+    }
+
+    /// Whether a pattern tests the value, so that matching it can fail.
+    fn can_fail(&self, pattern: &Span<Pattern<'source>>) -> bool {
+        match &pattern.value {
+            Pattern::Error | Pattern::Wildcard | Pattern::Binding(ARGUMENT) => false,
+            Pattern::Binding(_) => self
+                .resolution
+                .binding_of(&pattern.range)
+                .is_some_and(|id| {
+                    matches!(
+                        self.resolution.binding(id).kind,
+                        BindingKind::Abstract | BindingKind::Builtin
+                    )
+                }),
+            Pattern::Literal(_) | Pattern::Tuple(_) | Pattern::Record(_) | Pattern::Or(_) => true,
+        }
+    }
+
+    fn test(
+        &mut self,
+        value: Operand<'source>,
+        test: Test<'source>,
+        fail: BlockId,
+        range: Range<u32>,
+    ) {
+        let condition = self.assign(
+            Rvalue::Test {
+                operand: value,
+                test,
+            },
+            range,
+        );
+        self.branch_or(condition, fail);
+    }
+
+    /// Continues in a new block if `condition` is `True` and in `fail` otherwise.
+    fn branch_or(&mut self, condition: Operand<'source>, fail: BlockId) {
+        let then = self.new_block();
+
+        self.terminate(Terminator::Branch {
+            condition,
+            then,
+            otherwise: fail,
+        });
+        self.switch_to(then);
+    }
+
+    /// Returns the operand for the value of a binding.
+    fn reference(
+        &mut self,
+        id: BindingId,
+        name: &'source str,
+        range: Range<u32>,
+    ) -> Operand<'source> {
+        if let Some(atom) = self.atoms.get(&id) {
+            return Operand::Constant(Value::Atom(*atom));
+        }
+
+        if let Some(global) = self.globals.get(&id) {
+            return Operand::Global(*global);
+        }
+
+        if let Some(builtin) = Builtin::from_name(name)
+            && self.resolution.binding(id).kind == BindingKind::Builtin
+        {
+            return Operand::Builtin(builtin);
+        }
+
+        match self.local(id) {
+            Some(local) => Operand::Local(local),
+            None => self.fail(Failure::Error, range),
+        }
+    }
+
+    /// Returns the local of a binding in the current function, capturing it
+    /// from the enclosing functions if needed.
+    fn local(&mut self, id: BindingId) -> Option<Local> {
+        self.local_in(self.builders.len() - 1, id)
+    }
+
+    fn local_in(&mut self, depth: usize, id: BindingId) -> Option<Local> {
+        if let Some(local) = self.builders[depth].locals.get(&id) {
+            return Some(*local);
+        }
+
+        if depth == 0 {
+            return None;
+        }
+
+        self.local_in(depth - 1, id)?;
+
+        let builder = &mut self.builders[depth];
+        let local = Local(builder.function.locals.len() as u32);
+        builder.function.locals.push(LocalInfo {
+            binding: Some(id),
+            name: Some(self.resolution.binding(id).name),
+        });
+        builder.function.captures.push(local);
+        builder.captured.push(id);
+        builder.locals.insert(id, local);
+        Some(local)
+    }
+
+    /// Returns the local of a binding bound in the current function.
+    fn bind(&mut self, id: BindingId, name: &'source str) -> Local {
+        if let Some(local) = self.builder().locals.get(&id) {
+            return *local;
+        }
+
+        let builder = self.builder_mut();
+        let local = Local(builder.function.locals.len() as u32);
+        builder.function.locals.push(LocalInfo {
+            binding: Some(id),
+            name: Some(name),
+        });
+        builder.locals.insert(id, local);
+        local
+    }
+
+    /// Returns the global with the `@merge` definitions named `name`.
+    fn overload(&self, name: &str) -> Option<GlobalId> {
+        self.globals
+            .iter()
+            .find(|(id, _)| {
+                self.resolution.is_merged(**id) && self.resolution.binding(**id).name == name
+            })
+            .map(|(_, global)| *global)
+    }
+
+    /// Stores an operand in a local, so it is not recomputed when used multiple times.
+    fn materialize(&mut self, operand: Operand<'source>, range: Range<u32>) -> Operand<'source> {
+        match operand {
+            Operand::Local(_) | Operand::Constant(_) => operand,
+            _ => self.assign(Rvalue::Use(operand), range),
+        }
+    }
+
+    fn temporary(&mut self) -> Local {
+        let function = &mut self.builder_mut().function;
+        function.locals.push(LocalInfo {
+            binding: None,
+            name: None,
+        });
+        Local(function.locals.len() as u32 - 1)
+    }
+
+    /// Assigns a value to a new temporary and returns it.
+    fn assign(&mut self, value: Rvalue<'source>, range: Range<u32>) -> Operand<'source> {
+        let target = self.temporary();
+        self.assign_to(target, value, range);
+        Operand::Local(target)
+    }
+
+    fn assign_to(&mut self, target: Local, value: Rvalue<'source>, range: Range<u32>) {
+        let current = self.current();
+        self.builder_mut().function.blocks[current.index()]
+            .statements
+            .push(Statement {
+                target,
+                value,
+                range,
+            });
+    }
+
+    /// Terminates the current block with a failure and continues in a new, unreachable
+    /// block. Returns a placeholder operand.
+    fn fail(&mut self, failure: Failure, range: Range<u32>) -> Operand<'source> {
+        self.terminate(Terminator::Fail { failure, range });
+        let unreachable = self.new_block();
+        self.switch_to(unreachable);
+        Operand::Local(self.temporary())
+    }
+
+    fn terminate(&mut self, terminator: Terminator<'source>) {
+        let current = self.current();
+        self.builder_mut().function.blocks[current.index()].terminator = terminator;
+    }
+
+    /// Creates a new block. Until it is terminated, it fails with [`Failure::Error`].
+    fn new_block(&mut self) -> BlockId {
+        let function = &mut self.builder_mut().function;
+        function.blocks.push(Block {
+            statements: Vec::new(),
+            terminator: Terminator::Fail {
+                failure: Failure::Error,
+                range: function.range.clone(),
+            },
+        });
+        BlockId(function.blocks.len() as u32 - 1)
+    }
+
+    fn current(&self) -> BlockId {
+        self.builder().current
+    }
+
+    fn switch_to(&mut self, block: BlockId) {
+        self.builder_mut().current = block;
+    }
+
+    /// Finishes the innermost function, removing the blocks that are not reachable
+    /// from its first block, like those after a [`Lowerer::fail`].
+    fn finish_function(&mut self) -> FunctionId {
+        let mut function = self.builders.pop().unwrap().function;
+        let mut reachable = vec![false; function.blocks.len()];
+        let mut pending = vec![BlockId(0)];
+
+        while let Some(id) = pending.pop() {
+            if !reachable[id.index()] {
+                reachable[id.index()] = true;
+                pending.extend(function.block(id).terminator.successors());
+            }
+        }
+
+        // The new id of each block that is kept.
+        let ids = reachable
+            .iter()
+            .scan(0, |next, &reachable| {
+                let id = BlockId(*next);
+                *next += reachable as u32;
+                Some(id)
+            })
+            .collect::<Vec<_>>();
+        let mut index = 0;
+
+        function.blocks.retain(|_| {
+            index += 1;
+            reachable[index - 1]
+        });
+
+        for block in &mut function.blocks {
+            match &mut block.terminator {
+                Terminator::Goto(target) => *target = ids[target.index()],
+                Terminator::Branch {
+                    then, otherwise, ..
+                } => {
+                    *then = ids[then.index()];
+                    *otherwise = ids[otherwise.index()];
+                }
+                Terminator::Return(_) | Terminator::Fail { .. } => {}
+            }
+        }
+
+        self.program.functions.push(function);
+        FunctionId(self.program.functions.len() as u32 - 1)
+    }
+
+    fn builder(&self) -> &Builder<'source> {
+        self.builders.last().unwrap()
+    }
+
+    fn builder_mut(&mut self) -> &mut Builder<'source> {
+        self.builders.last_mut().unwrap()
+    }
+}
+
+fn constant<'source>(literal: &Literal<'source>) -> Value<'source> {
+    match literal {
+        Literal::Number(number) => Value::Number(number.clone()),
+        Literal::String(string) => Value::String(string.clone()),
+        Literal::Character(character) => Value::Character(*character),
+    }
+}
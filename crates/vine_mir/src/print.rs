@@ -0,0 +1,220 @@
+use core::fmt::{self, Display, Formatter};
+
+use vine_parse::lower::Literal;
+
+use crate::tree::*;
+
+impl Display for Program<'_> {
+    /// Writes the atoms, the globals and then every function with its blocks.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, atom) in self.atoms.iter().enumerate() {
+            writeln!(f, "atom {} {}", AtomId(index as u32), atom.name)?;
+        }
+
+        for (index, global) in self.globals.iter().enumerate() {
+            write!(f, "global {} {} =", GlobalId(index as u32), global.name)?;
+            write_list(f, " ", &global.initializers, " |")?;
+            writeln!(f)?;
+        }
+
+        for expression in &self.expressions {
+            writeln!(f, "expression {expression}")?;
+        }
+
+        for (index, function) in self.functions.iter().enumerate() {
+            writeln!(f)?;
+            write!(f, "function {}", FunctionId(index as u32))?;
+
+            if let Some(name) = function.name {
+                write!(f, " {name}")?;
+            }
+
+            f.write_str("(")?;
+
+            if let Some(parameter) = function.parameter {
+                write!(f, "{parameter}")?;
+            }
+
+            f.write_str(")")?;
+
+            if !function.captures.is_empty() {
+                f.write_str(" captures (")?;
+                write_list(f, "", &function.captures, ",")?;
+                f.write_str(")")?;
+            }
+
+            writeln!(f, " {{")?;
+
+            for (index, local) in function.locals.iter().enumerate() {
+                if let Some(name) = local.name {
+                    writeln!(f, "    let {} {name}", Local(index as u32))?;
+                }
+            }
+
+            for (index, block) in function.blocks.iter().enumerate() {
+                writeln!(f, "    {}:", BlockId(index as u32))?;
+
+                for statement in &block.statements {
+                    writeln!(f, "        {} = {}", statement.target, statement.value)?;
+                }
+
+                writeln!(f, "        {}", block.terminator)?;
+            }
+
+            writeln!(f, "}}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Terminator<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Goto(target) => write!(f, "goto {target}"),
+            Self::Branch {
+                condition,
+                then,
+                otherwise,
+            } => write!(f, "branch {condition} {then} {otherwise}"),
+            Self::Return(operand) => write!(f, "return {operand}"),
+            Self::Fail { failure, .. } => f.write_str(match failure {
+                Failure::NoMatch => "fail no match",
+                Failure::OutsideDomain => "fail outside domain",
+                Failure::Error => "fail error",
+            }),
+        }
+    }
+}
+
+impl Display for Rvalue<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Use(operand) => write!(f, "{operand}"),
+            Self::Unary {
+                operation,
+                operand,
+                overload,
+            } => {
+                write!(f, "{operation:?}({operand})")?;
+                write_overload(f, overload)
+            }
+            Self::Binary {
+                operation,
+                left,
+                right,
+                overload,
+            } => {
+                write!(f, "{operation:?}({left}, {right})")?;
+                write_overload(f, overload)
+            }
+            Self::Tuple(operands) => {
+                f.write_str("(")?;
+                write_list(f, "", operands, ",")?;
+
+                if operands.len() == 1 {
+                    f.write_str(",")?;
+                }
+
+                f.write_str(")")
+            }
+            Self::Record(fields) => {
+                f.write_str("(")?;
+
+                for (index, (name, operand)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    write!(f, "{name} = {operand}")?;
+                }
+
+                f.write_str(")")
+            }
+            Self::Set(operands) => {
+                f.write_str("set {")?;
+                write_list(f, "", operands, ",")?;
+                f.write_str("}")
+            }
+            Self::List(operands) => {
+                f.write_str("[")?;
+                write_list(f, "", operands, ",")?;
+                f.write_str("]")
+            }
+            Self::Field { operand, name } => write!(f, "{operand}.{name}"),
+            Self::Component { operand, index } => write!(f, "{operand}.{index}"),
+            Self::Closure { function, captures } => {
+                write!(f, "closure {function}")?;
+
+                if !captures.is_empty() {
+                    f.write_str(" (")?;
+                    write_list(f, "", captures, ",")?;
+                    f.write_str(")")?;
+                }
+
+                Ok(())
+            }
+            Self::Call { function, argument } => write!(f, "call {function} {argument}"),
+            Self::Test { operand, test } => match test {
+                Test::Equal(value) => write!(f, "test {operand} == {value}"),
+                Test::Tuple(length) => write!(f, "test {operand} is tuple {length}"),
+                Test::Record(names) => {
+                    write!(f, "test {operand} is record (")?;
+                    write_list(f, "", names, ",")?;
+                    f.write_str(")")
+                }
+            },
+            Self::In { operand, domain } => write!(f, "{operand} in {domain}"),
+        }
+    }
+}
+
+impl Display for Operand<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local(local) => write!(f, "{local}"),
+            Self::Constant(value) => write!(f, "{value}"),
+            Self::Global(global) => write!(f, "{global}"),
+            Self::Builtin(builtin) => f.write_str(builtin.name()),
+        }
+    }
+}
+
+impl Display for Value<'_> {
+    /// Writes numbers, strings and characters in Vine syntax and atoms by their id.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{}", Literal::Number(number.clone())),
+            Self::String(string) => write!(f, "{}", Literal::String(string.clone())),
+            Self::Character(character) => write!(f, "{}", Literal::Character(*character)),
+            Self::Atom(atom) => write!(f, "{atom}"),
+        }
+    }
+}
+
+/// Writes the items separated by `separator` and a space, after `prefix` if there are any.
+fn write_list(
+    f: &mut Formatter<'_>,
+    prefix: &str,
+    items: &[impl Display],
+    separator: &str,
+) -> fmt::Result {
+    for (index, item) in items.iter().enumerate() {
+        f.write_str(if index == 0 { prefix } else { separator })?;
+
+        if index > 0 {
+            f.write_str(" ")?;
+        }
+
+        write!(f, "{item}")?;
+    }
+
+    Ok(())
+}
+
+fn write_overload(f: &mut Formatter<'_>, overload: &Option<GlobalId>) -> fmt::Result {
+    match overload {
+        Some(global) => write!(f, " @merge {global}"),
+        None => Ok(()),
+    }
+}
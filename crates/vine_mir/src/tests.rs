@@ -0,0 +1,198 @@
+#![allow(non_snake_case)]
+
+mod Program {
+    use vine_parse::{lower::lower_module, parse_module};
+    use vine_resolve::resolve;
+
+    use vine_parse::ast::UnaryOperation;
+
+    use crate::*;
+
+    fn lowered(source: &str) -> Program<'_> {
+        let module = parse_module(source).unwrap();
+        let (resolution, diagnostics) = resolve(&module);
+        assert_eq!(diagnostics, []);
+
+        let (items, diagnostics) = lower_module(&module);
+        assert_eq!(diagnostics, []);
+
+        lower(&items, &resolution)
+    }
+
+    #[test]
+    fn lower_finite() {
+        let program = lowered(include_str!("../../../language-design/finite.vn"));
+
+        assert_eq!(program.validate(), []);
+        assert_eq!(
+            program
+                .atoms
+                .iter()
+                .map(|atom| atom.name)
+                .collect::<Vec<_>>(),
+            ["True", "False", "Zero", "One"]
+        );
+        assert_eq!(
+            program
+                .globals
+                .iter()
+                .map(|global| global.name)
+                .collect::<Vec<_>>(),
+            [
+                "ZeroOrOne",
+                "add",
+                "multiply",
+                "additive_inverse",
+                "multiplicative_inverse",
+                "x"
+            ]
+        );
+
+        // `-One` calls `additive_inverse` for values that are not numbers.
+        let x = program.function(program.global(GlobalId(5)).initializers[0]);
+        assert_eq!(
+            x.blocks[0].statements[0].value,
+            Rvalue::Unary {
+                operation: UnaryOperation::Negate,
+                operand: Operand::Constant(Value::Atom(AtomId(3))),
+                overload: Some(GlobalId(3)),
+            }
+        );
+    }
+
+    #[test]
+    fn lower_closures() {
+        let program = lowered("f = function x => function y => function z => x + z");
+        let captures = program
+            .functions
+            .iter()
+            .map(|function| function.captures.len())
+            .collect::<Vec<_>>();
+
+        // The innermost function captures `x` from the middle one, which captures it from the outer one.
+        assert_eq!(captures, [1, 1, 0, 0]);
+        assert_eq!(program.validate(), []);
+        assert_eq!(
+            program.functions[1].blocks[0].statements[1].value,
+            Rvalue::Closure {
+                function: FunctionId(0),
+                captures: vec![Operand::Local(program.functions[1].captures[0])],
+            }
+        );
+    }
+
+    #[test]
+    fn lower_alternatives() {
+        let program = lowered("f = function (0 or 1) => 2");
+        let f = &program.functions[0];
+
+        // The alternatives test the parameter itself instead of a binding of it.
+        assert_eq!(program.validate(), []);
+        assert_eq!(
+            f.blocks[0].statements[0].value,
+            Rvalue::Test {
+                operand: Operand::Local(f.parameter.unwrap()),
+                test: Test::Equal(Value::Number(0.into())),
+            }
+        );
+    }
+
+    #[test]
+    fn lower_merged() {
+        let program = lowered("@merge\nf = function 1 => 2\n@merge\nf = function 2 => 1\ng = f 1");
+
+        assert_eq!(program.globals.len(), 2);
+        assert_eq!(program.global(GlobalId(0)).initializers.len(), 2);
+        assert_eq!(program.validate(), []);
+    }
+
+    #[test]
+    fn validate() {
+        let mut program = lowered("f = function x => if x then 1 else 2");
+        assert_eq!(program.validate(), []);
+
+        let mut invalid = program.clone();
+        invalid.functions[0].blocks[0].statements.clear();
+        assert_eq!(
+            invalid
+                .validate()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["f0: _1 is used in bb0 before it is assigned"]
+        );
+
+        let mut unreachable = program.clone();
+        let Terminator::Branch {
+            then, otherwise, ..
+        } = &mut unreachable.functions[0].blocks[0].terminator
+        else {
+            panic!("expected a branch");
+        };
+        *then = *otherwise;
+        assert_eq!(
+            unreachable
+                .validate()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["f0: bb1 is unreachable"]
+        );
+
+        let Terminator::Branch { then, .. } = &mut program.functions[0].blocks[0].terminator else {
+            panic!("expected a branch");
+        };
+        *then = BlockId(100);
+        program.functions[1].blocks[0].statements[0].value = Rvalue::Closure {
+            function: FunctionId(0),
+            captures: vec![Operand::Constant(Value::Atom(AtomId(0)))],
+        };
+
+        assert_eq!(
+            program
+                .validate()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "f0: bb100 in bb0 does not exist",
+                "f1: f0 captures 0 values, but bb0 passes 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn fmt() {
+        assert_eq!(
+            lowered("abstract One\nf = function x => match x\n    case One => 1\n    case _ => -x")
+                .to_string(),
+            "atom a0 True\n\
+             atom a1 False\n\
+             atom a2 One\n\
+             global g0 f = f1\n\
+             \n\
+             function f0 f(_0) {\n\
+            \x20   let _1 x\n\
+            \x20   bb0:\n\
+            \x20       _1 = _0\n\
+            \x20       _3 = test _1 == a2\n\
+            \x20       branch _3 bb3 bb2\n\
+            \x20   bb1:\n\
+            \x20       return _2\n\
+            \x20   bb2:\n\
+            \x20       _4 = Negate(_1)\n\
+            \x20       _2 = _4\n\
+            \x20       goto bb1\n\
+            \x20   bb3:\n\
+            \x20       _2 = 1\n\
+            \x20       goto bb1\n\
+             }\n\
+             \n\
+             function f1 f() {\n\
+            \x20   bb0:\n\
+            \x20       _0 = closure f0\n\
+            \x20       return _0\n\
+             }\n"
+        );
+    }
+}
@@ -1,9 +1,357 @@
-use std::sync::Arc;
+use std::{borrow::Cow, ops::Range};
 
+use vine_num::Rational;
+use vine_parse::ast::{BinaryOperation, UnaryOperation};
+use vine_resolve::BindingId;
+
+/// A lowered module.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Program<'source> {
+    /// The abstract values, starting with the builtins `True` and `False`.
+    pub atoms: Vec<Atom<'source>>,
+    pub globals: Vec<Global<'source>>,
+    pub functions: Vec<Function<'source>>,
+
+    /// The functions evaluating the module-level expressions that are not definitions, in order.
+    pub expressions: Vec<FunctionId>,
+}
+
+macro_rules! id {
+    ($(#[$attribute:meta])* $name:ident, $prefix:literal) => {
+        $(#[$attribute])*
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(pub u32);
+
+        impl $name {
+            pub const fn index(self) -> usize {
+                self.0 as usize
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, concat!($prefix, "{}"), self.0)
+            }
+        }
+    };
+}
+
+id!(
+    /// Identifies an [`Atom`] in [`Program::atoms`].
+    AtomId,
+    "a"
+);
+id!(
+    /// Identifies a [`Global`] in [`Program::globals`].
+    GlobalId,
+    "g"
+);
+id!(
+    /// Identifies a [`Function`] in [`Program::functions`].
+    FunctionId,
+    "f"
+);
+id!(
+    /// Identifies a [`LocalInfo`] in [`Function::locals`].
+    Local,
+    "_"
+);
+id!(
+    /// Identifies a [`Block`] in [`Function::blocks`].
+    BlockId,
+    "bb"
+);
+
+/// A value declared with `abstract`, or `True` or `False`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Atom<'source> {
+    pub name: &'source str,
+    pub binding: BindingId,
+}
+
+/// A module-level definition. It is evaluated when it is first used.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Global<'source> {
+    pub name: &'source str,
+    pub binding: BindingId,
+
+    /// The functions without a parameter that compute the value. Definitions with
+    /// a `@merge` attribute have multiple; the value is then a function calling the
+    /// first of the resulting functions whose parameter accepts the argument.
+    pub initializers: Vec<FunctionId>,
+}
+
+/// A function as a control flow graph. Execution starts in the first block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Function<'source> {
+    /// The name of the definition the function is part of, for printing.
+    pub name: Option<&'source str>,
+
+    /// The local holding the argument. [`None`] for initializers
+    /// of globals and module-level expressions.
+    pub parameter: Option<Local>,
+
+    /// The locals holding the captured values, in the order of [`Rvalue::Closure::captures`].
+    pub captures: Vec<Local>,
+    pub locals: Vec<LocalInfo<'source>>,
+    pub blocks: Vec<Block<'source>>,
+    pub range: Range<u32>,
+}
+
+/// A variable of a function. Every local belongs to exactly one function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalInfo<'source> {
+    /// The binding the local holds the value of. [`None`] for temporaries.
+    pub binding: Option<BindingId>,
+    pub name: Option<&'source str>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Block<'source> {
+    pub statements: Vec<Statement<'source>>,
+    pub terminator: Terminator<'source>,
+}
+
+/// An assignment `target = value`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statement<'source> {
+    pub target: Local,
+    pub value: Rvalue<'source>,
+
+    /// The range of the expression the statement was lowered from.
+    pub range: Range<u32>,
+}
+
+/// How a block ends.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Terminator<'source> {
+    Goto(BlockId),
+
+    /// Continues in `then` if the condition is `True` and in `otherwise` if it is `False`.
+    Branch {
+        condition: Operand<'source>,
+        then: BlockId,
+        otherwise: BlockId,
+    },
+
+    Return(Operand<'source>),
+
+    /// Stops the program.
+    Fail {
+        failure: Failure,
+        range: Range<u32>,
+    },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// No case of a match matched the value, or a definition's pattern did not match.
+    NoMatch,
+
+    /// The argument of a function is not matched by its parameter pattern or not in its domain.
+    /// Calls of merged functions try the next function instead.
+    OutsideDomain,
+
+    /// The source had an error that was reported as a diagnostic.
+    Error,
+}
+
+/// The value of an assignment.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Rvalue<'source> {
+    Use(Operand<'source>),
+
+    /// A unary operation. If `overload` is given, it is called for operands that
+    /// are not numbers (booleans for `!`).
+    Unary {
+        operation: UnaryOperation,
+        operand: Operand<'source>,
+        overload: Option<GlobalId>,
+    },
+
+    /// A binary operation. If `overload` is given, it is called with the record
+    /// `(left = ..., right = ...)` for operands that are not numbers.
+    Binary {
+        operation: BinaryOperation,
+        left: Operand<'source>,
+        right: Operand<'source>,
+        overload: Option<GlobalId>,
+    },
+
+    Tuple(Vec<Operand<'source>>),
+    Record(Vec<(&'source str, Operand<'source>)>),
+
+    /// `set {...}`
+    Set(Vec<Operand<'source>>),
+
+    /// `[...]`
+    List(Vec<Operand<'source>>),
+
+    /// The field of a record.
+    Field {
+        operand: Operand<'source>,
+        name: &'source str,
+    },
+
+    /// The component of a tuple.
+    Component {
+        operand: Operand<'source>,
+        index: usize,
+    },
+
+    /// Creates a function value from a function and the values it captures.
+    Closure {
+        function: FunctionId,
+        captures: Vec<Operand<'source>>,
+    },
+
+    Call {
+        function: Operand<'source>,
+        argument: Operand<'source>,
+    },
+
+    /// `True` if the operand passes the test, `False` otherwise.
+    Test {
+        operand: Operand<'source>,
+        test: Test<'source>,
+    },
+
+    /// `True` if the operand is in the domain, `False` otherwise. A domain is a set,
+    /// a builtin type, an atom, a tuple or record of domains, or a function value
+    /// returning whether its argument is in the domain.
+    In {
+        operand: Operand<'source>,
+        domain: Operand<'source>,
+    },
+}
+
+/// A test of the structure of a value, used for patterns.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Test<'source> {
+    /// Whether the value is equal to the constant.
+    Equal(Value<'source>),
+
+    /// Whether the value is a tuple with this many components.
+    Tuple(usize),
+
+    /// Whether the value is a record with at least these fields.
+    Record(Vec<&'source str>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operand<'source> {
+    Local(Local),
+    Constant(Value<'source>),
+    Global(GlobalId),
+    Builtin(Builtin),
+}
+
+/// A constant.
+#[derive(Clone, Debug, PartialEq)]
 pub enum Value<'source> {
-    String(&'source str),
-    Array(Arc<[u8]>),
-    Function {},
+    Number(Rational),
+    String(Cow<'source, str>),
+    Character(char),
+    Atom(AtomId),
 }
 
-pub enum Expression {}
+/// A builtin type, used as a domain.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Builtin {
+    Number,
+    String,
+    Character,
+    Boolean,
+}
+
+impl Builtin {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "Number" => Self::Number,
+            "String" => Self::String,
+            "Character" => Self::Character,
+            "Boolean" => Self::Boolean,
+            _ => return None,
+        })
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Number => "Number",
+            Self::String => "String",
+            Self::Character => "Character",
+            Self::Boolean => "Boolean",
+        }
+    }
+}
+
+impl<'source> Program<'source> {
+    pub const TRUE: AtomId = AtomId(0);
+    pub const FALSE: AtomId = AtomId(1);
+
+    pub fn atom(&self, id: AtomId) -> &Atom<'source> {
+        &self.atoms[id.index()]
+    }
+
+    pub fn global(&self, id: GlobalId) -> &Global<'source> {
+        &self.globals[id.index()]
+    }
+
+    pub fn function(&self, id: FunctionId) -> &Function<'source> {
+        &self.functions[id.index()]
+    }
+
+    /// Returns the global defined under `name`.
+    pub fn global_named(&self, name: &str) -> Option<GlobalId> {
+        self.globals
+            .iter()
+            .position(|global| global.name == name)
+            .map(|index| GlobalId(index as u32))
+    }
+}
+
+impl<'source> Function<'source> {
+    pub fn block(&self, id: BlockId) -> &Block<'source> {
+        &self.blocks[id.index()]
+    }
+
+    pub fn local(&self, local: Local) -> &LocalInfo<'source> {
+        &self.locals[local.index()]
+    }
+}
+
+impl<'source> Rvalue<'source> {
+    /// Returns the operands the value is computed from.
+    pub fn operands(&self) -> Vec<&Operand<'source>> {
+        match self {
+            Self::Use(operand)
+            | Self::Unary { operand, .. }
+            | Self::Field { operand, .. }
+            | Self::Component { operand, .. }
+            | Self::Test { operand, .. } => vec![operand],
+            Self::Binary { left, right, .. } => vec![left, right],
+            Self::Tuple(operands)
+            | Self::Set(operands)
+            | Self::List(operands)
+            | Self::Closure {
+                captures: operands, ..
+            } => operands.iter().collect(),
+            Self::Record(fields) => fields.iter().map(|(_, operand)| operand).collect(),
+            Self::Call { function, argument } => vec![function, argument],
+            Self::In { operand, domain } => vec![operand, domain],
+        }
+    }
+}
+
+impl<'source> Terminator<'source> {
+    /// Returns the blocks execution may continue in.
+    pub fn successors(&self) -> Vec<BlockId> {
+        match self {
+            Self::Goto(target) => vec![*target],
+            Self::Branch {
+                then, otherwise, ..
+            } => vec![*then, *otherwise],
+            Self::Return(_) | Self::Fail { .. } => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::tree::*;
+
+/// An inconsistency found by [`Program::validate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The function the inconsistency is in, if any.
+    pub function: Option<FunctionId>,
+    pub message: String,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.function {
+            Some(function) => write!(f, "{function}: {}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl Program<'_> {
+    /// Checks that all ids refer to existing items, that closures capture as many
+    /// values as their function expects, that every block is reachable and that
+    /// every local is assigned before it is used. Programs produced by
+    /// [`lower`](crate::lower) are always valid.
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut validator = Validator {
+            program: self,
+            function: None,
+            errors: Vec::new(),
+        };
+
+        if self.atoms.len() < 2
+            || self.atom(Self::TRUE).name != "True"
+            || self.atom(Self::FALSE).name != "False"
+        {
+            validator.error("the first atoms are not `True` and `False`".into());
+        }
+
+        for (index, global) in self.globals.iter().enumerate() {
+            if global.initializers.is_empty() {
+                validator.error(format!("{} has no initializers", GlobalId(index as u32)));
+            }
+
+            for initializer in &global.initializers {
+                validator.thunk(*initializer);
+            }
+        }
+
+        for expression in &self.expressions {
+            validator.thunk(*expression);
+        }
+
+        for (index, function) in self.functions.iter().enumerate() {
+            validator.function = Some(FunctionId(index as u32));
+            validator.validate_function(function);
+        }
+
+        validator.errors
+    }
+}
+
+struct Validator<'a, 'source> {
+    program: &'a Program<'source>,
+    function: Option<FunctionId>,
+    errors: Vec<ValidationError>,
+}
+
+impl Validator<'_, '_> {
+    /// Checks a function that is run without argument.
+    fn thunk(&mut self, function: FunctionId) {
+        match self.program.functions.get(function.index()) {
+            None => self.error(format!("{function} does not exist")),
+            Some(thunk) if thunk.parameter.is_some() || !thunk.captures.is_empty() => {
+                self.error(format!(
+                    "{function} is run without argument but has a parameter or captures"
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+
+    fn validate_function(&mut self, function: &Function) {
+        if function.blocks.is_empty() {
+            self.error("the function has no blocks".into());
+            return;
+        }
+
+        let errors = self.errors.len();
+
+        let mut entry = BTreeSet::new();
+
+        for local in function.parameter.iter().chain(&function.captures) {
+            if local.index() >= function.locals.len() {
+                self.error(format!("{local} does not exist"));
+            } else if !entry.insert(*local) {
+                self.error(format!("{local} is bound more than once"));
+            }
+        }
+
+        for (index, block) in function.blocks.iter().enumerate() {
+            let id = BlockId(index as u32);
+
+            for statement in &block.statements {
+                if statement.target.index() >= function.locals.len() {
+                    self.error(format!("{} in {id} does not exist", statement.target));
+                }
+
+                self.rvalue(&statement.value, id);
+            }
+
+            if let Terminator::Branch { condition, .. } | Terminator::Return(condition) =
+                &block.terminator
+            {
+                self.operand(condition, id);
+            }
+
+            for successor in block.terminator.successors() {
+                if successor.index() >= function.blocks.len() {
+                    self.error(format!("{successor} in {id} does not exist"));
+                }
+            }
+        }
+
+        // The ids must all exist to follow the control flow.
+        if self.errors.len() == errors {
+            self.assignments(function, entry);
+        }
+    }
+
+    fn rvalue(&mut self, rvalue: &Rvalue, block: BlockId) {
+        for operand in rvalue.operands() {
+            self.operand(operand, block);
+        }
+
+        match rvalue {
+            Rvalue::Unary {
+                overload: Some(global),
+                ..
+            }
+            | Rvalue::Binary {
+                overload: Some(global),
+                ..
+            } if global.index() >= self.program.globals.len() => {
+                self.error(format!("{global} in {block} does not exist"));
+            }
+            Rvalue::Test {
+                test: Test::Equal(Value::Atom(atom)),
+                ..
+            } if atom.index() >= self.program.atoms.len() => {
+                self.error(format!("{atom} in {block} does not exist"));
+            }
+            Rvalue::Closure { function, captures } => {
+                match self.program.functions.get(function.index()) {
+                    None => self.error(format!("{function} in {block} does not exist")),
+                    Some(closure) if closure.captures.len() != captures.len() => {
+                        self.error(format!(
+                            "{function} captures {} values, but {block} passes {}",
+                            closure.captures.len(),
+                            captures.len()
+                        ))
+                    }
+                    Some(closure) if closure.parameter.is_none() => {
+                        self.error(format!("{function} in {block} has no parameter"));
+                    }
+                    Some(_) => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn operand(&mut self, operand: &Operand, block: BlockId) {
+        let exists = match operand {
+            Operand::Local(local) => local.index() < self.current().locals.len(),
+            Operand::Constant(Value::Atom(atom)) => atom.index() < self.program.atoms.len(),
+            Operand::Global(global) => global.index() < self.program.globals.len(),
+            Operand::Constant(_) | Operand::Builtin(_) => true,
+        };
+
+        if !exists {
+            self.error(format!("{operand} in {block} does not exist"));
+        }
+    }
+
+    /// Checks that every block is reachable and that every local is assigned
+    /// before it is used.
+    fn assignments(&mut self, function: &Function, entry: BTreeSet<Local>) {
+        // The locals that are assigned at the start of each block on every path to it.
+        let mut assigned: Vec<Option<BTreeSet<Local>>> = vec![None; function.blocks.len()];
+        assigned[0] = Some(entry);
+        let mut pending = vec![BlockId(0)];
+
+        while let Some(id) = pending.pop() {
+            let mut state = assigned[id.index()].clone().unwrap();
+            let block = function.block(id);
+
+            for statement in &block.statements {
+                state.insert(statement.target);
+            }
+
+            for successor in block.terminator.successors() {
+                let next = match &assigned[successor.index()] {
+                    Some(previous) => previous.intersection(&state).copied().collect(),
+                    None => state.clone(),
+                };
+
+                if assigned[successor.index()].as_ref() != Some(&next) {
+                    assigned[successor.index()] = Some(next);
+                    pending.push(successor);
+                }
+            }
+        }
+
+        for (index, state) in assigned.into_iter().enumerate() {
+            let id = BlockId(index as u32);
+
+            let Some(mut state) = state else {
+                self.error(format!("{id} is unreachable"));
+                continue;
+            };
+
+            let block = function.block(id);
+            let mut unassigned = Vec::new();
+
+            for statement in &block.statements {
+                unassigned.extend(
+                    statement
+                        .value
+                        .operands()
+                        .into_iter()
+                        .filter_map(|operand| match operand {
+                            Operand::Local(local) if !state.contains(local) => Some(*local),
+                            _ => None,
+                        }),
+                );
+                state.insert(statement.target);
+            }
+
+            if let Terminator::Branch {
+                condition: Operand::Local(local),
+                ..
+            }
+            | Terminator::Return(Operand::Local(local)) = &block.terminator
+                && !state.contains(local)
+            {
+                unassigned.push(*local);
+            }
+
+            for local in unassigned {
+                self.error(format!("{local} is used in {id} before it is assigned"));
+            }
+        }
+    }
+
+    fn current(&self) -> &Function<'_> {
+        self.program.function(self.function.unwrap())
+    }
+
+    fn error(&mut self, message: String) {
+        self.errors.push(ValidationError {
+            function: self.function,
+            message,
+        });
+    }
+}
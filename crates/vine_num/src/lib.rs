@@ -0,0 +1,11 @@
+//! Arbitrary-precision numbers used for Vine's exact numeric literals.
+
+#![no_std]
+
+extern crate alloc;
+
+mod natural;
+mod rational;
+
+pub use natural::*;
+pub use rational::*;
@@ -0,0 +1,354 @@
+#[cfg(test)]
+mod tests;
+
+use alloc::{string::String, vec, vec::Vec};
+use core::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::{Add, Div, Mul, Rem, Sub},
+};
+
+/// An arbitrary-precision natural number (including zero).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Natural {
+    /// The limbs, least significant first. There are never
+    /// any trailing zero limbs, so zero has no limbs at all.
+    limbs: Vec<u32>,
+}
+
+impl Natural {
+    pub const ZERO: Self = Self { limbs: Vec::new() };
+
+    pub fn one() -> Self {
+        Self::from(1_u32)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Returns the number of significant bits.
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(last) => self.limbs.len() as u64 * 32 - last.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    /// Returns the value if it fits into a [`u64`].
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [low] => Some(low as u64),
+            [low, high] => Some((high as u64) << 32 | low as u64),
+            _ => None,
+        }
+    }
+
+    /// Computes `self * factor + summand` in place.
+    pub fn mul_add_small(&mut self, factor: u32, summand: u32) {
+        let mut carry = summand as u64;
+
+        for limb in &mut self.limbs {
+            let product = *limb as u64 * factor as u64 + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+
+        if carry != 0 {
+            self.limbs.push(carry as u32);
+        }
+
+        self.normalize();
+    }
+
+    /// Divides `self` by `divisor` in place and returns the remainder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        assert_ne!(divisor, 0, "division by zero");
+
+        let mut remainder = 0_u64;
+
+        for limb in self.limbs.iter_mut().rev() {
+            let current = remainder << 32 | *limb as u64;
+            *limb = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        }
+
+        self.normalize();
+        remainder as u32
+    }
+
+    /// Divides `self` by `divisor`, returning the quotient and the remainder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        assert!(!divisor.is_zero(), "division by zero");
+
+        if self < divisor {
+            return (Self::ZERO, self.clone());
+        }
+
+        if let [small] = divisor.limbs[..] {
+            let mut quotient = self.clone();
+            let remainder = quotient.div_rem_small(small);
+            return (quotient, Self::from(remainder));
+        }
+
+        // Binary long division. Numbers in Vine programs are rarely
+        // large enough for this to matter.
+        let mut quotient = Self {
+            limbs: vec![0; self.limbs.len()],
+        };
+        let mut remainder = Self::ZERO;
+
+        for bit in (0..self.bits()).rev() {
+            remainder.shl_one();
+
+            if self.bit(bit) {
+                remainder.mul_add_small(1, 1);
+            }
+
+            if remainder >= *divisor {
+                remainder = &remainder - divisor;
+                quotient.limbs[(bit / 32) as usize] |= 1 << (bit % 32);
+            }
+        }
+
+        quotient.normalize();
+        (quotient, remainder)
+    }
+
+    /// Subtracts `other` from `self`, returning [`None`] if the result would be negative.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        if self < other {
+            return None;
+        }
+
+        let mut limbs = self.limbs.clone();
+        let mut borrow = 0_i64;
+
+        for (index, limb) in limbs.iter_mut().enumerate() {
+            let difference =
+                *limb as i64 - other.limbs.get(index).copied().unwrap_or(0) as i64 - borrow;
+
+            if difference < 0 {
+                *limb = (difference + (1 << 32)) as u32;
+                borrow = 1;
+            } else {
+                *limb = difference as u32;
+                borrow = 0;
+            }
+        }
+
+        let mut result = Self { limbs };
+        result.normalize();
+        Some(result)
+    }
+
+    /// Raises `self` to the power of `exponent`.
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let mut base = self.clone();
+        let mut result = Self::one();
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+
+            exponent >>= 1;
+
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+
+        result
+    }
+
+    /// Computes the greatest common divisor.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let mut a = self.clone();
+        let mut b = other.clone();
+
+        while !b.is_zero() {
+            let (_, remainder) = a.div_rem(&b);
+            a = b;
+            b = remainder;
+        }
+
+        a
+    }
+
+    /// Formats the number in the given radix, which must be between 2 and 36.
+    pub fn to_string_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix out of range");
+
+        if self.is_zero() {
+            return "0".into();
+        }
+
+        let mut digits = Vec::new();
+        let mut rest = self.clone();
+
+        while !rest.is_zero() {
+            let digit = rest.div_rem_small(radix);
+            digits.push(char::from_digit(digit, radix).unwrap());
+        }
+
+        digits.iter().rev().collect()
+    }
+
+    fn bit(&self, bit: u64) -> bool {
+        self.limbs
+            .get((bit / 32) as usize)
+            .is_some_and(|limb| limb >> (bit % 32) & 1 == 1)
+    }
+
+    fn shl_one(&mut self) {
+        let mut carry = 0;
+
+        for limb in &mut self.limbs {
+            let next_carry = *limb >> 31;
+            *limb = *limb << 1 | carry;
+            carry = next_carry;
+        }
+
+        if carry != 0 {
+            self.limbs.push(carry);
+        }
+    }
+
+    fn normalize(&mut self) {
+        while let Some(0) = self.limbs.last() {
+            self.limbs.pop();
+        }
+    }
+}
+
+impl From<u32> for Natural {
+    fn from(value: u32) -> Self {
+        let mut natural = Self { limbs: vec![value] };
+        natural.normalize();
+        natural
+    }
+}
+
+impl From<u64> for Natural {
+    fn from(value: u64) -> Self {
+        let mut natural = Self {
+            limbs: vec![value as u32, (value >> 32) as u32],
+        };
+        natural.normalize();
+        natural
+    }
+}
+
+impl Ord for Natural {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for Natural {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &Natural {
+    type Output = Natural;
+
+    fn add(self, other: Self) -> Natural {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let mut limbs = Vec::with_capacity(long.limbs.len() + 1);
+        let mut carry = 0_u64;
+
+        for (index, limb) in long.limbs.iter().enumerate() {
+            let sum = *limb as u64 + short.limbs.get(index).copied().unwrap_or(0) as u64 + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+
+        if carry != 0 {
+            limbs.push(carry as u32);
+        }
+
+        Natural { limbs }
+    }
+}
+
+impl Sub for &Natural {
+    type Output = Natural;
+
+    /// # Panics
+    ///
+    /// Panics if `other` is greater than `self`.
+    fn sub(self, other: Self) -> Natural {
+        self.checked_sub(other)
+            .expect("natural number subtraction underflowed")
+    }
+}
+
+impl Mul for &Natural {
+    type Output = Natural;
+
+    fn mul(self, other: Self) -> Natural {
+        if self.is_zero() || other.is_zero() {
+            return Natural::ZERO;
+        }
+
+        let mut limbs = vec![0_u32; self.limbs.len() + other.limbs.len()];
+
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0_u64;
+
+            for (j, b) in other.limbs.iter().enumerate() {
+                let product = *a as u64 * *b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        let mut result = Natural { limbs };
+        result.normalize();
+        result
+    }
+}
+
+impl Div for &Natural {
+    type Output = Natural;
+
+    fn div(self, other: Self) -> Natural {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem for &Natural {
+    type Output = Natural;
+
+    fn rem(self, other: Self) -> Natural {
+        self.div_rem(other).1
+    }
+}
+
+impl Display for Natural {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "", &self.to_string_radix(10))
+    }
+}
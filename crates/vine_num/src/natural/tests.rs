@@ -0,0 +1,74 @@
+#![allow(non_snake_case)]
+
+mod Natural {
+    use alloc::string::ToString;
+
+    use super::super::*;
+
+    fn parse(digits: &str) -> Natural {
+        let mut natural = Natural::ZERO;
+
+        for digit in digits.bytes() {
+            natural.mul_add_small(10, (digit - b'0') as u32);
+        }
+
+        natural
+    }
+
+    #[test]
+    fn to_string() {
+        assert_eq!(Natural::ZERO.to_string(), "0");
+        assert_eq!(Natural::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(
+            parse("340282366920938463463374607431768211456").to_string(),
+            "340282366920938463463374607431768211456"
+        );
+        assert_eq!(Natural::from(255_u32).to_string_radix(16), "ff");
+    }
+
+    #[test]
+    fn to_u64() {
+        assert_eq!(Natural::ZERO.to_u64(), Some(0));
+        assert_eq!(Natural::from(u64::MAX).to_u64(), Some(u64::MAX));
+        assert_eq!(parse("18446744073709551616").to_u64(), None);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = parse("123456789012345678901234567890");
+        let b = parse("987654321098765432109876543210");
+
+        assert_eq!(&a + &b, parse("1111111110111111111011111111100"));
+        assert_eq!(&b - &a, parse("864197532086419753208641975320"));
+        assert_eq!(a.checked_sub(&b), None);
+        assert_eq!(
+            &a * &b,
+            parse("121932631137021795226185032733622923332237463801111263526900")
+        );
+        assert_eq!(&b / &a, Natural::from(8_u32));
+        assert_eq!(&b % &a, parse("9000000000900000000090"));
+        assert_eq!(
+            Natural::from(10_u32).pow(30),
+            parse("1000000000000000000000000000000")
+        );
+    }
+
+    #[test]
+    fn gcd() {
+        assert_eq!(
+            parse("2000000000000000000000").gcd(&parse("1500000000000000000000")),
+            parse("500000000000000000000")
+        );
+        assert_eq!(
+            Natural::from(7_u32).gcd(&Natural::ZERO),
+            Natural::from(7_u32)
+        );
+    }
+
+    #[test]
+    fn cmp() {
+        assert!(parse("18446744073709551616") > Natural::from(u64::MAX));
+        assert!(Natural::ZERO < Natural::one());
+        assert_eq!(parse("00042"), Natural::from(42_u32));
+    }
+}
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod tests;
+
+use core::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use crate::Natural;
+
+/// An exact rational number.
+///
+/// The value is always kept in lowest terms with a positive
+/// denominator, so structurally equal rationals are numerically
+/// equal and vice versa.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    /// Whether the number is less than zero. Zero is never negative.
+    negative: bool,
+    numerator: Natural,
+    denominator: Natural,
+}
+
+impl Rational {
+    /// Creates the rational `numerator / denominator`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denominator` is zero.
+    pub fn new(negative: bool, numerator: Natural, denominator: Natural) -> Self {
+        assert!(!denominator.is_zero(), "denominator is zero");
+
+        let gcd = numerator.gcd(&denominator);
+
+        Self {
+            negative: negative && !numerator.is_zero(),
+            numerator: &numerator / &gcd,
+            denominator: &denominator / &gcd,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::from(Natural::ZERO)
+    }
+
+    pub const fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == Natural::one()
+    }
+
+    /// The absolute value of the numerator.
+    pub const fn numerator(&self) -> &Natural {
+        &self.numerator
+    }
+
+    pub const fn denominator(&self) -> &Natural {
+        &self.denominator
+    }
+
+    /// Divides `self` by `other`, returning [`None`] if `other` is zero.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+
+        Some(Self::new(
+            self.negative != other.negative,
+            &self.numerator * &other.denominator,
+            &self.denominator * &other.numerator,
+        ))
+    }
+
+    /// Adds two rationals given as sign and (unreduced) numerator over a shared denominator.
+    fn add_signed(
+        (a_negative, a): (bool, Natural),
+        (b_negative, b): (bool, Natural),
+        denominator: Natural,
+    ) -> Self {
+        if a_negative == b_negative {
+            Self::new(a_negative, &a + &b, denominator)
+        } else if a >= b {
+            Self::new(a_negative, &a - &b, denominator)
+        } else {
+            Self::new(b_negative, &b - &a, denominator)
+        }
+    }
+}
+
+impl From<Natural> for Rational {
+    fn from(natural: Natural) -> Self {
+        Self {
+            negative: false,
+            numerator: natural,
+            denominator: Natural::one(),
+        }
+    }
+}
+
+impl From<u64> for Rational {
+    fn from(value: u64) -> Self {
+        Self::from(Natural::from(value))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let magnitude =
+            || (&self.numerator * &other.denominator).cmp(&(&other.numerator * &self.denominator));
+
+        match (self.negative, other.negative) {
+            (false, false) => magnitude(),
+            (true, true) => magnitude().reverse(),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            negative: !self.negative && !self.is_zero(),
+            numerator: self.numerator.clone(),
+            denominator: self.denominator.clone(),
+        }
+    }
+}
+
+impl Add for &Rational {
+    type Output = Rational;
+
+    fn add(self, other: Self) -> Rational {
+        Rational::add_signed(
+            (self.negative, &self.numerator * &other.denominator),
+            (other.negative, &other.numerator * &self.denominator),
+            &self.denominator * &other.denominator,
+        )
+    }
+}
+
+impl Sub for &Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Self) -> Rational {
+        self + &-other
+    }
+}
+
+impl Mul for &Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Self) -> Rational {
+        Rational::new(
+            self.negative != other.negative,
+            &self.numerator * &other.numerator,
+            &self.denominator * &other.denominator,
+        )
+    }
+}
+
+impl Display for Rational {
+    /// Formats the number as `n` for integers and `n/d` otherwise.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            f.write_str("-")?;
+        }
+
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
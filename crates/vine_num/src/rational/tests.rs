@@ -0,0 +1,49 @@
+#![allow(non_snake_case)]
+
+mod Rational {
+    use alloc::string::ToString;
+
+    use super::super::*;
+
+    fn rational(numerator: i64, denominator: u64) -> Rational {
+        Rational::new(
+            numerator < 0,
+            Natural::from(numerator.unsigned_abs()),
+            Natural::from(denominator),
+        )
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(rational(2, 4), rational(1, 2));
+        assert_eq!(rational(-0, 3), Rational::zero());
+        assert!(!rational(-0, 3).is_negative());
+        assert!(rational(10, 5).is_integer());
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(&rational(1, 10) + &rational(2, 10), rational(3, 10));
+        assert_eq!(&rational(1, 3) - &rational(1, 2), rational(-1, 6));
+        assert_eq!(&rational(-2, 3) * &rational(-3, 4), rational(1, 2));
+        assert_eq!(
+            rational(1, 2).checked_div(&rational(-1, 4)),
+            Some(rational(-2, 1))
+        );
+        assert_eq!(rational(1, 2).checked_div(&Rational::zero()), None);
+        assert_eq!(-&Rational::zero(), Rational::zero());
+    }
+
+    #[test]
+    fn cmp() {
+        assert!(rational(1, 3) < rational(1, 2));
+        assert!(rational(-1, 2) < rational(-1, 3));
+        assert!(rational(-1, 2) < Rational::zero());
+    }
+
+    #[test]
+    fn to_string() {
+        assert_eq!(rational(6, 3).to_string(), "2");
+        assert_eq!(rational(-1, 10).to_string(), "-1/10");
+    }
+}
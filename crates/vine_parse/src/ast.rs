@@ -1,8 +1,34 @@
+use std::{borrow::Cow, iter};
+
 use parser_tools::Span;
+use vine_num::Rational;
+
+/// A source file:
+///
+/// ```plain
+/// <EXPRESSION>
+/// [<LINE_BREAK> <EXPRESSION>]*
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Module<'source> {
+    /// The top-level items, usually definitions.
+    pub items: Vec<Span<Expression<'source>>>,
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expression<'source> {
-    Number(u64),
+    /// A placeholder for an expression that could not be parsed.
+    /// The syntax error has been reported as a diagnostic.
+    Error,
+
+    /// An exact number.
+    Number(Rational),
+
+    /// A string with its escape sequences decoded.
+    String(Cow<'source, str>),
+
+    /// A unicode character.
+    Character(char),
 
     /// An identifier.
     Identifier(&'source str),
@@ -64,6 +90,19 @@ pub enum Expression<'source> {
         other_cases: Vec<Span<MatchCase<'source>>>,
     },
 
+    /// A custom infix operator, declared with a fixity declaration:
+    ///
+    /// ```plain
+    /// <EXPRESSION> `<IDENTIFIER>` <EXPRESSION>
+    /// ```
+    Infix {
+        left: Box<Span<Expression<'source>>>,
+
+        /// The name of the operator, without the backticks.
+        operator: Span<&'source str>,
+        right: Box<Span<Expression<'source>>>,
+    },
+
     /// A call expression:
     ///
     /// ```plain
@@ -77,13 +116,76 @@ pub enum Expression<'source> {
     /// A function expression:
     ///
     /// ```plain
-    /// function <EXPRESSION> is|in <EXPRESSION> => <EXPRESSION>
+    /// function <EXPRESSION> [is|in <EXPRESSION>] => <EXPRESSION>
     /// ```
     Function {
+        /// See [`MatchCase::pattern`].
         parameter_pattern: Box<Span<Expression<'source>>>,
-        parameter_domain: Box<Span<Expression<'source>>>,
+
+        /// Optionally, a set to denote the domain of the parameter.
+        parameter_domain: Option<Box<Span<Expression<'source>>>>,
         body: Box<Span<Expression<'source>>>,
     },
+
+    /// A declaration of a nominal atom, a value that is only equal to itself:
+    ///
+    /// ```plain
+    /// abstract <IDENTIFIER>
+    /// ```
+    Abstract { name: Span<&'source str> },
+
+    /// An expression with an attribute:
+    ///
+    /// ```plain
+    /// <ATTRIBUTE> <EXPRESSION>
+    /// ```
+    Attributed {
+        attribute: Span<Attribute<'source>>,
+        expression: Box<Span<Expression<'source>>>,
+    },
+}
+
+impl<'source> Expression<'source> {
+    /// Returns the expression inside the attributes of `expression`.
+    pub fn unattributed(mut expression: &Span<Self>) -> &Span<Self> {
+        while let Self::Attributed {
+            expression: inner, ..
+        } = &expression.value
+        {
+            expression = inner;
+        }
+
+        expression
+    }
+
+    /// Returns the attributes of `expression`, outermost first.
+    pub fn attributes<'a>(
+        mut expression: &'a Span<Self>,
+    ) -> impl Iterator<Item = &'a Span<Attribute<'source>>> {
+        iter::from_fn(move || match &expression.value {
+            Self::Attributed {
+                attribute,
+                expression: inner,
+            } => {
+                expression = inner;
+                Some(attribute)
+            }
+            _ => None,
+        })
+    }
+}
+
+/// An attribute:
+///
+/// ```plain
+/// @<IDENTIFIER>[(<EXPRESSION>, ...)]
+/// ```
+///
+/// The arguments must directly follow the name, without whitespace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute<'source> {
+    pub name: Span<&'source str>,
+    pub arguments: Option<Span<Vec<Span<Expression<'source>>>>>,
 }
 
 #[derive(Copy, Debug, Clone, PartialEq)]
@@ -110,6 +212,17 @@ pub enum UnaryOperation {
     Not,
 }
 
+impl UnaryOperation {
+    /// The name of the `@merge` definitions that extend the operation
+    /// to values that are not numbers (or booleans, for `!`).
+    pub const fn merge_name(self) -> &'static str {
+        match self {
+            Self::Negate => "additive_inverse",
+            Self::Not => "not",
+        }
+    }
+}
+
 /// An operation that is used as an infix between
 /// two expression.
 #[derive(Copy, Debug, Clone, PartialEq)]
@@ -161,6 +274,95 @@ pub enum BinaryOperation {
 
     /// `and`
     And,
+
+    /// `%`
+    Remainder,
+
+    /// `**`
+    Power,
+
+    /// `++`
+    Concatenate,
+
+    /// `|`
+    BitwiseOr,
+
+    /// `&`
+    BitwiseAnd,
+
+    /// `^`
+    BitwiseXor,
+
+    /// `||`
+    LogicalOr,
+
+    /// `&&`
+    LogicalAnd,
+
+    /// `===`
+    Identical,
+
+    /// `!==`
+    NotIdentical,
+
+    /// `->`
+    Arrow,
+
+    /// `<-`
+    Bind,
+
+    /// `::`
+    Path,
+
+    /// `:=`
+    Assign,
+
+    /// `+=`
+    AddAssign,
+
+    /// `-=`
+    SubtractAssign,
+
+    /// `*=`
+    MultiplyAssign,
+
+    /// `/=`
+    DivideAssign,
+
+    /// `%=`
+    RemainderAssign,
+
+    /// `|=`
+    BitwiseOrAssign,
+
+    /// `||=`
+    LogicalOrAssign,
+
+    /// `&=`
+    BitwiseAndAssign,
+
+    /// `&&=`
+    LogicalAndAssign,
+
+    /// `^=`
+    BitwiseXorAssign,
+}
+
+impl BinaryOperation {
+    /// The name of the `@merge` definitions that extend an arithmetic
+    /// operation to values that are not numbers. These are called with
+    /// a record `(left = ..., right = ...)` of the operands.
+    pub const fn merge_name(self) -> Option<&'static str> {
+        Some(match self {
+            Self::Add => "add",
+            Self::Subtract => "subtract",
+            Self::Multiply => "multiply",
+            Self::Divide => "divide",
+            Self::Remainder => "remainder",
+            Self::Power => "power",
+            _ => return None,
+        })
+    }
 }
 
 /// A match case:
@@ -171,8 +373,8 @@ pub enum BinaryOperation {
 #[derive(Debug, Clone, PartialEq)]
 pub struct MatchCase<'source> {
     /// The pattern to match against. (This is really an
-    /// expression but will be checked if it is a pattern
-    /// in the next source tree.)
+    /// expression; it is checked and converted by
+    /// [`Pattern::lower`](crate::lower::Pattern::lower).)
     pub pattern: Box<Span<Expression<'source>>>,
 
     /// Optionally, a set to denote the domain of the pattern.
@@ -0,0 +1,176 @@
+use std::{iter::Peekable, sync::Arc};
+
+use parser_tools::{Span, Spanify};
+use vine_lex::{Lexer, tokens::Token};
+
+use crate::{
+    ast::{Attribute, Expression, MatchCase, Module},
+    cst::{GreenElement, GreenNode, GreenToken, NodeKind},
+};
+
+/// A child of a node in the AST.
+enum Child<'ast, 'source> {
+    Expression(&'ast Span<Expression<'source>>),
+    MatchCase(&'ast Span<MatchCase<'source>>),
+    Attribute(&'ast Span<Attribute<'source>>),
+    AttributeArguments(&'ast Span<Vec<Span<Expression<'source>>>>),
+}
+
+struct Builder<'source> {
+    source: &'source str,
+    tokens: Peekable<Spanify<Token<'source>, Lexer<'source>>>,
+}
+
+/// Builds the green tree of `source` by distributing its raw tokens over the nodes of `module`.
+///
+/// Each node takes the tokens within its range that are not taken by its children,
+/// so trivia between two nodes belongs to their closest common ancestor.
+pub(super) fn build<'source>(source: &'source str, module: &Module<'source>) -> GreenNode<'source> {
+    let mut builder = Builder {
+        source,
+        tokens: Spanify::new(Lexer::new(source)).peekable(),
+    };
+
+    builder.node(
+        NodeKind::Module,
+        u32::MAX,
+        module.items.iter().map(Child::Expression).collect(),
+    )
+}
+
+impl<'source> Builder<'source> {
+    /// Builds a node from its children and the remaining tokens that start before `end`.
+    fn node(
+        &mut self,
+        kind: NodeKind,
+        end: u32,
+        children: Vec<Child<'_, 'source>>,
+    ) -> GreenNode<'source> {
+        let mut elements = Vec::new();
+
+        for child in children {
+            let (kind, range, grandchildren) = Self::describe(child);
+
+            self.tokens_before(range.start, &mut elements);
+            elements.push(GreenElement::Node(Arc::new(self.node(
+                kind,
+                range.end,
+                grandchildren,
+            ))));
+        }
+
+        self.tokens_before(end, &mut elements);
+
+        GreenNode::new(kind, elements)
+    }
+
+    fn tokens_before(&mut self, end: u32, elements: &mut Vec<GreenElement<'source>>) {
+        while let Some(token) = self.tokens.next_if(|token| token.range.start < end) {
+            elements.push(GreenElement::Token(GreenToken {
+                text: &self.source[token.range.start as usize..token.range.end as usize],
+                token: token.value,
+            }));
+        }
+    }
+
+    /// Returns the kind, the range and the children of the node for an AST node.
+    fn describe<'ast>(
+        child: Child<'ast, 'source>,
+    ) -> (NodeKind, std::ops::Range<u32>, Vec<Child<'ast, 'source>>) {
+        let expression = match child {
+            Child::Expression(expression) => expression,
+            Child::MatchCase(Span { value, range }) => {
+                let mut children = vec![Child::Expression(&value.pattern)];
+                children.extend(value.domain.as_deref().map(Child::Expression));
+                children.push(Child::Expression(&value.maps_to));
+
+                return (NodeKind::MatchCase, range.clone(), children);
+            }
+            Child::Attribute(Span { value, range }) => {
+                return (
+                    NodeKind::Attribute,
+                    range.clone(),
+                    value
+                        .arguments
+                        .iter()
+                        .map(Child::AttributeArguments)
+                        .collect(),
+                );
+            }
+            Child::AttributeArguments(Span { value, range }) => {
+                return (
+                    NodeKind::AttributeArguments,
+                    range.clone(),
+                    value.iter().map(Child::Expression).collect(),
+                );
+            }
+        };
+
+        let (kind, children) = match &expression.value {
+            Expression::Error => (NodeKind::Error, Vec::new()),
+            Expression::Number(_) => (NodeKind::Number, Vec::new()),
+            Expression::String(_) => (NodeKind::String, Vec::new()),
+            Expression::Character(_) => (NodeKind::Character, Vec::new()),
+            Expression::Identifier(_) => (NodeKind::Identifier, Vec::new()),
+            Expression::Abstract { .. } => (NodeKind::Abstract, Vec::new()),
+            Expression::Grouped { expression, .. } => (
+                NodeKind::Grouped,
+                expression.iter().map(Child::Expression).collect(),
+            ),
+            Expression::Unary { inner, .. } => (NodeKind::Unary, vec![Child::Expression(inner)]),
+            Expression::Binary { left, right, .. } => (
+                NodeKind::Binary,
+                vec![Child::Expression(left), Child::Expression(right)],
+            ),
+            Expression::Infix { left, right, .. } => (
+                NodeKind::Infix,
+                vec![Child::Expression(left), Child::Expression(right)],
+            ),
+            Expression::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                let mut children = vec![Child::Expression(condition), Child::Expression(then)];
+                children.extend(otherwise.as_deref().map(Child::Expression));
+
+                (NodeKind::If, children)
+            }
+            Expression::Match {
+                on,
+                first_case,
+                other_cases,
+            } => (
+                NodeKind::Match,
+                [Child::Expression(on), Child::MatchCase(first_case)]
+                    .into_iter()
+                    .chain(other_cases.iter().map(Child::MatchCase))
+                    .collect(),
+            ),
+            Expression::Call { function, argument } => (
+                NodeKind::Call,
+                vec![Child::Expression(function), Child::Expression(argument)],
+            ),
+            Expression::Function {
+                parameter_pattern,
+                parameter_domain,
+                body,
+            } => {
+                let mut children = vec![Child::Expression(parameter_pattern)];
+                children.extend(parameter_domain.as_deref().map(Child::Expression));
+                children.push(Child::Expression(body));
+
+                (NodeKind::Function, children)
+            }
+            Expression::Attributed {
+                attribute,
+                expression,
+            } => (
+                NodeKind::Attributed,
+                vec![Child::Attribute(attribute), Child::Expression(expression)],
+            ),
+        };
+
+        (kind, expression.range.clone(), children)
+    }
+}
@@ -0,0 +1,183 @@
+use parser_tools::Span;
+use vine_lex::tokens::Token;
+
+use crate::{
+    BUILTIN_OPERATORS,
+    ast::{Attribute, Expression, GroupKind, MatchCase, Module, UnaryOperation},
+    cst::{NodeKind, SyntaxNode},
+};
+
+pub(super) fn module<'source>(node: SyntaxNode<'_, 'source>) -> Module<'source> {
+    Module {
+        items: node.child_nodes().map(expression).collect(),
+    }
+}
+
+/// Derives the expression of a node. Nodes that do not have the children or tokens
+/// their kind needs, which trees with syntax errors can have, become [`Expression::Error`].
+fn expression<'source>(node: SyntaxNode<'_, 'source>) -> Span<Expression<'source>> {
+    Span {
+        value: value(node).unwrap_or(Expression::Error),
+        range: node.range(),
+    }
+}
+
+fn value<'source>(node: SyntaxNode<'_, 'source>) -> Option<Expression<'source>> {
+    let mut nodes = node.child_nodes();
+    let mut next = || nodes.next().map(|node| Box::new(expression(node)));
+    let first_token = || node.significant_tokens().next();
+
+    Some(match node.kind() {
+        NodeKind::Error => Expression::Error,
+        NodeKind::Number => match first_token()?.token() {
+            Token::Number(source) => Expression::Number(source.parse().ok()?),
+            _ => return None,
+        },
+        NodeKind::String => match first_token()?.token() {
+            Token::String(source) => Expression::String(source.parse().ok()?),
+            _ => return None,
+        },
+        NodeKind::Character => match first_token()?.token() {
+            Token::Character(source) => Expression::Character(source.parse().ok()?),
+            _ => return None,
+        },
+        NodeKind::Identifier => Expression::Identifier(first_token()?.text()),
+        NodeKind::Abstract => Expression::Abstract {
+            name: node.significant_tokens().nth(1)?.span(),
+        },
+        NodeKind::Grouped => Expression::Grouped {
+            kind: match first_token()?.text() {
+                "(" => GroupKind::Parenthesized,
+                "[" => GroupKind::Bracketed,
+                "{" => GroupKind::Braced,
+                "set" => GroupKind::Set,
+                _ => return None,
+            },
+            expression: node.child_nodes().map(expression).collect(),
+        },
+        NodeKind::Unary => Expression::Unary {
+            operation: match first_token()?.text() {
+                "-" => UnaryOperation::Negate,
+                "!" => UnaryOperation::Not,
+                _ => return None,
+            },
+            inner: next()?,
+        },
+        NodeKind::Binary => {
+            // Operators like `==` consist of multiple raw tokens.
+            let operator = node
+                .significant_tokens()
+                .map(|token| token.text())
+                .collect::<String>();
+
+            Expression::Binary {
+                left: next()?,
+                operation: BUILTIN_OPERATORS
+                    .iter()
+                    .find(|builtin| builtin.token.to_string() == operator)?
+                    .operation,
+                right: next()?,
+            }
+        }
+        NodeKind::Infix => {
+            let Span { value, range } = first_token()?.span();
+
+            Expression::Infix {
+                left: next()?,
+                operator: Span {
+                    value: value.strip_prefix('`')?.strip_suffix('`')?,
+                    range,
+                },
+                right: next()?,
+            }
+        }
+        NodeKind::If => Expression::If {
+            condition: next()?,
+            then: next()?,
+            otherwise: next(),
+        },
+        NodeKind::Match => {
+            let on = next()?;
+            let mut cases = nodes
+                .map(match_case)
+                .collect::<Option<Vec<_>>>()?
+                .into_iter();
+
+            Expression::Match {
+                on,
+                first_case: cases.next()?,
+                other_cases: cases.collect(),
+            }
+        }
+        NodeKind::Call => Expression::Call {
+            function: next()?,
+            argument: next()?,
+        },
+        NodeKind::Function => {
+            let parameter_pattern = next()?;
+            let (parameter_domain, body) = match (next(), next()) {
+                (Some(domain), Some(body)) => (Some(domain), body),
+                (Some(body), None) => (None, body),
+                _ => return None,
+            };
+
+            Expression::Function {
+                parameter_pattern,
+                parameter_domain,
+                body,
+            }
+        }
+        NodeKind::Attributed => Expression::Attributed {
+            attribute: attribute(nodes.next()?)?,
+            expression: Box::new(expression(nodes.next()?)),
+        },
+        NodeKind::Module
+        | NodeKind::MatchCase
+        | NodeKind::Attribute
+        | NodeKind::AttributeArguments => return None,
+    })
+}
+
+fn match_case<'source>(node: SyntaxNode<'_, 'source>) -> Option<Span<MatchCase<'source>>> {
+    if node.kind() != NodeKind::MatchCase {
+        return None;
+    }
+
+    let mut nodes = node.child_nodes().map(|node| Box::new(expression(node)));
+
+    let value = match (nodes.next(), nodes.next(), nodes.next()) {
+        (Some(pattern), Some(domain), Some(maps_to)) => MatchCase {
+            pattern,
+            domain: Some(domain),
+            maps_to,
+        },
+        (Some(pattern), Some(maps_to), None) => MatchCase {
+            pattern,
+            domain: None,
+            maps_to,
+        },
+        _ => return None,
+    };
+
+    Some(Span {
+        value,
+        range: node.range(),
+    })
+}
+
+fn attribute<'source>(node: SyntaxNode<'_, 'source>) -> Option<Span<Attribute<'source>>> {
+    if node.kind() != NodeKind::Attribute {
+        return None;
+    }
+
+    Some(Span {
+        value: Attribute {
+            name: node.significant_tokens().nth(1)?.span(),
+            arguments: node.child_nodes().next().map(|arguments| Span {
+                value: arguments.child_nodes().map(expression).collect(),
+                range: arguments.range(),
+            }),
+        },
+        range: node.range(),
+    })
+}
@@ -0,0 +1,283 @@
+//! A lossless concrete syntax tree.
+//!
+//! The tree has two layers. The green layer ([`GreenNode`], [`GreenToken`]) is immutable,
+//! only knows the lengths of its elements and can share subtrees. The red layer
+//! ([`SyntaxNode`], [`SyntaxToken`]) is a cheap view on top of it that knows absolute offsets.
+//!
+//! Every raw token from the [`Lexer`](vine_lex::Lexer), including whitespace and comments
+//! (trivia), is part of the tree exactly once, so printing the tree reproduces the source.
+//! The [`ast`](crate::ast) can be derived from the tree with [`SyntaxTree::to_module`].
+
+mod build;
+mod derive;
+#[cfg(test)]
+mod tests;
+
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+    sync::Arc,
+};
+
+use parser_tools::Span;
+use vine_diagnostics::Diagnostic;
+use vine_lex::tokens::Token;
+
+use crate::{Parser, ast::Module};
+
+/// The kind of a [`GreenNode`]. Each kind except [`NodeKind::Module`],
+/// [`NodeKind::MatchCase`], [`NodeKind::Attribute`] and [`NodeKind::AttributeArguments`]
+/// corresponds to a variant of [`Expression`](crate::ast::Expression).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    Module,
+    Error,
+    Number,
+    String,
+    Character,
+    Identifier,
+    Grouped,
+    Unary,
+    Binary,
+    Infix,
+    If,
+    Match,
+    MatchCase,
+    Call,
+    Function,
+    Abstract,
+    Attributed,
+    Attribute,
+
+    /// The parenthesized arguments of an attribute.
+    AttributeArguments,
+}
+
+/// A raw token with its source text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GreenToken<'source> {
+    pub token: Token<'source>,
+    pub text: &'source str,
+}
+
+impl GreenToken<'_> {
+    /// Whether the token is whitespace or a comment.
+    pub fn is_trivia(&self) -> bool {
+        matches!(self.token, Token::Whitespace(_) | Token::Comment(_))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GreenElement<'source> {
+    Node(Arc<GreenNode<'source>>),
+    Token(GreenToken<'source>),
+}
+
+impl GreenElement<'_> {
+    pub fn text_len(&self) -> u32 {
+        match self {
+            Self::Node(node) => node.text_len,
+            Self::Token(token) => token.text.len() as u32,
+        }
+    }
+}
+
+/// An immutable node without a position.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GreenNode<'source> {
+    kind: NodeKind,
+    text_len: u32,
+    children: Vec<GreenElement<'source>>,
+}
+
+impl<'source> GreenNode<'source> {
+    pub fn new(kind: NodeKind, children: Vec<GreenElement<'source>>) -> Self {
+        Self {
+            kind,
+            text_len: children.iter().map(GreenElement::text_len).sum(),
+            children,
+        }
+    }
+
+    pub const fn kind(&self) -> NodeKind {
+        self.kind
+    }
+
+    /// The length of the text of all tokens in this node.
+    pub const fn text_len(&self) -> u32 {
+        self.text_len
+    }
+
+    pub fn children(&self) -> &[GreenElement<'source>] {
+        &self.children
+    }
+}
+
+impl Display for GreenNode<'_> {
+    /// Writes the source text of the node, including trivia.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for child in &self.children {
+            match child {
+                GreenElement::Node(node) => write!(f, "{node}")?,
+                GreenElement::Token(token) => f.write_str(token.text)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`GreenNode`] at an absolute offset.
+#[derive(Copy, Clone, Debug)]
+pub struct SyntaxNode<'tree, 'source> {
+    green: &'tree GreenNode<'source>,
+    offset: u32,
+}
+
+impl<'tree, 'source> SyntaxNode<'tree, 'source> {
+    pub const fn new_root(green: &'tree GreenNode<'source>) -> Self {
+        Self { green, offset: 0 }
+    }
+
+    pub const fn green(self) -> &'tree GreenNode<'source> {
+        self.green
+    }
+
+    pub const fn kind(self) -> NodeKind {
+        self.green.kind
+    }
+
+    /// The byte range of the node in the source. Since trivia between nodes belongs
+    /// to the enclosing node, this is the same as the range of the corresponding
+    /// AST node.
+    pub const fn range(self) -> Range<u32> {
+        self.offset..self.offset + self.green.text_len
+    }
+
+    pub fn children(self) -> impl Iterator<Item = SyntaxElement<'tree, 'source>> {
+        self.green
+            .children
+            .iter()
+            .scan(self.offset, |offset, child| {
+                let start = *offset;
+                *offset += child.text_len();
+
+                Some(match child {
+                    GreenElement::Node(green) => SyntaxElement::Node(SyntaxNode {
+                        green,
+                        offset: start,
+                    }),
+                    GreenElement::Token(green) => SyntaxElement::Token(SyntaxToken {
+                        green,
+                        offset: start,
+                    }),
+                })
+            })
+    }
+
+    pub fn child_nodes(self) -> impl Iterator<Item = SyntaxNode<'tree, 'source>> {
+        self.children().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    /// Returns the tokens directly in this node that are not trivia.
+    pub fn significant_tokens(self) -> impl Iterator<Item = SyntaxToken<'tree, 'source>> {
+        self.children().filter_map(|child| match child {
+            SyntaxElement::Token(token) if !token.green.is_trivia() => Some(token),
+            _ => None,
+        })
+    }
+}
+
+/// A [`GreenToken`] at an absolute offset.
+#[derive(Copy, Clone, Debug)]
+pub struct SyntaxToken<'tree, 'source> {
+    green: &'tree GreenToken<'source>,
+    offset: u32,
+}
+
+impl<'tree, 'source> SyntaxToken<'tree, 'source> {
+    pub const fn green(self) -> &'tree GreenToken<'source> {
+        self.green
+    }
+
+    pub const fn token(self) -> &'tree Token<'source> {
+        &self.green.token
+    }
+
+    pub const fn text(self) -> &'source str {
+        self.green.text
+    }
+
+    pub const fn range(self) -> Range<u32> {
+        self.offset..self.offset + self.green.text.len() as u32
+    }
+
+    /// Returns the token as a [`Span`] of its text.
+    pub const fn span(self) -> Span<&'source str> {
+        Span {
+            value: self.green.text,
+            range: self.range(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum SyntaxElement<'tree, 'source> {
+    Node(SyntaxNode<'tree, 'source>),
+    Token(SyntaxToken<'tree, 'source>),
+}
+
+/// The lossless syntax tree of a source file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntaxTree<'source> {
+    root: GreenNode<'source>,
+}
+
+impl<'source> SyntaxTree<'source> {
+    /// Parses a source file as a module and builds its syntax tree.
+    ///
+    /// Returns the syntax errors alongside the tree; the tree is lossless either way.
+    pub fn parse(source: &'source str) -> (Self, Vec<Diagnostic>) {
+        let mut parser = Parser::new(vine_lex::lex(source));
+        let module = parser.parse_module();
+
+        (
+            Self::from_module(source, &module),
+            parser.into_diagnostics(),
+        )
+    }
+
+    /// Builds the syntax tree of `source` from its parsed module.
+    pub fn from_module(source: &'source str, module: &Module<'source>) -> Self {
+        Self {
+            root: build::build(source, module),
+        }
+    }
+
+    pub const fn green(&self) -> &GreenNode<'source> {
+        &self.root
+    }
+
+    pub const fn root(&self) -> SyntaxNode<'_, 'source> {
+        SyntaxNode::new_root(&self.root)
+    }
+
+    /// Derives the AST from the tree.
+    ///
+    /// This is the same module the parser produces, including the parts it recovered
+    /// from syntax errors. Nodes that do not have the shape of their kind become
+    /// [`Expression::Error`](crate::ast::Expression::Error).
+    pub fn to_module(&self) -> Module<'source> {
+        derive::module(self.root())
+    }
+}
+
+impl Display for SyntaxTree<'_> {
+    /// Writes the source text the tree was built from.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.root)
+    }
+}
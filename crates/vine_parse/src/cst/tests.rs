@@ -0,0 +1,126 @@
+#![allow(non_snake_case)]
+
+mod SyntaxTree {
+    use super::super::*;
+    use crate::{ast::Expression, parse_module};
+
+    const FINITE: &str = include_str!("../../../../language-design/finite.vn");
+
+    /// Prints the kinds of all nodes, like `(Binary (Identifier) (Identifier))`.
+    fn kinds(node: SyntaxNode) -> String {
+        let mut output = format!("({:?}", node.kind());
+
+        for child in node.child_nodes() {
+            output.push(' ');
+            output.push_str(&kinds(child));
+        }
+
+        output.push(')');
+        output
+    }
+
+    #[test]
+    fn parse() {
+        let source = "// comment\nf  x /* inline */ + 1 # trailing\n\n@infix(left, 3)\nmax = function a => b\n";
+        let (tree, diagnostics) = SyntaxTree::parse(source);
+
+        assert!(diagnostics.is_empty());
+        assert_eq!(tree.to_string(), source);
+        assert_eq!(
+            kinds(tree.root()),
+            "(Module (Binary (Call (Identifier) (Identifier)) (Number)) \
+             (Attributed (Attribute (AttributeArguments (Identifier) (Number))) \
+             (Binary (Identifier) (Function (Identifier) (Identifier)))))"
+        );
+    }
+
+    #[test]
+    fn parse_finite() {
+        let (tree, diagnostics) = SyntaxTree::parse(FINITE);
+
+        assert!(diagnostics.is_empty());
+        assert_eq!(tree.to_string(), FINITE);
+        assert_eq!(tree.root().range(), 0..FINITE.len() as u32);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for source in ["a = (b +\nc = ]", "match x\n  case", "`", "@ ) ) x"] {
+            let (tree, diagnostics) = SyntaxTree::parse(source);
+
+            assert!(!diagnostics.is_empty());
+            assert_eq!(tree.to_string(), source);
+        }
+    }
+
+    #[test]
+    fn to_module() {
+        for source in [
+            FINITE,
+            "a - -b * c ** 2",
+            "x `max` y\nset {1, 2}\n[a, b]\n{ 'c', \"s\\n\" }",
+            "match x\n  case 0 => a\n  case n is Number => b",
+            "if a then b else c\nf = function x in Number => x",
+            "@doc abstract T",
+        ] {
+            let (tree, _) = SyntaxTree::parse(source);
+            assert_eq!(tree.to_module(), parse_module(source).unwrap());
+        }
+    }
+
+    #[test]
+    fn to_module_recovered() {
+        for source in [
+            "a = (b +\nc = ]",
+            "match x\n  case",
+            "`",
+            "@ ) ) x",
+            "f = function => 1\nif then",
+            "x `",
+        ] {
+            let (tree, _) = SyntaxTree::parse(source);
+            let mut parser = Parser::new(vine_lex::lex(source));
+
+            assert_eq!(tree.to_module(), parser.parse_module(), "{source:?}");
+        }
+    }
+
+    #[test]
+    fn to_module_malformed() {
+        let tree = SyntaxTree {
+            root: GreenNode::new(
+                NodeKind::Module,
+                vec![GreenElement::Node(Arc::new(GreenNode::new(
+                    NodeKind::Binary,
+                    vec![GreenElement::Token(GreenToken {
+                        token: Token::Plus,
+                        text: "+",
+                    })],
+                )))],
+            ),
+        };
+
+        assert_eq!(
+            tree.to_module().items,
+            [Span {
+                value: Expression::Error,
+                range: 0..1
+            }]
+        );
+    }
+
+    #[test]
+    fn significant_tokens() {
+        let (tree, _) = SyntaxTree::parse("a  ==  /* equal? */ b");
+        let binary = tree.root().child_nodes().next().unwrap();
+
+        assert_eq!(binary.kind(), NodeKind::Binary);
+        assert_eq!(
+            binary
+                .significant_tokens()
+                .map(|token| (token.text(), token.range()))
+                .collect::<Vec<_>>(),
+            [("=", 3..4), ("=", 4..5)]
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! An indented S-expression printer for the AST, for debugging and snapshot tests.
+//!
+//! Every node is written as `(<kind> [details] <start>..<end>` followed by its
+//! children, each on its own line and indented by two more spaces. Children
+//! other than operands and group items are labeled, like `body: (...)`:
+//!
+//! ```plain
+//! (definition 0..19
+//!   (identifier x 0..1)
+//!   (function 4..19
+//!     parameter: (identifier y 13..14)
+//!     body: (identifier y 18..19)))
+//! ```
+//!
+//! for `x = function y => y`.
+
+#[cfg(test)]
+mod tests;
+
+use parser_tools::Span;
+
+use crate::ast::{BinaryOperation, Expression, GroupKind, Module, UnaryOperation};
+
+/// Writes every item of the module, each followed by a line break.
+pub fn module(module: &Module) -> String {
+    let mut output = String::new();
+
+    for item in &module.items {
+        output += &expression(item);
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Writes an expression and its children.
+pub fn expression(expression: &Span<Expression>) -> String {
+    let mut output = String::new();
+    Node::from(expression).write(&mut output, 0);
+    output
+}
+
+struct Node {
+    head: String,
+    children: Vec<(Option<&'static str>, Node)>,
+}
+
+impl Node {
+    fn from(expression: &Span<Expression>) -> Self {
+        let range = &expression.range;
+        let node = |head: String, children| Self {
+            head: format!("{head} {}..{}", range.start, range.end),
+            children,
+        };
+        let child = |label, expression| (label, Self::from(expression));
+
+        match &expression.value {
+            Expression::Error => node("error".into(), Vec::new()),
+            Expression::Number(number) => node(format!("number {number}"), Vec::new()),
+            Expression::String(string) => node(format!("string {string:?}"), Vec::new()),
+            Expression::Character(character) => {
+                node(format!("character {character:?}"), Vec::new())
+            }
+            Expression::Identifier(name) => node(format!("identifier {name}"), Vec::new()),
+            Expression::Grouped {
+                kind,
+                expression: items,
+            } => node(
+                match kind {
+                    GroupKind::Parenthesized => "parenthesized",
+                    GroupKind::Bracketed => "bracketed",
+                    GroupKind::Braced => "braced",
+                    GroupKind::Set => "set",
+                }
+                .into(),
+                items.iter().map(|item| child(None, item)).collect(),
+            ),
+            Expression::Unary { operation, inner } => node(
+                match operation {
+                    UnaryOperation::Negate => "negate",
+                    UnaryOperation::Not => "not",
+                }
+                .into(),
+                vec![child(None, inner)],
+            ),
+            Expression::Binary {
+                left,
+                operation,
+                right,
+            } => node(
+                match operation {
+                    BinaryOperation::Definition => "definition".into(),
+                    BinaryOperation::Access => "access".into(),
+                    operation => format!("binary {operation:?}"),
+                },
+                vec![child(None, left), child(None, right)],
+            ),
+            Expression::If {
+                condition,
+                then,
+                otherwise,
+            } => node(
+                "if".into(),
+                [
+                    child(Some("condition"), condition),
+                    child(Some("then"), then),
+                ]
+                .into_iter()
+                .chain(
+                    otherwise
+                        .iter()
+                        .map(|otherwise| child(Some("else"), otherwise)),
+                )
+                .collect(),
+            ),
+            Expression::Match {
+                on,
+                first_case,
+                other_cases,
+            } => node(
+                "match".into(),
+                [(Some("on"), Self::from(on))]
+                    .into_iter()
+                    .chain([first_case].into_iter().chain(other_cases).map(|case| {
+                        let mut children = vec![child(Some("pattern"), &case.value.pattern)];
+
+                        if let Some(domain) = &case.value.domain {
+                            children.push(child(Some("domain"), domain));
+                        }
+
+                        children.push(child(Some("body"), &case.value.maps_to));
+
+                        (
+                            None,
+                            Self {
+                                head: format!("case {}..{}", case.range.start, case.range.end),
+                                children,
+                            },
+                        )
+                    }))
+                    .collect(),
+            ),
+            Expression::Infix {
+                left,
+                operator,
+                right,
+            } => node(
+                format!("infix {}", operator.value),
+                vec![child(None, left), child(None, right)],
+            ),
+            Expression::Call { function, argument } => node(
+                "call".into(),
+                vec![
+                    child(Some("function"), function),
+                    child(Some("argument"), argument),
+                ],
+            ),
+            Expression::Function {
+                parameter_pattern,
+                parameter_domain,
+                body,
+            } => {
+                let mut children = vec![child(Some("parameter"), parameter_pattern)];
+
+                if let Some(domain) = parameter_domain {
+                    children.push(child(Some("domain"), domain));
+                }
+
+                children.push(child(Some("body"), body));
+                node("function".into(), children)
+            }
+            Expression::Abstract { name } => node(format!("abstract {}", name.value), Vec::new()),
+            Expression::Attributed {
+                attribute,
+                expression,
+            } => node(
+                format!("attributed @{}", attribute.value.name.value),
+                attribute
+                    .value
+                    .arguments
+                    .iter()
+                    .flat_map(|arguments| &arguments.value)
+                    .map(|argument| child(Some("argument"), argument))
+                    .chain([child(None, expression)])
+                    .collect(),
+            ),
+        }
+    }
+
+    fn write(&self, output: &mut String, indentation: usize) {
+        output.push('(');
+        output.push_str(&self.head);
+
+        for (label, child) in &self.children {
+            output.push('\n');
+            output.extend(std::iter::repeat_n(' ', indentation + 2));
+
+            if let Some(label) = label {
+                output.push_str(label);
+                output.push_str(": ");
+            }
+
+            child.write(output, indentation + 2);
+        }
+
+        output.push(')');
+    }
+}
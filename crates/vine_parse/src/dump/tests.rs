@@ -0,0 +1,53 @@
+#![allow(non_snake_case)]
+
+mod Node {
+    use super::super::*;
+    use crate::{parse_expression, parse_module};
+
+    #[test]
+    fn expression_tree() {
+        let parsed = parse_expression("f = function x is Number => -x + 1").unwrap();
+
+        assert_eq!(
+            expression(&parsed),
+            "\
+(definition 0..34
+  (identifier f 0..1)
+  (function 4..34
+    parameter: (identifier x 13..14)
+    domain: (identifier Number 18..24)
+    body: (binary Add 28..34
+      (negate 28..30
+        (identifier x 29..30))
+      (number 1 33..34))))"
+        );
+    }
+
+    #[test]
+    fn module_tree() {
+        let parsed = parse_module("@merge(1) x = \"a\"\nmatch x\n    case 'b' => ()\n").unwrap();
+
+        assert_eq!(
+            module(&parsed),
+            "\
+(attributed @merge 0..17
+  argument: (number 1 7..8)
+  (definition 10..17
+    (identifier x 10..11)
+    (string \"a\" 14..17)))
+(match 18..44
+  on: (identifier x 24..25)
+  (case 30..44
+    pattern: (character 'b' 35..38)
+    body: (parenthesized 42..44)))
+"
+        );
+    }
+
+    #[test]
+    fn finite_tree() {
+        let parsed = parse_module(include_str!("../../../../language-design/finite.vn")).unwrap();
+
+        assert_eq!(module(&parsed), include_str!("finite.snap"));
+    }
+}
@@ -1,16 +1,44 @@
 pub mod ast;
+pub mod cst;
+pub mod dump;
 pub mod lower;
 mod parser;
 
 pub use parser::*;
 use parser_tools::Span;
+use vine_diagnostics::Diagnostic;
 use vine_lex::lex;
 
-use crate::ast::Expression;
+use crate::ast::{Expression, Module};
+
+/// Parses a source file as a sequence of line-break-delimited items.
+///
+/// Returns all syntax errors in the input if there are any.
+pub fn parse_module(input: &str) -> Result<Module<'_>, Vec<Diagnostic>> {
+    let mut parser = Parser::new(lex(input));
+    let module = parser.parse_module();
+    let diagnostics = parser.into_diagnostics();
+
+    if diagnostics.is_empty() {
+        Ok(module)
+    } else {
+        Err(diagnostics)
+    }
+}
 
 /// Parses an expression, ensuring that there are no more tokens after the expression.
+///
+/// Returns all syntax errors in the input if there are any.
 pub fn parse_expression<'source>(
     input: &'source str,
-) -> Result<Span<Expression<'source>>, Error<'source>> {
-    Parser::new(lex(input)).parse_root_expression()
+) -> Result<Span<Expression<'source>>, Vec<Diagnostic>> {
+    let mut parser = Parser::new(lex(input));
+    let expression = parser.parse_root_expression();
+    let diagnostics = parser.into_diagnostics();
+
+    if diagnostics.is_empty() {
+        Ok(expression)
+    } else {
+        Err(diagnostics)
+    }
 }
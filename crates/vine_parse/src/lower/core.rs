@@ -0,0 +1,463 @@
+use std::{iter, ops::Range};
+
+use parser_tools::Span;
+use vine_diagnostics::Diagnostic;
+
+use crate::{
+    ast::{Attribute, BinaryOperation, Expression, GroupKind, Module, UnaryOperation},
+    lower::{Literal, Pattern, PatternError, field},
+};
+
+/// The name of the parameter of functions whose parameter pattern has alternatives.
+/// It is not a valid identifier, so it cannot clash with names in the source.
+pub const ARGUMENT: &str = "$argument";
+
+/// An expression of the core language, which is the AST with its syntactic sugar removed:
+///
+/// - parenthesized groups are tuples or records, and `(x)` is just `x`;
+/// - `if` always has an `else`, which is `()` if it was omitted;
+/// - patterns are [`Pattern`]s without alternatives: match cases with `a or b` are
+///   split into a case per alternative, and functions with alternatives in their
+///   parameter match on the [`ARGUMENT`] instead;
+/// - `set {...}` constructs a set, and `a.b` projects a field;
+/// - custom infix operators `` a `f` b `` call `f` with `(left = a, right = b)`.
+///
+/// Every node keeps the range of the source it was lowered from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Core<'source> {
+    /// A placeholder for a part that could not be lowered.
+    /// The error has been reported as a diagnostic.
+    Error,
+
+    Literal(Literal<'source>),
+    Identifier(&'source str),
+
+    /// `(a, b)`. `()` is the empty tuple.
+    Tuple(Vec<Span<Core<'source>>>),
+
+    /// `(left = a, right = b)`
+    Record(Vec<(Span<&'source str>, Span<Core<'source>>)>),
+
+    /// Constructs a set from its elements.
+    Set(Vec<Span<Core<'source>>>),
+
+    /// `[a, b]`
+    List(Vec<Span<Core<'source>>>),
+
+    /// `{...}`, whose value is the value of the last expression.
+    Block(Vec<Span<Core<'source>>>),
+
+    /// `<PATTERN> = <VALUE>`
+    Definition {
+        pattern: Span<Pattern<'source>>,
+        value: Box<Span<Core<'source>>>,
+    },
+
+    Unary {
+        operation: UnaryOperation,
+        operand: Box<Span<Core<'source>>>,
+    },
+
+    /// A binary operation other than [`BinaryOperation::Definition`] and [`BinaryOperation::Access`].
+    Binary {
+        left: Box<Span<Core<'source>>>,
+        operation: BinaryOperation,
+        right: Box<Span<Core<'source>>>,
+    },
+
+    /// The field of a record.
+    Field {
+        record: Box<Span<Core<'source>>>,
+        name: Span<&'source str>,
+    },
+
+    If {
+        condition: Box<Span<Core<'source>>>,
+        then: Box<Span<Core<'source>>>,
+        otherwise: Box<Span<Core<'source>>>,
+    },
+
+    /// A match with at least one case.
+    Match {
+        on: Box<Span<Core<'source>>>,
+        cases: Vec<Span<Case<'source>>>,
+    },
+
+    Call {
+        function: Box<Span<Core<'source>>>,
+        argument: Box<Span<Core<'source>>>,
+    },
+
+    Function {
+        parameter: Span<Pattern<'source>>,
+        domain: Option<Box<Span<Core<'source>>>>,
+        body: Box<Span<Core<'source>>>,
+    },
+
+    /// `abstract <IDENTIFIER>`
+    Abstract {
+        name: Span<&'source str>,
+    },
+
+    Attributed {
+        attribute: Span<Attribute<'source>>,
+        expression: Box<Span<Core<'source>>>,
+    },
+}
+
+/// A match case whose pattern has no alternatives.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case<'source> {
+    pub pattern: Span<Pattern<'source>>,
+    pub domain: Option<Box<Span<Core<'source>>>>,
+    pub body: Box<Span<Core<'source>>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum LowerError {
+    /// A definition whose pattern has alternatives.
+    AlternativesInDefinition { range: Range<u32> },
+
+    /// An access `a.b` where `b` is not a name.
+    NotAFieldName { range: Range<u32> },
+}
+
+impl LowerError {
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        match self {
+            Self::AlternativesInDefinition { range } => {
+                Diagnostic::error("a definition cannot have alternatives")
+                    .with_primary(range.clone(), "")
+                    .with_help("use a `match` to match one of multiple patterns")
+            }
+            Self::NotAFieldName { range } => Diagnostic::error("expected a field name")
+                .with_primary(range.clone(), "not a name")
+                .with_note("fields are accessed with `record.name`"),
+        }
+    }
+}
+
+impl<'source> Core<'source> {
+    /// Lowers an expression to the core language. Parts that cannot be lowered are
+    /// reported to `diagnostics` and lowered to [`Core::Error`].
+    pub fn lower(
+        expression: &Span<Expression<'source>>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Span<Self> {
+        let range = expression.range.clone();
+
+        let value = match &expression.value {
+            Expression::Error => Self::Error,
+            Expression::Number(number) => Self::Literal(Literal::Number(number.clone())),
+            Expression::String(string) => Self::Literal(Literal::String(string.clone())),
+            Expression::Character(character) => Self::Literal(Literal::Character(*character)),
+            Expression::Identifier(name) => Self::Identifier(name),
+            Expression::Grouped {
+                kind: GroupKind::Parenthesized,
+                expression: items,
+            } => return Self::lower_group(items, range, diagnostics),
+            Expression::Grouped {
+                kind,
+                expression: items,
+            } => {
+                let items = items
+                    .iter()
+                    .map(|item| Self::lower(item, diagnostics))
+                    .collect();
+
+                match kind {
+                    GroupKind::Set => Self::Set(items),
+                    GroupKind::Braced => Self::Block(items),
+                    _ => Self::List(items),
+                }
+            }
+            Expression::Unary {
+                operation: UnaryOperation::Negate,
+                inner,
+            } if let Expression::Number(number) = &inner.value => {
+                Self::Literal(Literal::Number(-number))
+            }
+            Expression::Unary { operation, inner } => Self::Unary {
+                operation: *operation,
+                operand: Self::boxed(inner, diagnostics),
+            },
+            Expression::Binary {
+                left,
+                operation: BinaryOperation::Definition,
+                right,
+            } => {
+                let pattern = Pattern::lower(left, diagnostics);
+
+                if let Pattern::Or(_) = pattern.value {
+                    diagnostics.push(
+                        LowerError::AlternativesInDefinition {
+                            range: pattern.range.clone(),
+                        }
+                        .to_diagnostic(),
+                    );
+                }
+
+                Self::Definition {
+                    pattern,
+                    value: Self::boxed(right, diagnostics),
+                }
+            }
+            Expression::Binary {
+                left,
+                operation: BinaryOperation::Access,
+                right,
+            } => match right.value {
+                Expression::Identifier(name) => Self::Field {
+                    record: Self::boxed(left, diagnostics),
+                    name: Span {
+                        value: name,
+                        range: right.range.clone(),
+                    },
+                },
+                _ => {
+                    diagnostics.push(
+                        LowerError::NotAFieldName {
+                            range: right.range.clone(),
+                        }
+                        .to_diagnostic(),
+                    );
+                    Self::Error
+                }
+            },
+            Expression::Binary {
+                left,
+                operation,
+                right,
+            } => Self::Binary {
+                left: Self::boxed(left, diagnostics),
+                operation: *operation,
+                right: Self::boxed(right, diagnostics),
+            },
+            Expression::If {
+                condition,
+                then,
+                otherwise,
+            } => Self::If {
+                condition: Self::boxed(condition, diagnostics),
+                then: Self::boxed(then, diagnostics),
+                otherwise: match otherwise {
+                    Some(otherwise) => Self::boxed(otherwise, diagnostics),
+                    None => Box::new(Span {
+                        value: Self::Tuple(Vec::new()),
+                        range: range.end..range.end,
+                    }),
+                },
+            },
+            Expression::Match {
+                on,
+                first_case,
+                other_cases,
+            } => Self::Match {
+                on: Self::boxed(on, diagnostics),
+                cases: [first_case]
+                    .into_iter()
+                    .chain(other_cases)
+                    .flat_map(|case| {
+                        let pattern = Pattern::lower(&case.value.pattern, diagnostics);
+                        let domain = case
+                            .value
+                            .domain
+                            .as_ref()
+                            .map(|domain| Self::boxed(domain, diagnostics));
+                        let body = Self::boxed(&case.value.maps_to, diagnostics);
+
+                        Pattern::alternatives(&pattern)
+                            .into_iter()
+                            .map(move |pattern| Span {
+                                value: Case {
+                                    pattern,
+                                    domain: domain.clone(),
+                                    body: body.clone(),
+                                },
+                                range: case.range.clone(),
+                            })
+                    })
+                    .collect(),
+            },
+            Expression::Infix {
+                left,
+                operator,
+                right,
+            } => Self::Call {
+                function: Box::new(Span {
+                    value: Self::Identifier(operator.value),
+                    range: operator.range.clone(),
+                }),
+                argument: Box::new(Span {
+                    value: Self::Record(vec![
+                        (
+                            Span {
+                                value: "left",
+                                range: left.range.clone(),
+                            },
+                            Self::lower(left, diagnostics),
+                        ),
+                        (
+                            Span {
+                                value: "right",
+                                range: right.range.clone(),
+                            },
+                            Self::lower(right, diagnostics),
+                        ),
+                    ]),
+                    range: range.clone(),
+                }),
+            },
+            Expression::Call { function, argument } => Self::Call {
+                function: Self::boxed(function, diagnostics),
+                argument: Self::boxed(argument, diagnostics),
+            },
+            Expression::Function {
+                parameter_pattern,
+                parameter_domain,
+                body,
+            } => {
+                let parameter = Pattern::lower(parameter_pattern, diagnostics);
+                let domain = parameter_domain
+                    .as_ref()
+                    .map(|domain| Self::boxed(domain, diagnostics));
+                let body = Self::boxed(body, diagnostics);
+                let alternatives = Pattern::alternatives(&parameter);
+
+                if alternatives.len() == 1 {
+                    Self::Function {
+                        parameter: alternatives.into_iter().next().unwrap(),
+                        domain,
+                        body,
+                    }
+                } else {
+                    // function a or b => body
+                    // function $argument => match $argument case a => body case b => body
+                    let range = parameter.range.clone();
+
+                    Self::Function {
+                        parameter: Span {
+                            value: Pattern::Binding(ARGUMENT),
+                            range: range.clone(),
+                        },
+                        domain,
+                        body: Box::new(Span {
+                            value: Self::Match {
+                                on: Box::new(Span {
+                                    value: Self::Identifier(ARGUMENT),
+                                    range,
+                                }),
+                                cases: alternatives
+                                    .into_iter()
+                                    .map(|pattern| Span {
+                                        range: pattern.range.start..body.range.end,
+                                        value: Case {
+                                            pattern,
+                                            domain: None,
+                                            body: body.clone(),
+                                        },
+                                    })
+                                    .collect(),
+                            },
+                            range: body.range.clone(),
+                        }),
+                    }
+                }
+            }
+            Expression::Abstract { name } => Self::Abstract { name: name.clone() },
+            Expression::Attributed {
+                attribute,
+                expression,
+            } => Self::Attributed {
+                attribute: attribute.clone(),
+                expression: Self::boxed(expression, diagnostics),
+            },
+        };
+
+        Span { value, range }
+    }
+
+    /// Returns the expression inside the attributes of `core`.
+    pub fn unattributed(mut core: &Span<Self>) -> &Span<Self> {
+        while let Self::Attributed { expression, .. } = &core.value {
+            core = expression;
+        }
+
+        core
+    }
+
+    /// Returns the attributes of `core`, outermost first.
+    pub fn attributes<'a>(
+        mut core: &'a Span<Self>,
+    ) -> impl Iterator<Item = &'a Span<Attribute<'source>>> {
+        iter::from_fn(move || match &core.value {
+            Self::Attributed {
+                attribute,
+                expression,
+            } => {
+                core = expression;
+                Some(attribute)
+            }
+            _ => None,
+        })
+    }
+
+    fn boxed(
+        expression: &Span<Expression<'source>>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Box<Span<Self>> {
+        Box::new(Self::lower(expression, diagnostics))
+    }
+
+    fn lower_group(
+        items: &[Span<Expression<'source>>],
+        range: Range<u32>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Span<Self> {
+        let value = if let [item] = items
+            && field(item).is_none()
+        {
+            // Just parentheses.
+            return Self::lower(item, diagnostics);
+        } else if items.iter().all(|item| field(item).is_none()) {
+            Self::Tuple(
+                items
+                    .iter()
+                    .map(|item| Self::lower(item, diagnostics))
+                    .collect(),
+            )
+        } else if items.iter().all(|item| field(item).is_some()) {
+            Self::Record(
+                items
+                    .iter()
+                    .filter_map(field)
+                    .map(|(name, value)| (name, Self::lower(value, diagnostics)))
+                    .collect(),
+            )
+        } else {
+            diagnostics.push(
+                PatternError::MixedFields {
+                    range: range.clone(),
+                }
+                .to_diagnostic(),
+            );
+            Self::Error
+        };
+
+        Span { value, range }
+    }
+}
+
+/// Lowers all items of a module to the core language.
+pub fn lower_module<'source>(
+    module: &Module<'source>,
+) -> (Vec<Span<Core<'source>>>, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+    let items = module
+        .items
+        .iter()
+        .map(|item| Core::lower(item, &mut diagnostics))
+        .collect();
+
+    (items, diagnostics)
+}
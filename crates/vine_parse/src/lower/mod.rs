@@ -1 +1,9 @@
-//! Lowered AST.
+//! Lowered AST: patterns and the core language the AST desugars into.
+
+mod core;
+mod pattern;
+#[cfg(test)]
+mod tests;
+
+pub use core::*;
+pub use pattern::*;
@@ -0,0 +1,458 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+    iter,
+    ops::Range,
+};
+
+use parser_tools::Span;
+use vine_diagnostics::Diagnostic;
+use vine_num::Rational;
+
+use crate::{
+    BUILTIN_OPERATORS,
+    ast::{BinaryOperation, Expression, GroupKind, MatchCase, Module, UnaryOperation},
+};
+
+/// A pattern, as used by match cases and function parameters:
+///
+/// ```plain
+/// _
+/// <IDENTIFIER>
+/// <LITERAL>
+/// (<PATTERN>, ...)
+/// (<IDENTIFIER> = <PATTERN>, ...)
+/// <PATTERN> or <PATTERN>
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern<'source> {
+    /// A placeholder for a part that is not a valid pattern.
+    /// The error has been reported as a diagnostic.
+    Error,
+
+    /// `_`, which matches anything.
+    Wildcard,
+
+    /// An identifier. If it refers to an abstract value, it matches that value;
+    /// otherwise, it matches anything and binds the name.
+    Binding(&'source str),
+
+    /// A literal that matches only its own value, like `1`, `-0.5` or `"text"`.
+    Literal(Literal<'source>),
+
+    /// A tuple `(a, b)`, matched by position. `()` is the empty tuple.
+    Tuple(Vec<Span<Pattern<'source>>>),
+
+    /// A record `(left = a, right = b)`, matched by field names.
+    Record(Vec<(Span<&'source str>, Span<Pattern<'source>>)>),
+
+    /// Alternatives `a or b or c`. There are always at least two.
+    Or(Vec<Span<Pattern<'source>>>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal<'source> {
+    Number(Rational),
+    String(Cow<'source, str>),
+    Character(char),
+}
+
+impl Display for Literal<'_> {
+    /// Writes the literal in Vine syntax.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{number}"),
+            Self::String(string) => write!(f, "{string:?}"),
+            Self::Character(character) => write!(f, "{character:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum PatternError<'source> {
+    /// An expression that cannot be used as a pattern.
+    NotAPattern {
+        /// What was found, like "a function".
+        found: String,
+        range: Range<u32>,
+    },
+
+    /// A parenthesized group, in a pattern or an expression,
+    /// that has both positional and named fields.
+    MixedFields { range: Range<u32> },
+
+    /// A record pattern that names a field more than once.
+    DuplicateField {
+        name: &'source str,
+        range: Range<u32>,
+        previous: Range<u32>,
+    },
+}
+
+impl PatternError<'_> {
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        match self {
+            Self::NotAPattern { found, range } => {
+                Diagnostic::error(format!("expected a pattern, found {found}"))
+                    .with_primary(range.clone(), "not a pattern")
+                    .with_help(
+                        "patterns are names, `_`, literals, tuples `(a, b)`, \
+                         records `(left = a, right = b)` and alternatives `a or b`",
+                    )
+            }
+            Self::MixedFields { range } => {
+                Diagnostic::error("a group cannot have both named and positional fields")
+                    .with_primary(range.clone(), "")
+                    .with_help("use either a tuple `(a, b)` or a record `(left = a, right = b)`")
+            }
+            Self::DuplicateField {
+                name,
+                range,
+                previous,
+            } => Diagnostic::error(format!("field `{name}` is matched more than once"))
+                .with_primary(range.clone(), "matched again here")
+                .with_secondary(previous.clone(), "first matched here"),
+        }
+    }
+}
+
+impl<'source> Pattern<'source> {
+    /// Lowers an expression to a pattern. Parts that are not valid patterns are
+    /// reported to `diagnostics` and lowered to [`Pattern::Error`].
+    pub fn lower(
+        expression: &Span<Expression<'source>>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Span<Self> {
+        let mut report = |error: PatternError| {
+            diagnostics.push(error.to_diagnostic());
+            Self::Error
+        };
+
+        let value = match &expression.value {
+            Expression::Error => Self::Error,
+            Expression::Identifier("_") => Self::Wildcard,
+            Expression::Identifier(name) => Self::Binding(name),
+            Expression::Number(number) => Self::Literal(Literal::Number(number.clone())),
+            Expression::String(string) => Self::Literal(Literal::String(string.clone())),
+            Expression::Character(character) => Self::Literal(Literal::Character(*character)),
+            Expression::Unary {
+                operation: UnaryOperation::Negate,
+                inner,
+            } if let Expression::Number(number) = &inner.value => {
+                Self::Literal(Literal::Number(-number))
+            }
+            Expression::Grouped {
+                kind: GroupKind::Parenthesized,
+                expression: items,
+            } => return Self::lower_group(items, expression.range.clone(), diagnostics),
+            Expression::Binary {
+                operation: BinaryOperation::Or,
+                ..
+            } => {
+                let mut alternatives = Vec::new();
+                Self::lower_alternatives(expression, &mut alternatives, diagnostics);
+                Self::Or(alternatives)
+            }
+            _ => report(PatternError::NotAPattern {
+                found: describe(&expression.value),
+                range: expression.range.clone(),
+            }),
+        };
+
+        Span {
+            value,
+            range: expression.range.clone(),
+        }
+    }
+
+    fn lower_group(
+        items: &[Span<Expression<'source>>],
+        range: Range<u32>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Span<Self> {
+        let value = if let [item] = items
+            && field(item).is_none()
+        {
+            // Just parentheses.
+            return Self::lower(item, diagnostics);
+        } else if items.iter().all(|item| field(item).is_none()) {
+            Self::Tuple(
+                items
+                    .iter()
+                    .map(|item| Self::lower(item, diagnostics))
+                    .collect(),
+            )
+        } else if items.iter().all(|item| field(item).is_some()) {
+            let mut fields = Vec::<(Span<&str>, Span<Self>)>::new();
+
+            for (name, pattern) in items.iter().filter_map(field) {
+                if let Some((previous, _)) =
+                    fields.iter().find(|(other, _)| other.value == name.value)
+                {
+                    diagnostics.push(
+                        PatternError::DuplicateField {
+                            name: name.value,
+                            range: name.range.clone(),
+                            previous: previous.range.clone(),
+                        }
+                        .to_diagnostic(),
+                    );
+                }
+
+                fields.push((name, Self::lower(pattern, diagnostics)));
+            }
+
+            Self::Record(fields)
+        } else {
+            diagnostics.push(
+                PatternError::MixedFields {
+                    range: range.clone(),
+                }
+                .to_diagnostic(),
+            );
+            Self::Error
+        };
+
+        Span { value, range }
+    }
+
+    /// Returns the names the pattern binds, in order. For alternatives `a or b`,
+    /// these are the names of the first alternative.
+    pub fn bound_names(pattern: &Span<Self>) -> Vec<Span<&'source str>> {
+        match &pattern.value {
+            Self::Binding(name) => vec![Span {
+                value: name,
+                range: pattern.range.clone(),
+            }],
+            Self::Tuple(components) => components.iter().flat_map(Self::bound_names).collect(),
+            Self::Record(fields) => fields
+                .iter()
+                .flat_map(|(_, field)| Self::bound_names(field))
+                .collect(),
+            Self::Or(alternatives) => alternatives
+                .first()
+                .map(Self::bound_names)
+                .unwrap_or_default(),
+            Self::Error | Self::Wildcard | Self::Literal(_) => Vec::new(),
+        }
+    }
+
+    /// Flattens `a or b or c` into its alternatives.
+    fn lower_alternatives(
+        expression: &Span<Expression<'source>>,
+        alternatives: &mut Vec<Span<Self>>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        match &expression.value {
+            Expression::Binary {
+                left,
+                operation: BinaryOperation::Or,
+                right,
+            } => {
+                Self::lower_alternatives(left, alternatives, diagnostics);
+                Self::lower_alternatives(right, alternatives, diagnostics);
+            }
+            _ => alternatives.push(Self::lower(expression, diagnostics)),
+        }
+    }
+}
+
+impl<'source> Pattern<'source> {
+    /// Returns the patterns without alternatives that together match the same values,
+    /// in order. Alternatives nested in tuples and records are expanded, so
+    /// `(a or b, c or d)` has the four alternatives `(a, c)`, `(a, d)`, `(b, c)` and `(b, d)`.
+    pub fn alternatives(pattern: &Span<Self>) -> Vec<Span<Self>> {
+        let with_range = |value| Span {
+            value,
+            range: pattern.range.clone(),
+        };
+
+        match &pattern.value {
+            Pattern::Or(alternatives) => alternatives.iter().flat_map(Self::alternatives).collect(),
+            Pattern::Tuple(items) => product(items.iter().map(Self::alternatives))
+                .into_iter()
+                .map(|items| with_range(Pattern::Tuple(items)))
+                .collect(),
+            Pattern::Record(fields) => product(
+                fields
+                    .iter()
+                    .map(|(_, pattern)| Self::alternatives(pattern)),
+            )
+            .into_iter()
+            .map(|patterns| {
+                with_range(Pattern::Record(
+                    fields
+                        .iter()
+                        .map(|(name, _)| name.clone())
+                        .zip(patterns)
+                        .collect(),
+                ))
+            })
+            .collect(),
+            _ => vec![pattern.clone()],
+        }
+    }
+}
+
+/// Returns every combination of one item of each list.
+fn product<T: Clone>(lists: impl Iterator<Item = Vec<T>>) -> Vec<Vec<T>> {
+    lists.fold(vec![Vec::new()], |combinations, list| {
+        combinations
+            .iter()
+            .flat_map(|combination| {
+                list.iter().map(move |item| {
+                    let mut combination = combination.clone();
+                    combination.push(item.clone());
+                    combination
+                })
+            })
+            .collect()
+    })
+}
+
+/// Returns the name and the pattern or value of a record field `name = ...`.
+pub fn field<'a, 'source>(
+    item: &'a Span<Expression<'source>>,
+) -> Option<(Span<&'source str>, &'a Span<Expression<'source>>)> {
+    match &item.value {
+        Expression::Binary {
+            left,
+            operation: BinaryOperation::Definition,
+            right,
+        } if let Expression::Identifier(name) = left.value => Some((
+            Span {
+                value: name,
+                range: left.range.clone(),
+            },
+            right,
+        )),
+        _ => None,
+    }
+}
+
+/// Describes an expression that is not a pattern, like "a function".
+fn describe(expression: &Expression) -> String {
+    match expression {
+        Expression::Grouped {
+            kind: GroupKind::Bracketed,
+            ..
+        } => "a bracketed group".into(),
+        Expression::Grouped {
+            kind: GroupKind::Braced,
+            ..
+        } => "a block".into(),
+        Expression::Grouped { .. } => "a set".into(),
+        Expression::Unary {
+            operation: UnaryOperation::Negate,
+            ..
+        } => "a negation".into(),
+        Expression::Unary {
+            operation: UnaryOperation::Not,
+            ..
+        } => "a `!` expression".into(),
+        Expression::Binary {
+            operation: BinaryOperation::Definition,
+            ..
+        } => "a definition".into(),
+        Expression::Binary { operation, .. } => BUILTIN_OPERATORS
+            .iter()
+            .find(|builtin| builtin.operation == *operation)
+            .map(|builtin| format!("a `{}` expression", builtin.token))
+            .unwrap(),
+        Expression::If { .. } => "an `if` expression".into(),
+        Expression::Match { .. } => "a `match` expression".into(),
+        Expression::Infix { operator, .. } => format!("a `{}` expression", operator.value),
+        Expression::Call { .. } => "a call".into(),
+        Expression::Function { .. } => "a function".into(),
+        Expression::Abstract { .. } => "an `abstract` declaration".into(),
+        Expression::Attributed { .. } => "an attributed expression".into(),
+        Expression::Error
+        | Expression::Number(_)
+        | Expression::String(_)
+        | Expression::Character(_)
+        | Expression::Identifier(_) => unreachable!("valid pattern"),
+    }
+}
+
+/// Checks the patterns of all match cases and function parameters in a module.
+pub fn check_patterns(module: &Module) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for item in &module.items {
+        check_expression(item, &mut diagnostics);
+    }
+
+    diagnostics
+}
+
+fn check_expression(expression: &Span<Expression>, diagnostics: &mut Vec<Diagnostic>) {
+    match &expression.value {
+        Expression::Error
+        | Expression::Number(_)
+        | Expression::String(_)
+        | Expression::Character(_)
+        | Expression::Identifier(_)
+        | Expression::Abstract { .. } => {}
+        Expression::Grouped {
+            expression: items, ..
+        } => {
+            for item in items {
+                check_expression(item, diagnostics);
+            }
+        }
+        Expression::Unary { inner, .. }
+        | Expression::Attributed {
+            expression: inner, ..
+        } => check_expression(inner, diagnostics),
+        Expression::Binary { left, right, .. }
+        | Expression::Infix { left, right, .. }
+        | Expression::Call {
+            function: left,
+            argument: right,
+        } => {
+            check_expression(left, diagnostics);
+            check_expression(right, diagnostics);
+        }
+        Expression::If {
+            condition,
+            then,
+            otherwise,
+        } => {
+            for expression in [condition, then].into_iter().chain(otherwise) {
+                check_expression(expression, diagnostics);
+            }
+        }
+        Expression::Match {
+            on,
+            first_case,
+            other_cases,
+        } => {
+            check_expression(on, diagnostics);
+
+            for case in iter::once(first_case).chain(other_cases) {
+                let MatchCase {
+                    pattern,
+                    domain,
+                    maps_to,
+                } = &case.value;
+
+                Pattern::lower(pattern, diagnostics);
+
+                for expression in domain.iter().chain([maps_to]) {
+                    check_expression(expression, diagnostics);
+                }
+            }
+        }
+        Expression::Function {
+            parameter_pattern,
+            parameter_domain,
+            body,
+        } => {
+            Pattern::lower(parameter_pattern, diagnostics);
+
+            for expression in parameter_domain.iter().chain([body]) {
+                check_expression(expression, diagnostics);
+            }
+        }
+    }
+}
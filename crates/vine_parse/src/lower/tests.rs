@@ -0,0 +1,404 @@
+#![allow(non_snake_case)]
+
+mod Pattern {
+    use std::ops::Range;
+
+    use parser_tools::Span;
+    use vine_num::Rational;
+
+    use super::super::*;
+    use crate::{parse_expression, parse_module};
+
+    fn lower(input: &str) -> (Pattern<'_>, Vec<(String, Range<u32>)>) {
+        let mut diagnostics = Vec::new();
+        let pattern = Pattern::lower(&parse_expression(input).unwrap(), &mut diagnostics);
+
+        let diagnostics = diagnostics
+            .into_iter()
+            .map(|diagnostic| {
+                let range = diagnostic.primary_range().unwrap();
+                (diagnostic.message, range)
+            })
+            .collect();
+
+        (pattern.value, diagnostics)
+    }
+
+    fn span<T>(value: T, range: Range<u32>) -> Span<T> {
+        Span { value, range }
+    }
+
+    #[test]
+    fn lower_simple() {
+        assert_eq!(lower("_"), (Pattern::Wildcard, vec![]));
+        assert_eq!(lower("x"), (Pattern::Binding("x"), vec![]));
+        assert_eq!(lower("(x)"), (Pattern::Binding("x"), vec![]));
+        assert_eq!(
+            lower("-3"),
+            (
+                Pattern::Literal(Literal::Number(-&Rational::from(3))),
+                vec![]
+            )
+        );
+        assert_eq!(
+            lower("'c'"),
+            (Pattern::Literal(Literal::Character('c')), vec![])
+        );
+        assert_eq!(lower("()"), (Pattern::Tuple(vec![]), vec![]));
+    }
+
+    #[test]
+    fn lower_nested() {
+        assert_eq!(
+            lower("(a, _) or (left = (b, 1), right = c)").0,
+            Pattern::Or(vec![
+                span(
+                    Pattern::Tuple(vec![
+                        span(Pattern::Binding("a"), 1..2),
+                        span(Pattern::Wildcard, 4..5),
+                    ]),
+                    0..6
+                ),
+                span(
+                    Pattern::Record(vec![
+                        (
+                            span("left", 11..15),
+                            span(
+                                Pattern::Tuple(vec![
+                                    span(Pattern::Binding("b"), 19..20),
+                                    span(
+                                        Pattern::Literal(Literal::Number(Rational::from(1))),
+                                        22..23
+                                    ),
+                                ]),
+                                18..24
+                            )
+                        ),
+                        (span("right", 26..31), span(Pattern::Binding("c"), 34..35)),
+                    ]),
+                    10..36
+                ),
+            ])
+        );
+        assert_eq!(
+            lower("a or b or c").0,
+            Pattern::Or(vec![
+                span(Pattern::Binding("a"), 0..1),
+                span(Pattern::Binding("b"), 5..6),
+                span(Pattern::Binding("c"), 10..11),
+            ])
+        );
+    }
+
+    #[test]
+    fn lower_invalid() {
+        assert_eq!(
+            lower("(f x, a + 1, [b])"),
+            (
+                Pattern::Tuple(vec![
+                    span(Pattern::Error, 1..4),
+                    span(Pattern::Error, 6..11),
+                    span(Pattern::Error, 13..16),
+                ]),
+                vec![
+                    ("expected a pattern, found a call".into(), 1..4),
+                    ("expected a pattern, found a `+` expression".into(), 6..11),
+                    ("expected a pattern, found a bracketed group".into(), 13..16),
+                ]
+            )
+        );
+        assert_eq!(
+            lower("(a, b = c)"),
+            (
+                Pattern::Error,
+                vec![(
+                    "a group cannot have both named and positional fields".into(),
+                    0..10
+                )]
+            )
+        );
+        assert_eq!(
+            lower("(x = a, x = b)").1,
+            [("field `x` is matched more than once".into(), 8..9)]
+        );
+    }
+
+    #[test]
+    fn alternatives() {
+        let mut diagnostics = Vec::new();
+        let pattern = Pattern::lower(
+            &parse_expression("(a or b, c or 1)").unwrap(),
+            &mut diagnostics,
+        );
+
+        assert_eq!(
+            Pattern::alternatives(&pattern),
+            [("a", 1..2), ("b", 6..7)]
+                .into_iter()
+                .flat_map(|(first, first_range)| {
+                    [
+                        span(Pattern::Binding("c"), 9..10),
+                        span(Pattern::Literal(Literal::Number(1.into())), 14..15),
+                    ]
+                    .map(|second| {
+                        span(
+                            Pattern::Tuple(vec![
+                                span(Pattern::Binding(first), first_range.clone()),
+                                second,
+                            ]),
+                            0..16,
+                        )
+                    })
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn check_patterns() {
+        let module = parse_module(include_str!("../../../../language-design/finite.vn")).unwrap();
+        assert!(super::super::check_patterns(&module).is_empty());
+
+        let module = parse_module("f = function x + 1 => match x\n    case f y => y").unwrap();
+        let messages = super::super::check_patterns(&module)
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            messages,
+            [
+                "expected a pattern, found a `+` expression",
+                "expected a pattern, found a call"
+            ]
+        );
+    }
+}
+
+mod Core {
+    use std::ops::Range;
+
+    use super::super::*;
+    use crate::parse_expression;
+
+    fn lower(input: &str) -> (String, Vec<(String, Range<u32>)>) {
+        let mut diagnostics = Vec::new();
+        let core = Core::lower(&parse_expression(input).unwrap(), &mut diagnostics);
+
+        let diagnostics = diagnostics
+            .into_iter()
+            .map(|diagnostic| {
+                let range = diagnostic.primary_range().unwrap();
+                (diagnostic.message, range)
+            })
+            .collect();
+
+        (print(&core.value), diagnostics)
+    }
+
+    fn print(core: &Core) -> String {
+        let list = |name: &str, items: &[parser_tools::Span<Core>]| {
+            let mut output = format!("({name}");
+
+            for item in items {
+                output += " ";
+                output += &print(&item.value);
+            }
+
+            output + ")"
+        };
+
+        match core {
+            Core::Error => "error".into(),
+            Core::Literal(literal) => literal.to_string(),
+            Core::Identifier(name) => name.to_string(),
+            Core::Tuple(items) => list("tuple", items),
+            Core::Record(fields) => format!(
+                "(record{})",
+                fields
+                    .iter()
+                    .map(|(name, value)| format!(" ({} {})", name.value, print(&value.value)))
+                    .collect::<String>()
+            ),
+            Core::Set(items) => list("set", items),
+            Core::List(items) => list("list", items),
+            Core::Block(items) => list("block", items),
+            Core::Definition { pattern, value } => format!(
+                "(= {} {})",
+                print_pattern(&pattern.value),
+                print(&value.value)
+            ),
+            Core::Unary { operation, operand } => {
+                format!("({operation:?} {})", print(&operand.value))
+            }
+            Core::Binary {
+                left,
+                operation,
+                right,
+            } => format!(
+                "({operation:?} {} {})",
+                print(&left.value),
+                print(&right.value)
+            ),
+            Core::Field { record, name } => format!("(. {} {})", print(&record.value), name.value),
+            Core::If {
+                condition,
+                then,
+                otherwise,
+            } => format!(
+                "(if {} {} {})",
+                print(&condition.value),
+                print(&then.value),
+                print(&otherwise.value)
+            ),
+            Core::Match { on, cases } => format!(
+                "(match {}{})",
+                print(&on.value),
+                cases
+                    .iter()
+                    .map(|case| format!(
+                        " (case {} {})",
+                        print_pattern(&case.value.pattern.value),
+                        print(&case.value.body.value)
+                    ))
+                    .collect::<String>()
+            ),
+            Core::Call { function, argument } => {
+                format!("({} {})", print(&function.value), print(&argument.value))
+            }
+            Core::Function {
+                parameter, body, ..
+            } => format!(
+                "(function {} {})",
+                print_pattern(&parameter.value),
+                print(&body.value)
+            ),
+            Core::Abstract { name } => format!("(abstract {})", name.value),
+            Core::Attributed {
+                attribute,
+                expression,
+            } => format!(
+                "(@{} {})",
+                attribute.value.name.value,
+                print(&expression.value)
+            ),
+        }
+    }
+
+    fn print_pattern(pattern: &Pattern) -> String {
+        match pattern {
+            Pattern::Error => "error".into(),
+            Pattern::Wildcard => "_".into(),
+            Pattern::Binding(name) => name.to_string(),
+            Pattern::Literal(literal) => literal.to_string(),
+            Pattern::Tuple(items) => format!(
+                "(tuple{})",
+                items
+                    .iter()
+                    .map(|item| format!(" {}", print_pattern(&item.value)))
+                    .collect::<String>()
+            ),
+            Pattern::Record(fields) => format!(
+                "(record{})",
+                fields
+                    .iter()
+                    .map(|(name, item)| format!(" ({} {})", name.value, print_pattern(&item.value)))
+                    .collect::<String>()
+            ),
+            Pattern::Or(alternatives) => format!(
+                "(or{})",
+                alternatives
+                    .iter()
+                    .map(|item| format!(" {}", print_pattern(&item.value)))
+                    .collect::<String>()
+            ),
+        }
+    }
+
+    #[test]
+    fn lower_if() {
+        assert_eq!(lower("if x then 1"), ("(if x 1 (tuple))".into(), vec![]));
+        assert_eq!(lower("if x then 1 else -2"), ("(if x 1 -2)".into(), vec![]));
+
+        let mut diagnostics = Vec::new();
+        let Core::If { otherwise, .. } =
+            Core::lower(&parse_expression("if x then 1").unwrap(), &mut diagnostics).value
+        else {
+            panic!("expected an if");
+        };
+        assert_eq!(otherwise.range, 11..11);
+    }
+
+    #[test]
+    fn lower_match() {
+        assert_eq!(
+            lower("match x case (1 or 2, a) => a case _ => 0"),
+            (
+                "(match x (case (tuple 1 a) a) (case (tuple 2 a) a) (case _ 0))".into(),
+                vec![]
+            )
+        );
+    }
+
+    #[test]
+    fn lower_function() {
+        assert_eq!(
+            lower("function (x, y) => x"),
+            ("(function (tuple x y) x)".into(), vec![])
+        );
+        assert_eq!(
+            lower("function (0 or 1) => x"),
+            (
+                format!("(function {ARGUMENT} (match {ARGUMENT} (case 0 x) (case 1 x)))"),
+                vec![]
+            )
+        );
+    }
+
+    #[test]
+    fn lower_groups() {
+        assert_eq!(lower("(x)"), ("x".into(), vec![]));
+        assert_eq!(
+            lower("(a, (b = 1))"),
+            ("(tuple a (record (b 1)))".into(), vec![])
+        );
+        assert_eq!(
+            lower("set {a.b.c, [1]}"),
+            ("(set (. (. a b) c) (list 1))".into(), vec![])
+        );
+        assert_eq!(lower("{ x = 1, x }"), ("(block (= x 1) x)".into(), vec![]));
+
+        let mut diagnostics = Vec::new();
+        let Core::Field { record, name } =
+            Core::lower(&parse_expression("a.b.c").unwrap(), &mut diagnostics).value
+        else {
+            panic!("expected a field");
+        };
+        assert_eq!((record.range, name.range), (0..3, 4..5));
+    }
+
+    #[test]
+    fn lower_invalid() {
+        assert_eq!(
+            lower("(a, b = 1)"),
+            (
+                "error".into(),
+                vec![(
+                    "a group cannot have both named and positional fields".into(),
+                    0..10
+                )]
+            )
+        );
+        assert_eq!(
+            lower("{ a or b = 1 }"),
+            (
+                "(block (= (or a b) 1))".into(),
+                vec![("a definition cannot have alternatives".into(), 2..8)]
+            )
+        );
+        assert_eq!(
+            lower("a.1"),
+            ("error".into(), vec![("expected a field name".into(), 2..3)])
+        );
+    }
+}
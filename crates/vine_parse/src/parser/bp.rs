@@ -1,24 +1,88 @@
+/// The precedence level of an operator. Higher levels bind tighter.
+///
+/// The built-in levels are ten apart, so that custom operators
+/// can be declared between them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Precedence(pub u8);
+
+impl Precedence {
+    pub const ASSIGNMENT: Self = Self(10);
+    pub const ARROW: Self = Self(20);
+    pub const OR: Self = Self(30);
+    pub const AND: Self = Self(40);
+    pub const EQUALITY: Self = Self(50);
+    pub const COMPARISON: Self = Self(60);
+    pub const RANGE: Self = Self(70);
+    pub const BITWISE_OR: Self = Self(80);
+    pub const BITWISE_XOR: Self = Self(90);
+    pub const BITWISE_AND: Self = Self(100);
+    pub const ADDITIVE: Self = Self(110);
+    pub const MULTIPLICATIVE: Self = Self(120);
+    pub const POWER: Self = Self(130);
+
+    /// Juxtaposition, like `f x`.
+    pub const CALL: Self = Self(140);
+
+    /// Prefix `-` and `!`.
+    pub const UNARY: Self = Self(150);
+    pub const ACCESS: Self = Self(160);
+    pub const PATH: Self = Self(170);
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Associativity {
+    /// `a + b + c` is `(a + b) + c`.
+    Left,
+
+    /// `a ** b ** c` is `a ** (b ** c)`.
+    Right,
+}
+
+/// How an infix operator binds.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Fixity {
+    pub precedence: Precedence,
+    pub associativity: Associativity,
+}
+
+impl Fixity {
+    pub const fn left(precedence: Precedence) -> Self {
+        Self {
+            precedence,
+            associativity: Associativity::Left,
+        }
+    }
+
+    pub const fn right(precedence: Precedence) -> Self {
+        Self {
+            precedence,
+            associativity: Associativity::Right,
+        }
+    }
+
+    /// Returns the left and right binding precedence of an operator with this fixity.
+    ///
+    /// The left one is compared against the minimum precedence to decide whether
+    /// the operator binds, and the right one is used to parse the right operand.
+    pub const fn binding_precedences(self) -> (BindingPrecedence, BindingPrecedence) {
+        let base = self.precedence.0 as u16 * 2;
+
+        match self.associativity {
+            Associativity::Left => (BindingPrecedence(base), BindingPrecedence(base + 1)),
+            Associativity::Right => (BindingPrecedence(base + 1), BindingPrecedence(base)),
+        }
+    }
+}
+
+/// The minimum binding precedence an operator must have to be parsed
+/// as part of the current expression. See [`BUILTIN_OPERATORS`](super::BUILTIN_OPERATORS)
+/// for the precedence table.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
-pub enum BindingPrecedence {
-    #[default]
-    Lowest,
-    AssignmentRight,
-    AssignmentLeft,
-    OrLeft,
-    OrRight,
-    AndLeft,
-    AndRight,
-    EqualityLeft,
-    EqualityRight,
-    ComparisonLeft,
-    ComparisonRight,
-    AdditiveLeft,
-    AdditiveRight,
-    MultiplicativeLeft,
-    MultiplicativeRight,
-    CallLeft,
-    CallRight,
-    Unary,
-    AccessLeft,
-    AccessRight,
+pub struct BindingPrecedence(u16);
+
+impl BindingPrecedence {
+    pub const LOWEST: Self = Self(0);
+    pub const CALL_LEFT: Self = Fixity::left(Precedence::CALL).binding_precedences().0;
+    pub const CALL_RIGHT: Self = Fixity::left(Precedence::CALL).binding_precedences().1;
+    pub const UNARY: Self = Self(Precedence::UNARY.0 as u16 * 2);
 }
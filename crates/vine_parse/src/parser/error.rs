@@ -1,10 +1,120 @@
+use std::ops::Range;
+
 use parser_tools::Span;
-use vine_lex::filter::FilteredToken;
+use vine_diagnostics::Diagnostic;
+use vine_lex::{
+    filter::{FilteredToken, FilteredTokenKind},
+    tokens::{EscapeErrorKind, MAX_EXPONENT, NumberError},
+};
 
 pub type Error<'source> = Box<ErrorInfo<'source>>;
 
 #[derive(Debug, Clone)]
-pub struct ErrorInfo<'source> {
-    pub found: Option<Span<FilteredToken<'source>>>,
-    pub expected: &'static str,
+pub enum ErrorInfo<'source> {
+    /// A token that cannot appear at this position (or the end of the input).
+    UnexpectedToken {
+        found: Option<Span<FilteredToken<'source>>>,
+        expected: &'static str,
+    },
+
+    /// A group that is not closed before the end of the input.
+    UnclosedDelimiter {
+        /// The range of the opening delimiter.
+        opening: Range<u32>,
+        expected: FilteredTokenKind<'source>,
+    },
+
+    /// An invalid token that looks like a number, such as `12abc` or `0xZZ`.
+    MalformedNumber { range: Range<u32> },
+
+    /// A well-formed number literal whose value cannot be represented.
+    InvalidNumber {
+        error: NumberError,
+        range: Range<u32>,
+    },
+
+    /// An `@infix` attribute whose arguments are not an associativity and a precedence.
+    MalformedFixity { range: Range<u32> },
+
+    /// An `@infix` attribute that is not attached to a definition of a name.
+    MisplacedFixity { range: Range<u32> },
+
+    /// An `@infix` attribute that is not at the start of a top-level item.
+    NestedFixity { range: Range<u32> },
+
+    /// A literal contains an escape sequence that cannot be decoded.
+    InvalidEscape {
+        kind: EscapeErrorKind,
+
+        /// The range of the escape sequence in the source.
+        range: Range<u32>,
+    },
+}
+
+impl ErrorInfo<'_> {
+    /// Converts the error into a [`Diagnostic`]. `end_of_input` is used
+    /// as the location for errors at the end of the input.
+    pub fn to_diagnostic(&self, end_of_input: u32) -> Diagnostic {
+        match self {
+            Self::UnexpectedToken {
+                found: Some(Span { value, range }),
+                expected,
+            } => Diagnostic::error(format!("expected {expected}, found `{}`", value.kind))
+                .with_primary(range.clone(), "unexpected token"),
+            Self::UnexpectedToken {
+                found: None,
+                expected,
+            } => Diagnostic::error(format!("expected {expected}, found the end of the input"))
+                .with_primary(end_of_input..end_of_input, "unexpected end of input"),
+            Self::UnclosedDelimiter { opening, expected } => {
+                Diagnostic::error(format!("unclosed delimiter; expected `{expected}`"))
+                    .with_primary(end_of_input..end_of_input, format!("expected `{expected}`"))
+                    .with_secondary(opening.clone(), "unclosed delimiter")
+            }
+            Self::MalformedNumber { range } => Diagnostic::error("malformed number literal")
+                .with_primary(range.clone(), "")
+                .with_help(
+                    "numbers are written like `1_000`, `0.5`, `6.02e23`, `0xFF`, `0o17` or `0b1010`",
+                ),
+            Self::InvalidNumber {
+                error: NumberError::ExponentTooLarge,
+                range,
+            } => Diagnostic::error("exponent is too large")
+                .with_primary(range.clone(), "")
+                .with_note(format!("exponents must be at most {MAX_EXPONENT}")),
+            Self::MalformedFixity { range } => Diagnostic::error("malformed fixity declaration")
+                .with_primary(range.clone(), "")
+                .with_help("write `@infix(left, <PRECEDENCE>)` or `@infix(right, <PRECEDENCE>)` with a precedence from 0 to 255"),
+            Self::MisplacedFixity { range } => {
+                Diagnostic::error("fixity declarations must be attached to a definition of a name")
+                    .with_primary(range.clone(), "")
+            }
+            Self::NestedFixity { range } => {
+                Diagnostic::error("fixity declarations are only allowed at module level")
+                    .with_primary(range.clone(), "")
+                    .with_note("the operator is declared for the whole module")
+            }
+            Self::InvalidEscape { kind, range } => {
+                let diagnostic = match kind {
+                    EscapeErrorKind::UnknownEscape(c) => {
+                        Diagnostic::error(format!("unknown escape sequence `\\{c}`"))
+                    }
+                    EscapeErrorKind::IncompleteEscape => {
+                        Diagnostic::error("incomplete escape sequence")
+                    }
+                    EscapeErrorKind::MalformedUnicodeEscape => {
+                        Diagnostic::error("malformed unicode escape sequence")
+                            .with_help("unicode escapes have one to six hexadecimal digits, like `\\u{1F600}`")
+                    }
+                    EscapeErrorKind::InvalidCodePoint(code_point) => Diagnostic::error(format!(
+                        "`{code_point:X}` is not a valid unicode scalar value"
+                    )),
+                };
+
+                diagnostic.with_primary(range.clone(), "").with_note(
+                    r#"valid escape sequences are `\n`, `\r`, `\t`, `\0`, `\\`, `\"`, `\'` and `\u{...}`"#,
+                )
+            }
+        }
+    }
 }
@@ -1,44 +1,87 @@
 mod bp;
 mod error;
+mod operators;
+#[cfg(test)]
+mod tests;
 
 use std::{iter::Peekable, ops::Range};
 
+pub use bp::*;
 pub use error::*;
+pub use operators::*;
 
 use parser_tools::Span;
+use vine_diagnostics::Diagnostic;
 use vine_lex::filter::{FilteredToken, FilteredTokenKind};
 
-use crate::{
-    ast::{BinaryOperation, Expression, GroupKind, MatchCase, UnaryOperation},
-    parser::bp::BindingPrecedence,
+use crate::ast::{
+    Attribute, BinaryOperation, Expression, GroupKind, MatchCase, Module, UnaryOperation,
 };
 
 pub struct Parser<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> {
     tokens: Peekable<Tokens>,
+
+    /// The end of the last consumed token.
+    end: u32,
+
+    /// The syntax errors encountered so far.
+    diagnostics: Vec<Diagnostic>,
+
+    /// The start of the primary range of the last reported error.
+    /// Used to suppress follow-up errors at the same location.
+    last_error_at: Option<u32>,
+
+    /// The infix operators, including custom operators declared so far.
+    operators: OperatorTable<'source>,
+
+    /// Whether the next expression starts a top-level item, where
+    /// fixity declarations are allowed.
+    at_item_start: bool,
 }
 
 macro_rules! bail {
     ($found:expr, $expected:literal) => {
-        return Err(Box::new(ErrorInfo {
+        return Err(Box::new(ErrorInfo::UnexpectedToken {
             found: $found,
             expected: $expected,
         }))
     };
 }
 
+/// Determines whether the parser can resynchronize on the token after an error
+/// without consuming it, because an enclosing construct expects it.
+fn token_kind_is_synchronizing(token: &FilteredTokenKind) -> bool {
+    matches!(
+        token,
+        FilteredTokenKind::Comma
+            | FilteredTokenKind::ClosingParenthesis
+            | FilteredTokenKind::ClosingBracket
+            | FilteredTokenKind::ClosingBrace
+            | FilteredTokenKind::Case
+            | FilteredTokenKind::Then
+            | FilteredTokenKind::Else
+            | FilteredTokenKind::EqualsGreaterThan
+            | FilteredTokenKind::Is
+            | FilteredTokenKind::In
+    )
+}
+
 /// Determines whether a token can start an expression.
 fn token_kind_can_start_expression(token: &FilteredTokenKind) -> bool {
     matches!(
         token,
         FilteredTokenKind::Number(_)
+            | FilteredTokenKind::String(_)
+            | FilteredTokenKind::Character(_)
             | FilteredTokenKind::Function
             | FilteredTokenKind::If
             | FilteredTokenKind::Identifier(_)
             | FilteredTokenKind::OpeningBracket
             | FilteredTokenKind::OpeningBrace
             | FilteredTokenKind::OpeningParenthesis
-            | FilteredTokenKind::Ampersand
             | FilteredTokenKind::Match
+            | FilteredTokenKind::At
+            | FilteredTokenKind::Abstract
     )
 }
 
@@ -46,205 +89,302 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
     pub fn new(tokens: Tokens) -> Self {
         Self {
             tokens: tokens.peekable(),
+            end: 0,
+            diagnostics: Vec::new(),
+            last_error_at: None,
+            operators: OperatorTable::new(),
+            at_item_start: false,
+        }
+    }
+
+    /// Creates a parser that knows the custom operators in `operators`.
+    pub fn with_operators(tokens: Tokens, operators: OperatorTable<'source>) -> Self {
+        Self {
+            operators,
+            ..Self::new(tokens)
+        }
+    }
+
+    pub fn operators(&self) -> &OperatorTable<'source> {
+        &self.operators
+    }
+
+    pub fn into_operators(self) -> OperatorTable<'source> {
+        self.operators
+    }
+
+    /// Returns the syntax errors encountered so far.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.diagnostics
+    }
+
+    /// Parses an expression, ensuring that there are no more tokens after the expression.
+    ///
+    /// Syntax errors are recorded in [`Parser::diagnostics`]; the parts of
+    /// the input that could not be parsed become [`Expression::Error`].
+    pub fn parse_root_expression(&mut self) -> Span<Expression<'source>> {
+        let expression = self.parse_expression_or_recover(false);
+
+        if let Some(token) = self.next_token() {
+            self.report(ErrorInfo::UnexpectedToken {
+                found: Some(token),
+                expected: "the end of the input",
+            });
+
+            while self.next_token().is_some() {}
         }
+
+        expression
     }
 
-    pub fn parse_root_expression(&mut self) -> Result<Span<Expression<'source>>, Error<'source>> {
-        let expression = self.parse_expression(BindingPrecedence::Lowest, false)?;
+    /// Parses a sequence of top-level items, each of which must start on a new line.
+    ///
+    /// Syntax errors are recorded in [`Parser::diagnostics`]; the rest of
+    /// a line that could not be parsed is skipped.
+    ///
+    /// Fixity declarations (`@infix(...)`) apply to all items after the declaration.
+    pub fn parse_module(&mut self) -> Module<'source> {
+        let mut items = Vec::new();
+
+        while let Some(token) = self.tokens.peek() {
+            let token = token.clone();
 
-        match self.tokens.next() {
-            None => Ok(expression),
-            token => bail!(token, "no token"),
+            if !items.is_empty() && !token.value.line_break_before {
+                self.report(ErrorInfo::UnexpectedToken {
+                    found: Some(token),
+                    expected: "a line break",
+                });
+                self.skip_line();
+            } else if token_kind_is_synchronizing(&token.value.kind) {
+                self.report(ErrorInfo::UnexpectedToken {
+                    found: Some(token),
+                    expected: "an expression",
+                });
+                self.skip_line();
+            } else {
+                self.at_item_start = true;
+                let item = self.parse_expression_or_recover(true);
+                self.declare_fixities(&item);
+                items.push(item);
+            }
         }
+
+        Module { items }
     }
 
-    fn parse_expression(
+    fn next_token(&mut self) -> Option<Span<FilteredToken<'source>>> {
+        let token = self.tokens.next();
+
+        if let Some(token) = &token {
+            self.end = token.range.end;
+        }
+
+        token
+    }
+
+    /// Returns the start of the next token, or the end of the input.
+    fn peek_start(&mut self) -> u32 {
+        match self.tokens.peek() {
+            Some(token) => token.range.start,
+            None => self.end,
+        }
+    }
+
+    fn report(&mut self, error: ErrorInfo<'source>) {
+        let diagnostic = error.to_diagnostic(self.end);
+        let at = diagnostic.primary_range().map(|range| range.start);
+
+        if at.is_none() || at != self.last_error_at {
+            self.last_error_at = at;
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Skips tokens until a synchronization point: a `,`, a token preceded by a line break,
+    /// a closing delimiter that does not close a skipped group, or, if `stop_at_case`
+    /// is set, a `case`. The synchronization token itself is not consumed.
+    ///
+    /// Returns the end of the last consumed token.
+    fn synchronize(&mut self, stop_at_case: bool) -> u32 {
+        let mut depth = 0_usize;
+
+        while let Some(Span {
+            value:
+                FilteredToken {
+                    kind,
+                    line_break_before,
+                },
+            ..
+        }) = self.tokens.peek()
+        {
+            match kind {
+                FilteredTokenKind::OpeningParenthesis
+                | FilteredTokenKind::OpeningBracket
+                | FilteredTokenKind::OpeningBrace => depth += 1,
+                FilteredTokenKind::ClosingParenthesis
+                | FilteredTokenKind::ClosingBracket
+                | FilteredTokenKind::ClosingBrace => match depth.checked_sub(1) {
+                    Some(lower_depth) => depth = lower_depth,
+                    None => break,
+                },
+                _ if depth > 0 => {}
+                FilteredTokenKind::Comma => break,
+                FilteredTokenKind::Case if stop_at_case => break,
+                _ if *line_break_before => break,
+                _ => {}
+            }
+
+            self.next_token();
+        }
+
+        self.end
+    }
+
+    /// Declares the fixities of all `@infix(<ASSOCIATIVITY>, <PRECEDENCE>)`
+    /// attributes on a definition of a name.
+    fn declare_fixities(&mut self, item: &Span<Expression<'source>>) {
+        let name = match &Expression::unattributed(item).value {
+            Expression::Binary {
+                left,
+                operation: BinaryOperation::Definition,
+                ..
+            } => match left.value {
+                Expression::Identifier(name) => Some(name),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        for attribute in
+            Expression::attributes(item).filter(|attribute| attribute.value.name.value == "infix")
+        {
+            let Some(name) = name else {
+                self.report(ErrorInfo::MisplacedFixity {
+                    range: attribute.range.clone(),
+                });
+                continue;
+            };
+
+            match Self::parse_fixity(attribute) {
+                Some(fixity) => {
+                    self.operators.declare(name, fixity);
+                }
+                None => self.report(ErrorInfo::MalformedFixity {
+                    range: attribute.range.clone(),
+                }),
+            }
+        }
+    }
+
+    fn parse_fixity(attribute: &Span<Attribute>) -> Option<Fixity> {
+        let [associativity, precedence] = &attribute.value.arguments.as_ref()?.value[..] else {
+            return None;
+        };
+
+        let associativity = match associativity.value {
+            Expression::Identifier("left") => Associativity::Left,
+            Expression::Identifier("right") => Associativity::Right,
+            _ => return None,
+        };
+
+        let Expression::Number(precedence) = &precedence.value else {
+            return None;
+        };
+
+        if precedence.is_negative() || !precedence.is_integer() {
+            return None;
+        }
+
+        Some(Fixity {
+            precedence: Precedence(precedence.numerator().to_u64()?.try_into().ok()?),
+            associativity,
+        })
+    }
+
+    /// Skips the next token and all following tokens up to the next line break.
+    fn skip_line(&mut self) {
+        self.next_token();
+
+        while let Some(Span {
+            value:
+                FilteredToken {
+                    line_break_before: false,
+                    ..
+                },
+            ..
+        }) = self.tokens.peek()
+        {
+            self.next_token();
+        }
+    }
+
+    /// Parses an expression. On error, the error is reported, the parser
+    /// synchronizes and an [`Expression::Error`] is returned.
+    fn parse_expression_or_recover(
         &mut self,
-        min_bp: BindingPrecedence,
         line_break_as_delimiter: bool,
-    ) -> Result<Span<Expression<'source>>, Error<'source>> {
-        let mut left = self.parse_expression_start(line_break_as_delimiter)?;
-
-        macro_rules! binary_operator {
-            ($bp_right:expr, $operation:expr) => {{
-                self.tokens.next();
+    ) -> Span<Expression<'source>> {
+        let start = self.peek_start();
 
-                let right = self.parse_expression($bp_right, line_break_as_delimiter)?;
-                let range = left.range.start..right.range.end;
+        match self.parse_expression(BindingPrecedence::LOWEST, line_break_as_delimiter) {
+            Ok(expression) => expression,
+            Err(error) => {
+                self.report(*error);
+                let end = self.synchronize(false).max(start);
 
                 Span {
-                    value: Expression::Binary {
-                        left: Box::new(left),
-                        operation: $operation,
-                        right: Box::new(right),
-                    },
-                    range,
+                    value: Expression::Error,
+                    range: start..end,
                 }
-            }};
+            }
         }
+    }
+
+    fn parse_expression(
+        &mut self,
+        min_bp: BindingPrecedence,
+        line_break_as_delimiter: bool,
+    ) -> Result<Span<Expression<'source>>, Error<'source>> {
+        let mut left = self.parse_expression_start(line_break_as_delimiter)?;
 
         loop {
             left = match self.tokens.peek() {
                 Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::EqualsEquals,
-                            ..
-                        },
-                    ..
-                }) if min_bp <= BindingPrecedence::EqualityLeft => {
-                    binary_operator!(BindingPrecedence::EqualityRight, BinaryOperation::Equal)
-                }
-                Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::ExclamationMarkEquals,
-                            ..
-                        },
-                    ..
-                }) if min_bp <= BindingPrecedence::EqualityLeft => {
-                    binary_operator!(BindingPrecedence::EqualityRight, BinaryOperation::NotEqual)
-                }
-                Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::LessThan,
-                            ..
-                        },
-                    ..
-                }) if min_bp <= BindingPrecedence::ComparisonLeft => {
-                    binary_operator!(
-                        BindingPrecedence::ComparisonRight,
-                        BinaryOperation::LessThan
-                    )
-                }
-                Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::LessThanEquals,
-                            ..
-                        },
-                    ..
-                }) if min_bp <= BindingPrecedence::ComparisonLeft => {
-                    binary_operator!(
-                        BindingPrecedence::ComparisonRight,
-                        BinaryOperation::LessThanOrEqual
-                    )
-                }
-                Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::GreaterThan,
-                            ..
-                        },
-                    ..
-                }) if min_bp <= BindingPrecedence::ComparisonLeft => {
-                    binary_operator!(
-                        BindingPrecedence::ComparisonRight,
-                        BinaryOperation::GreaterThan
-                    )
-                }
-                Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::GreaterThanEquals,
-                            ..
-                        },
-                    ..
-                }) if min_bp <= BindingPrecedence::ComparisonLeft => {
-                    binary_operator!(
-                        BindingPrecedence::ComparisonRight,
-                        BinaryOperation::GreaterThanOrEqual
-                    )
-                }
-                Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::Equals,
-                            ..
-                        },
-                    ..
-                }) if min_bp <= BindingPrecedence::AssignmentLeft => {
-                    binary_operator!(
-                        BindingPrecedence::AssignmentRight,
-                        BinaryOperation::Definition
-                    )
-                }
-                Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::Period,
-                            ..
-                        },
-                    ..
-                }) if min_bp <= BindingPrecedence::AccessLeft => {
-                    binary_operator!(BindingPrecedence::AccessRight, BinaryOperation::Access)
-                }
-                Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::Plus,
-                            ..
-                        },
-                    ..
-                }) if min_bp <= BindingPrecedence::AdditiveLeft => {
-                    binary_operator!(BindingPrecedence::AdditiveRight, BinaryOperation::Add)
-                }
-                Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::Minus,
-                            ..
-                        },
-                    ..
-                }) if min_bp <= BindingPrecedence::AdditiveLeft => {
-                    binary_operator!(BindingPrecedence::AdditiveRight, BinaryOperation::Subtract)
-                }
-                Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::Star,
-                            ..
-                        },
-                    ..
-                }) if min_bp <= BindingPrecedence::MultiplicativeLeft => {
-                    binary_operator!(
-                        BindingPrecedence::MultiplicativeRight,
-                        BinaryOperation::Multiply
-                    )
-                }
-                Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::Slash,
-                            ..
-                        },
-                    ..
-                }) if min_bp <= BindingPrecedence::MultiplicativeLeft => {
-                    binary_operator!(
-                        BindingPrecedence::MultiplicativeRight,
-                        BinaryOperation::Divide
-                    )
-                }
-                Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::Or,
-                            ..
-                        },
-                    ..
-                }) if min_bp <= BindingPrecedence::OrLeft => {
-                    binary_operator!(BindingPrecedence::OrRight, BinaryOperation::Or)
-                }
-                Some(Span {
-                    value:
-                        FilteredToken {
-                            kind: FilteredTokenKind::And,
-                            ..
+                    value: FilteredToken { kind, .. },
+                    range,
+                }) if let Some((fixity, operator)) = self.operators.infix(kind)
+                    && min_bp <= fixity.binding_precedences().0 =>
+                {
+                    let operator_range = range.clone();
+                    self.next_token();
+
+                    let right = self.parse_expression(
+                        fixity.binding_precedences().1,
+                        line_break_as_delimiter,
+                    )?;
+
+                    Span {
+                        range: left.range.start..right.range.end,
+                        value: match operator {
+                            InfixOperator::Builtin(operation) => Expression::Binary {
+                                left: Box::new(left),
+                                operation,
+                                right: Box::new(right),
+                            },
+                            InfixOperator::Custom(name) => Expression::Infix {
+                                left: Box::new(left),
+                                operator: Span {
+                                    value: name,
+                                    range: operator_range,
+                                },
+                                right: Box::new(right),
+                            },
                         },
-                    ..
-                }) if min_bp <= BindingPrecedence::AndLeft => {
-                    binary_operator!(BindingPrecedence::AndRight, BinaryOperation::And)
+                    }
                 }
                 Some(Span {
                     value:
@@ -252,16 +392,15 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                             kind,
                             line_break_before,
                         },
-                    range,
-                }) if min_bp <= BindingPrecedence::CallLeft
+                    ..
+                }) if min_bp <= BindingPrecedence::CALL_LEFT
                     && (!*line_break_before || !line_break_as_delimiter)
                     && token_kind_can_start_expression(kind) =>
                 {
-                    let start = range.start;
-                    let argument = self.parse_expression(BindingPrecedence::CallRight, false)?;
+                    let argument = self.parse_expression(BindingPrecedence::CALL_RIGHT, false)?;
 
                     Span {
-                        range: start..argument.range.end,
+                        range: left.range.start..argument.range.end,
                         value: Expression::Call {
                             function: Box::new(left),
                             argument: Box::new(argument),
@@ -276,12 +415,44 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
     }
 
     /// Parses a [`MatchCase`]. Expects `peek` to yield the first token of the pattern.
+    ///
+    /// On error, the error is reported and the parser synchronizes on the next `case`.
     fn parse_match_case(
         &mut self,
         start: u32,
         line_break_as_delimiter: bool,
+    ) -> Span<MatchCase<'source>> {
+        match self.try_parse_match_case(start, line_break_as_delimiter) {
+            Ok(case) => case,
+            Err(error) => {
+                self.report(*error);
+                let end = self.synchronize(true).max(start);
+
+                // The skipped tokens are taken as the pattern, and the body is missing.
+                Span {
+                    value: MatchCase {
+                        pattern: Box::new(Span {
+                            value: Expression::Error,
+                            range: start..end,
+                        }),
+                        domain: None,
+                        maps_to: Box::new(Span {
+                            value: Expression::Error,
+                            range: end..end,
+                        }),
+                    },
+                    range: start..end,
+                }
+            }
+        }
+    }
+
+    fn try_parse_match_case(
+        &mut self,
+        start: u32,
+        line_break_as_delimiter: bool,
     ) -> Result<Span<MatchCase<'source>>, Error<'source>> {
-        let pattern = self.parse_expression(BindingPrecedence::Lowest, false)?;
+        let pattern = self.parse_expression(BindingPrecedence::LOWEST, false)?;
 
         let domain = match self.tokens.peek() {
             Some(Span {
@@ -292,14 +463,15 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                     },
                 ..
             }) => {
-                self.tokens.next();
+                self.next_token();
 
-                Some(self.parse_expression(BindingPrecedence::Lowest, false)?)
+                Some(self.parse_expression(BindingPrecedence::LOWEST, false)?)
             }
             _ => None,
         };
 
-        match self.tokens.next() {
+        // A missing `=>` leaves the token, which may start the next case.
+        match self.tokens.peek() {
             Some(Span {
                 value:
                     FilteredToken {
@@ -307,12 +479,14 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                         ..
                     },
                 ..
-            }) => {}
-            token => bail!(token, "'is', 'in', or '=>'"),
+            }) => {
+                self.next_token();
+            }
+            token => bail!(token.cloned(), "`is`, `in` or `=>`"),
         }
 
         let case_to_expression =
-            self.parse_expression(BindingPrecedence::Lowest, line_break_as_delimiter)?;
+            self.parse_expression(BindingPrecedence::LOWEST, line_break_as_delimiter)?;
 
         Ok(Span {
             range: start..case_to_expression.range.end,
@@ -324,31 +498,90 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
         })
     }
 
+    /// Parses the elements of a group up to and including `terminate_on`.
+    /// `start` is the start of the whole group and `opening` the range of its
+    /// opening delimiter.
+    ///
+    /// Elements that fail to parse are reported and replaced by [`Expression::Error`].
     fn parse_grouped(
         &mut self,
-        terminate_on: FilteredTokenKind,
+        terminate_on: FilteredTokenKind<'source>,
         start: u32,
-    ) -> Result<Span<Vec<Span<Expression<'source>>>>, Error<'source>> {
+        opening: Range<u32>,
+    ) -> Span<Vec<Span<Expression<'source>>>> {
         let mut expressions = Vec::new();
 
+        let (expected_closing, expected_separator) = match terminate_on {
+            FilteredTokenKind::ClosingParenthesis => ("`)`", "a line break, `,` or `)`"),
+            FilteredTokenKind::ClosingBracket => ("`]`", "a line break, `,` or `]`"),
+            _ => ("`}`", "a line break, `,` or `}`"),
+        };
+
         loop {
             match self.tokens.peek() {
                 Some(Span {
                     value: FilteredToken { kind, .. },
-                    range: Range { end, .. },
+                    ..
                 }) if kind == &terminate_on => {
-                    let end = *end;
-                    self.tokens.next();
+                    self.next_token();
 
-                    break Ok(Span {
+                    break Span {
                         value: expressions,
-                        range: start..end,
+                        range: start..self.end,
+                    };
+                }
+                Some(Span {
+                    value:
+                        FilteredToken {
+                            kind:
+                                FilteredTokenKind::ClosingParenthesis
+                                | FilteredTokenKind::ClosingBracket
+                                | FilteredTokenKind::ClosingBrace,
+                            ..
+                        },
+                    ..
+                }) => {
+                    // A closing delimiter that does not match; skip it.
+                    let token = self.next_token();
+
+                    self.report(ErrorInfo::UnexpectedToken {
+                        found: token,
+                        expected: expected_closing,
+                    });
+
+                    continue;
+                }
+                None => {
+                    self.report(ErrorInfo::UnclosedDelimiter {
+                        opening,
+                        expected: terminate_on,
                     });
+
+                    break Span {
+                        value: expressions,
+                        range: start..self.end,
+                    };
                 }
                 _ => {}
             }
 
-            expressions.push(self.parse_expression(BindingPrecedence::Lowest, true)?);
+            let end = self.end;
+            let mut expression = self.parse_expression_or_recover(true);
+
+            // A token that cannot start an expression, like `then` after a line break,
+            // is reported but left to an enclosing construct. Nothing in a group
+            // expects it, so it is skipped as part of the error.
+            if self.end == end
+                && let Some(token) = self.tokens.peek()
+                && token.value.kind != FilteredTokenKind::Comma
+            {
+                expression.range.end = token.range.end;
+                self.next_token();
+                expressions.push(expression);
+                continue;
+            }
+
+            expressions.push(expression);
 
             match self.tokens.peek() {
                 Some(Span {
@@ -359,7 +592,7 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                         },
                     ..
                 }) => {
-                    self.tokens.next();
+                    self.next_token();
                 }
                 Some(Span {
                     value:
@@ -368,13 +601,32 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                             ..
                         },
                     ..
-                }) => {}
+                })
+                | None => {}
                 Some(Span {
                     value: FilteredToken { kind, .. },
                     ..
                 }) if kind == &terminate_on => {}
                 token => {
-                    bail!(token.cloned(), "a line break, ',', or ')'")
+                    let token = token.cloned();
+
+                    self.report(ErrorInfo::UnexpectedToken {
+                        found: token,
+                        expected: expected_separator,
+                    });
+                    self.synchronize(false);
+
+                    if let Some(Span {
+                        value:
+                            FilteredToken {
+                                kind: FilteredTokenKind::Comma,
+                                ..
+                            },
+                        ..
+                    }) = self.tokens.peek()
+                    {
+                        self.next_token();
+                    }
                 }
             }
         }
@@ -384,7 +636,31 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
         &mut self,
         line_break_as_delimiter: bool,
     ) -> Result<Span<Expression<'source>>, Error<'source>> {
-        Ok(match self.tokens.next() {
+        let at_item_start = std::mem::take(&mut self.at_item_start);
+
+        match self.tokens.peek() {
+            Some(Span {
+                value: FilteredToken { kind, .. },
+                ..
+            }) if !token_kind_is_synchronizing(kind) => {}
+            token => {
+                // Leave the token to the enclosing construct that expects it.
+                let token = token.cloned();
+                let start = self.peek_start();
+
+                self.report(ErrorInfo::UnexpectedToken {
+                    found: token,
+                    expected: "an expression",
+                });
+
+                return Ok(Span {
+                    value: Expression::Error,
+                    range: start..start,
+                });
+            }
+        }
+
+        Ok(match self.next_token() {
             Some(Span {
                 value:
                     FilteredToken {
@@ -393,9 +669,9 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                     },
                 range: Range { start, .. },
             }) => {
-                let condition = self.parse_expression(BindingPrecedence::Lowest, false)?;
+                let condition = self.parse_expression(BindingPrecedence::LOWEST, false)?;
 
-                match self.tokens.next() {
+                match self.next_token() {
                     Some(Span {
                         value:
                             FilteredToken {
@@ -404,11 +680,11 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                             },
                         ..
                     }) => {}
-                    token => bail!(token, "'then'"),
+                    token => bail!(token, "`then`"),
                 }
 
                 let then =
-                    self.parse_expression(BindingPrecedence::Lowest, line_break_as_delimiter)?;
+                    self.parse_expression(BindingPrecedence::LOWEST, line_break_as_delimiter)?;
 
                 let otherwise =
                     match self.tokens.peek() {
@@ -420,10 +696,10 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                                 },
                             ..
                         }) => {
-                            self.tokens.next();
+                            self.next_token();
 
                             Some(self.parse_expression(
-                                BindingPrecedence::Lowest,
+                                BindingPrecedence::LOWEST,
                                 line_break_as_delimiter,
                             )?)
                         }
@@ -446,13 +722,91 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
             Some(Span {
                 value:
                     FilteredToken {
-                        kind: FilteredTokenKind::Number(n),
+                        kind: FilteredTokenKind::Number(source),
                         ..
                     },
                 range,
-            }) => Span {
-                value: Expression::Number(n),
+            }) => match source.parse() {
+                Ok(number) => Span {
+                    value: Expression::Number(number),
+                    range,
+                },
+                Err(error) => {
+                    self.report(ErrorInfo::InvalidNumber {
+                        error,
+                        range: range.clone(),
+                    });
+
+                    Span {
+                        value: Expression::Error,
+                        range,
+                    }
+                }
+            },
+            Some(Span {
+                value:
+                    FilteredToken {
+                        kind: FilteredTokenKind::Invalid(invalid),
+                        ..
+                    },
                 range,
+            }) if invalid.starts_with(|c: char| c.is_ascii_digit()) => {
+                self.report(ErrorInfo::MalformedNumber {
+                    range: range.clone(),
+                });
+
+                Span {
+                    value: Expression::Error,
+                    range,
+                }
+            }
+            Some(Span {
+                value:
+                    FilteredToken {
+                        kind: FilteredTokenKind::String(source),
+                        ..
+                    },
+                range,
+            }) => match source.parse() {
+                Ok(string) => Span {
+                    value: Expression::String(string),
+                    range,
+                },
+                Err(error) => {
+                    self.report(ErrorInfo::InvalidEscape {
+                        kind: error.kind,
+                        range: range.start + error.range.start..range.start + error.range.end,
+                    });
+
+                    Span {
+                        value: Expression::Error,
+                        range,
+                    }
+                }
+            },
+            Some(Span {
+                value:
+                    FilteredToken {
+                        kind: FilteredTokenKind::Character(source),
+                        ..
+                    },
+                range,
+            }) => match source.parse() {
+                Ok(character) => Span {
+                    value: Expression::Character(character),
+                    range,
+                },
+                Err(error) => {
+                    self.report(ErrorInfo::InvalidEscape {
+                        kind: error.kind,
+                        range: range.start + error.range.start..range.start + error.range.end,
+                    });
+
+                    Span {
+                        value: Expression::Error,
+                        range,
+                    }
+                }
             },
             Some(Span {
                 value:
@@ -463,7 +817,7 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                 range: Range { start, .. },
             }) => {
                 let inner =
-                    self.parse_expression(BindingPrecedence::Unary, line_break_as_delimiter)?;
+                    self.parse_expression(BindingPrecedence::UNARY, line_break_as_delimiter)?;
 
                 Span {
                     range: start..inner.range.end,
@@ -482,7 +836,7 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                 range: Range { start, .. },
             }) => {
                 let inner =
-                    self.parse_expression(BindingPrecedence::Unary, line_break_as_delimiter)?;
+                    self.parse_expression(BindingPrecedence::UNARY, line_break_as_delimiter)?;
 
                 Span {
                     range: start..inner.range.end,
@@ -512,9 +866,9 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                 range: Range { start, .. },
             }) => {
                 let expression_to_match_on =
-                    self.parse_expression(BindingPrecedence::Lowest, false)?;
+                    self.parse_expression(BindingPrecedence::LOWEST, false)?;
 
-                let case_start_index = match self.tokens.next() {
+                let case_start_index = match self.next_token() {
                     Some(Span {
                         value:
                             FilteredToken {
@@ -529,27 +883,23 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                     ),
                 };
 
-                let first_case =
-                    self.parse_match_case(case_start_index, line_break_as_delimiter)?;
+                let first_case = self.parse_match_case(case_start_index, line_break_as_delimiter);
 
                 let mut other_cases = Vec::new();
 
-                loop {
-                    let case_start = match self.tokens.peek() {
-                        Some(Span {
-                            value:
-                                FilteredToken {
-                                    kind: FilteredTokenKind::Case,
-                                    ..
-                                },
-                            range,
-                        }) => range.start,
-                        _ => break,
-                    };
-
-                    self.tokens.next();
+                while let Some(Span {
+                    value:
+                        FilteredToken {
+                            kind: FilteredTokenKind::Case,
+                            ..
+                        },
+                    range,
+                }) = self.tokens.peek()
+                {
+                    let case_start = range.start;
+                    self.next_token();
 
-                    other_cases.push(self.parse_match_case(case_start, line_break_as_delimiter)?);
+                    other_cases.push(self.parse_match_case(case_start, line_break_as_delimiter));
                 }
 
                 Span {
@@ -569,9 +919,9 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                     },
                 range,
             }) => {
-                let parameter_pattern = self.parse_expression(BindingPrecedence::Lowest, false)?;
+                let parameter_pattern = self.parse_expression(BindingPrecedence::LOWEST, false)?;
 
-                match self.tokens.next() {
+                let domain = match self.tokens.peek() {
                     Some(Span {
                         value:
                             FilteredToken {
@@ -579,13 +929,15 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                                 ..
                             },
                         ..
-                    }) => {}
-                    token => bail!(token, "'is' or 'in'"),
-                }
+                    }) => {
+                        self.next_token();
 
-                let domain = self.parse_expression(BindingPrecedence::Lowest, false)?;
+                        Some(self.parse_expression(BindingPrecedence::LOWEST, false)?)
+                    }
+                    _ => None,
+                };
 
-                match self.tokens.next() {
+                match self.next_token() {
                     Some(Span {
                         value:
                             FilteredToken {
@@ -601,21 +953,115 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                                 ..
                             },
                         ..
-                    }) => bail!(token, "'=>'. functions don't use 'then' they use '=>'"),
-                    token => bail!(token, "'=>'"),
+                    }) => bail!(token, "`=>`; functions don't use `then`, they use `=>`"),
+                    token if domain.is_none() => bail!(token, "`is`, `in` or `=>`"),
+                    token => bail!(token, "`=>`"),
                 }
 
-                let body = self.parse_expression(BindingPrecedence::Lowest, false)?;
+                let body =
+                    self.parse_expression(BindingPrecedence::LOWEST, line_break_as_delimiter)?;
 
                 Span {
                     range: range.start..body.range.end,
                     value: Expression::Function {
                         parameter_pattern: Box::new(parameter_pattern),
-                        parameter_domain: Box::new(domain),
+                        parameter_domain: domain.map(Box::new),
                         body: Box::new(body),
                     },
                 }
             }
+            Some(Span {
+                value:
+                    FilteredToken {
+                        kind: FilteredTokenKind::Abstract,
+                        ..
+                    },
+                range: Range { start, .. },
+            }) => match self.next_token() {
+                Some(Span {
+                    value:
+                        FilteredToken {
+                            kind: FilteredTokenKind::Identifier(name),
+                            ..
+                        },
+                    range,
+                }) => Span {
+                    range: start..range.end,
+                    value: Expression::Abstract {
+                        name: Span { value: name, range },
+                    },
+                },
+                token => bail!(token, "a name for the abstract value"),
+            },
+            Some(Span {
+                value:
+                    FilteredToken {
+                        kind: FilteredTokenKind::At,
+                        ..
+                    },
+                range: Range { start, .. },
+            }) => {
+                let name = match self.next_token() {
+                    Some(Span {
+                        value:
+                            FilteredToken {
+                                kind: FilteredTokenKind::Identifier(name),
+                                ..
+                            },
+                        range,
+                    }) => Span { value: name, range },
+                    token => bail!(token, "an attribute name"),
+                };
+
+                let arguments = match self.tokens.peek() {
+                    Some(Span {
+                        value:
+                            FilteredToken {
+                                kind: FilteredTokenKind::OpeningParenthesis,
+                                ..
+                            },
+                        range,
+                    }) if range.start == name.range.end => {
+                        let opening = range.clone();
+                        self.next_token();
+
+                        Some(self.parse_grouped(
+                            FilteredTokenKind::ClosingParenthesis,
+                            opening.start,
+                            opening,
+                        ))
+                    }
+                    _ => None,
+                };
+
+                let attribute = Span {
+                    range: start
+                        ..arguments
+                            .as_ref()
+                            .map_or(name.range.end, |arguments| arguments.range.end),
+                    value: Attribute { name, arguments },
+                };
+
+                if attribute.value.name.value == "infix" && !at_item_start {
+                    self.report(ErrorInfo::NestedFixity {
+                        range: attribute.range.clone(),
+                    });
+                }
+
+                // Further attributes still belong to the start of the item.
+                self.at_item_start = at_item_start;
+
+                let expression =
+                    self.parse_expression(BindingPrecedence::LOWEST, line_break_as_delimiter)?;
+
+                Span {
+                    range: start..expression.range.end,
+                    value: Expression::Attributed {
+                        attribute,
+                        expression: Box::new(expression),
+                    },
+                }
+            }
             Some(Span {
                 value:
                     FilteredToken {
@@ -624,20 +1070,20 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                     },
                 range: Range { start, .. },
             }) => {
-                match self.tokens.next() {
+                let opening = match self.next_token() {
                     Some(Span {
                         value:
                             FilteredToken {
                                 kind: FilteredTokenKind::OpeningBrace,
                                 ..
                             },
-                        ..
-                    }) => {}
-                    token => bail!(token, "'{'"),
-                }
+                        range: opening,
+                    }) => opening,
+                    token => bail!(token, "`{`"),
+                };
 
                 let Span { value, range } =
-                    self.parse_grouped(FilteredTokenKind::ClosingBrace, start)?;
+                    self.parse_grouped(FilteredTokenKind::ClosingBrace, start, opening);
 
                 Span {
                     value: Expression::Grouped {
@@ -656,7 +1102,7 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                             | FilteredTokenKind::OpeningBrace),
                         ..
                     },
-                range: Range { start, .. },
+                range: opening_range,
             }) => {
                 let (closing, kind) = match opening {
                     FilteredTokenKind::OpeningParenthesis => (
@@ -672,7 +1118,8 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                     _ => unreachable!(),
                 };
 
-                let Span { value, range } = self.parse_grouped(closing, start)?;
+                let Span { value, range } =
+                    self.parse_grouped(closing, opening_range.start, opening_range);
 
                 Span {
                     value: Expression::Grouped {
@@ -682,10 +1129,21 @@ impl<'source, Tokens: Iterator<Item = Span<FilteredToken<'source>>>> Parser<'sou
                     range,
                 }
             }
-            token => bail!(
-                token,
-                "'(', '!', '-', a number, a string, a character, '{', 'function', or '('"
-            ),
+            token => {
+                let range = token
+                    .as_ref()
+                    .map_or(self.end..self.end, |token| token.range.clone());
+
+                self.report(ErrorInfo::UnexpectedToken {
+                    found: token,
+                    expected: "an expression",
+                });
+
+                Span {
+                    value: Expression::Error,
+                    range,
+                }
+            }
         })
     }
 }
@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+
+use vine_lex::filter::FilteredTokenKind;
+
+use crate::{
+    ast::BinaryOperation,
+    parser::{Fixity, Precedence},
+};
+
+/// A built-in infix operator.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BuiltinOperator {
+    pub token: FilteredTokenKind<'static>,
+    pub fixity: Fixity,
+    pub operation: BinaryOperation,
+}
+
+const fn operator(
+    token: FilteredTokenKind<'static>,
+    fixity: Fixity,
+    operation: BinaryOperation,
+) -> BuiltinOperator {
+    BuiltinOperator {
+        token,
+        fixity,
+        operation,
+    }
+}
+
+/// The built-in infix operators, from loosest to tightest:
+///
+/// | Level          | Operators                                      | Associativity |
+/// |----------------|------------------------------------------------|---------------|
+/// | Assignment     | `=` `:=` `<-` `+=` `-=` `*=` `/=` `%=` `^=` `&=` `&&=` `\|=` `\|\|=` | right |
+/// | Arrow          | `->`                                           | right         |
+/// | Or             | `or` `\|\|`                                    | left          |
+/// | And            | `and` `&&`                                     | left          |
+/// | Equality       | `==` `!=` `===` `!==`                          | left          |
+/// | Comparison     | `<` `<=` `>` `>=`                              | left          |
+/// | Range          | `..` `..=`                                     | left          |
+/// | BitwiseOr      | `\|`                                           | left          |
+/// | BitwiseXor     | `^`                                            | left          |
+/// | BitwiseAnd     | `&`                                            | left          |
+/// | Additive       | `+` `-` `++`                                   | left          |
+/// | Multiplicative | `*` `/` `%`                                    | left          |
+/// | Power          | `**`                                           | right         |
+/// | Call           | juxtaposition                                  | left          |
+/// | Unary          | prefix `-` `!`                                 |               |
+/// | Access         | `.`                                            | left          |
+/// | Path           | `::`                                           | left          |
+///
+/// Calls and unary operators are not in this table; their levels are
+/// [`Precedence::CALL`] and [`Precedence::UNARY`].
+pub const BUILTIN_OPERATORS: &[BuiltinOperator] = {
+    use BinaryOperation as Op;
+    use FilteredTokenKind as T;
+
+    const ASSIGNMENT: Fixity = Fixity::right(Precedence::ASSIGNMENT);
+    const ARROW: Fixity = Fixity::right(Precedence::ARROW);
+    const OR: Fixity = Fixity::left(Precedence::OR);
+    const AND: Fixity = Fixity::left(Precedence::AND);
+    const EQUALITY: Fixity = Fixity::left(Precedence::EQUALITY);
+    const COMPARISON: Fixity = Fixity::left(Precedence::COMPARISON);
+    const RANGE: Fixity = Fixity::left(Precedence::RANGE);
+    const BITWISE_OR: Fixity = Fixity::left(Precedence::BITWISE_OR);
+    const BITWISE_XOR: Fixity = Fixity::left(Precedence::BITWISE_XOR);
+    const BITWISE_AND: Fixity = Fixity::left(Precedence::BITWISE_AND);
+    const ADDITIVE: Fixity = Fixity::left(Precedence::ADDITIVE);
+    const MULTIPLICATIVE: Fixity = Fixity::left(Precedence::MULTIPLICATIVE);
+    const POWER: Fixity = Fixity::right(Precedence::POWER);
+    const ACCESS: Fixity = Fixity::left(Precedence::ACCESS);
+    const PATH: Fixity = Fixity::left(Precedence::PATH);
+
+    &[
+        operator(T::Equals, ASSIGNMENT, Op::Definition),
+        operator(T::ColonEquals, ASSIGNMENT, Op::Assign),
+        operator(T::LessThanMinus, ASSIGNMENT, Op::Bind),
+        operator(T::PlusEquals, ASSIGNMENT, Op::AddAssign),
+        operator(T::MinusEquals, ASSIGNMENT, Op::SubtractAssign),
+        operator(T::StarEquals, ASSIGNMENT, Op::MultiplyAssign),
+        operator(T::SlashEquals, ASSIGNMENT, Op::DivideAssign),
+        operator(T::PercentEquals, ASSIGNMENT, Op::RemainderAssign),
+        operator(T::CaretEquals, ASSIGNMENT, Op::BitwiseXorAssign),
+        operator(T::AmpersandEquals, ASSIGNMENT, Op::BitwiseAndAssign),
+        operator(
+            T::AmpersandAmpersandEquals,
+            ASSIGNMENT,
+            Op::LogicalAndAssign,
+        ),
+        operator(T::BarEquals, ASSIGNMENT, Op::BitwiseOrAssign),
+        operator(T::BarBarEquals, ASSIGNMENT, Op::LogicalOrAssign),
+        operator(T::MinusGreaterThan, ARROW, Op::Arrow),
+        operator(T::Or, OR, Op::Or),
+        operator(T::BarBar, OR, Op::LogicalOr),
+        operator(T::And, AND, Op::And),
+        operator(T::AmpersandAmpersand, AND, Op::LogicalAnd),
+        operator(T::EqualsEquals, EQUALITY, Op::Equal),
+        operator(T::ExclamationMarkEquals, EQUALITY, Op::NotEqual),
+        operator(T::EqualsEqualsEquals, EQUALITY, Op::Identical),
+        operator(T::ExclamationMarkEqualsEquals, EQUALITY, Op::NotIdentical),
+        operator(T::LessThan, COMPARISON, Op::LessThan),
+        operator(T::LessThanEquals, COMPARISON, Op::LessThanOrEqual),
+        operator(T::GreaterThan, COMPARISON, Op::GreaterThan),
+        operator(T::GreaterThanEquals, COMPARISON, Op::GreaterThanOrEqual),
+        operator(T::PeriodPeriod, RANGE, Op::RangeExclusive),
+        operator(T::PeriodPeriodEquals, RANGE, Op::RangeInclusive),
+        operator(T::Bar, BITWISE_OR, Op::BitwiseOr),
+        operator(T::Caret, BITWISE_XOR, Op::BitwiseXor),
+        operator(T::Ampersand, BITWISE_AND, Op::BitwiseAnd),
+        operator(T::Plus, ADDITIVE, Op::Add),
+        operator(T::Minus, ADDITIVE, Op::Subtract),
+        operator(T::PlusPlus, ADDITIVE, Op::Concatenate),
+        operator(T::Star, MULTIPLICATIVE, Op::Multiply),
+        operator(T::Slash, MULTIPLICATIVE, Op::Divide),
+        operator(T::Percent, MULTIPLICATIVE, Op::Remainder),
+        operator(T::StarStar, POWER, Op::Power),
+        operator(T::Period, ACCESS, Op::Access),
+        operator(T::ColonColon, PATH, Op::Path),
+    ]
+};
+
+/// An infix operator found in the source.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InfixOperator<'source> {
+    Builtin(BinaryOperation),
+
+    /// An identifier in backticks, like `` `max` ``. Contains the identifier without the backticks.
+    Custom(&'source str),
+}
+
+/// The infix operators known to the parser: the [`BUILTIN_OPERATORS`]
+/// and custom operators with their declared fixities.
+///
+/// Custom operators are declared in the source with an attribute on their definition:
+///
+/// ```plain
+/// @infix(left, 115)
+/// max = function ...
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct OperatorTable<'source> {
+    custom: BTreeMap<&'source str, Fixity>,
+}
+
+impl<'source> OperatorTable<'source> {
+    /// The fixity of custom operators without a fixity declaration.
+    pub const DEFAULT_FIXITY: Fixity = Fixity::left(Precedence::MULTIPLICATIVE);
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares the fixity of a custom operator and returns the previous one, if any.
+    pub fn declare(&mut self, name: &'source str, fixity: Fixity) -> Option<Fixity> {
+        self.custom.insert(name, fixity)
+    }
+
+    /// Returns the fixity of a custom operator.
+    pub fn fixity(&self, name: &str) -> Fixity {
+        self.custom
+            .get(name)
+            .copied()
+            .unwrap_or(Self::DEFAULT_FIXITY)
+    }
+
+    /// Returns the declared custom operators, ordered by name.
+    pub fn custom(&self) -> impl Iterator<Item = (&'source str, Fixity)> + '_ {
+        self.custom.iter().map(|(name, fixity)| (*name, *fixity))
+    }
+
+    /// Returns the fixity and the operator of an infix token,
+    /// or [`None`] if the token is not an infix operator.
+    pub fn infix(
+        &self,
+        token: &FilteredTokenKind<'source>,
+    ) -> Option<(Fixity, InfixOperator<'source>)> {
+        match token {
+            FilteredTokenKind::InfixIdentifier(source) => {
+                let name = &source[1..source.len() - 1];
+                Some((self.fixity(name), InfixOperator::Custom(name)))
+            }
+            token => BUILTIN_OPERATORS
+                .iter()
+                .find(|operator| operator.token == *token)
+                .map(|operator| (operator.fixity, InfixOperator::Builtin(operator.operation))),
+        }
+    }
+}
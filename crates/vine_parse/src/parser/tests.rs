@@ -0,0 +1,498 @@
+#![allow(non_snake_case)]
+
+mod Parser {
+    use std::ops::Range;
+
+    use vine_lex::lex;
+
+    use super::super::*;
+    use crate::ast::BinaryOperation;
+
+    /// Parses `input` and returns the messages and primary ranges of all diagnostics.
+    fn errors(input: &str) -> Vec<(String, Range<u32>)> {
+        let mut parser = Parser::new(lex(input));
+        parser.parse_root_expression();
+        messages(parser)
+    }
+
+    /// Like [`errors`], but parses `input` as a module.
+    fn module_errors(input: &str) -> Vec<(String, Range<u32>)> {
+        let mut parser = Parser::new(lex(input));
+        parser.parse_module();
+        messages(parser)
+    }
+
+    /// Prints the operator structure of an expression, like `(Add a (Multiply b c))`.
+    pub(super) fn print(expression: &Expression) -> String {
+        match expression {
+            Expression::Identifier(identifier) => identifier.to_string(),
+            Expression::Number(number) => number.to_string(),
+            Expression::Unary { operation, inner } => {
+                format!("({operation:?} {})", print(&inner.value))
+            }
+            Expression::Binary {
+                left,
+                operation,
+                right,
+            } => format!(
+                "({operation:?} {} {})",
+                print(&left.value),
+                print(&right.value)
+            ),
+            Expression::Infix {
+                left,
+                operator,
+                right,
+            } => format!(
+                "({} {} {})",
+                operator.value,
+                print(&left.value),
+                print(&right.value)
+            ),
+            Expression::Call { function, argument } => format!(
+                "(Call {} {})",
+                print(&function.value),
+                print(&argument.value)
+            ),
+            expression => panic!("unexpected expression {expression:?}"),
+        }
+    }
+
+    /// Parses `input` and prints its operator structure.
+    pub(super) fn tree(input: &str) -> String {
+        let mut parser = Parser::new(lex(input));
+        let expression = parser.parse_root_expression();
+        assert_eq!(parser.diagnostics(), []);
+
+        print(&expression.value)
+    }
+
+    fn messages<'s>(
+        parser: Parser<'s, impl Iterator<Item = Span<vine_lex::filter::FilteredToken<'s>>>>,
+    ) -> Vec<(String, Range<u32>)> {
+        parser
+            .into_diagnostics()
+            .into_iter()
+            .map(|diagnostic| {
+                let range = diagnostic.primary_range().unwrap();
+                (diagnostic.message, range)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_root_expression_literals() {
+        let mut parser = Parser::new(lex(r#"f "a\tb" 'c'"#));
+
+        assert_eq!(
+            parser.parse_root_expression(),
+            Span {
+                value: Expression::Call {
+                    function: Box::new(Span {
+                        value: Expression::Call {
+                            function: Box::new(Span {
+                                value: Expression::Identifier("f"),
+                                range: 0..1,
+                            }),
+                            argument: Box::new(Span {
+                                value: Expression::String("a\tb".into()),
+                                range: 2..8,
+                            }),
+                        },
+                        range: 0..8,
+                    }),
+                    argument: Box::new(Span {
+                        value: Expression::Character('c'),
+                        range: 9..12,
+                    }),
+                },
+                range: 0..12,
+            }
+        );
+        assert!(parser.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn parse_root_expression_trailing_tokens() {
+        assert_eq!(
+            errors("1 2 3)"),
+            [("expected the end of the input, found `)`".into(), 5..6)]
+        );
+    }
+
+    #[test]
+    fn parse_module() {
+        let mut parser = Parser::new(lex("a = 1\n\nf = function x is N => g x\ng f\n  + a"));
+
+        assert_eq!(
+            parser
+                .parse_module()
+                .items
+                .into_iter()
+                .map(|item| item.range)
+                .collect::<Vec<_>>(),
+            [0..5, 7..33, 34..43]
+        );
+        assert!(parser.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn parse_module_finite() {
+        let mut parser = Parser::new(lex(include_str!("../../../../language-design/finite.vn")));
+        let module = parser.parse_module();
+
+        assert_eq!(parser.diagnostics(), []);
+        assert_eq!(module.items.len(), 8);
+        assert_eq!(
+            module.items[0],
+            Span {
+                value: Expression::Abstract {
+                    name: Span {
+                        value: "Zero",
+                        range: 9..13,
+                    },
+                },
+                range: 0..13,
+            }
+        );
+        assert!(matches!(
+            module.items[7].value,
+            Expression::Binary {
+                operation: BinaryOperation::Definition,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parse_abstract_recovery() {
+        assert_eq!(
+            module_errors("abstract 1\nabstract"),
+            [
+                (
+                    "expected a name for the abstract value, found `1`".into(),
+                    9..10
+                ),
+                (
+                    "expected a name for the abstract value, found the end of the input".into(),
+                    19..19
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_module_recovery() {
+        assert_eq!(
+            module_errors("a = 1 2 b)\n) c\nd = (\n1 +\n)\ne = 3"),
+            [
+                ("expected a line break, found `)`".into(), 9..10),
+                ("expected an expression, found `)`".into(), 11..12),
+                ("expected an expression, found `)`".into(), 25..26),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_expression_precedence() {
+        assert_eq!(
+            tree("a = b += 1 + 2 * 3"),
+            "(Definition a (AddAssign b (Add 1 (Multiply 2 3))))"
+        );
+        assert_eq!(
+            tree("a || b && c == d"),
+            "(LogicalOr a (LogicalAnd b (Equal c d)))"
+        );
+        assert_eq!(tree("a or b and c"), "(Or a (And b c))");
+        assert_eq!(tree("0 .. n + 1"), "(RangeExclusive 0 (Add n 1))");
+        assert_eq!(
+            tree("a | b ^ c & d"),
+            "(BitwiseOr a (BitwiseXor b (BitwiseAnd c d)))"
+        );
+        assert_eq!(tree("a % b ++ c"), "(Concatenate (Remainder a b) c)");
+        assert_eq!(tree("-a ** 2"), "(Power (Negate a) 2)");
+        assert_eq!(tree("f x ** 2"), "(Power (Call f x) 2)");
+        assert_eq!(tree("a < b ..= c"), "(LessThan a (RangeInclusive b c))");
+        assert_eq!(tree("x <- m::f y"), "(Bind x (Call (Path m f) y))");
+        assert_eq!(tree("a.b::c"), "(Access a (Path b c))");
+        assert_eq!(tree("a != b"), "(NotEqual a b)");
+        assert_eq!(tree("x &= y"), "(BitwiseAndAssign x y)");
+    }
+
+    #[test]
+    fn parse_expression_associativity() {
+        assert_eq!(tree("a - b - c"), "(Subtract (Subtract a b) c)");
+        assert_eq!(tree("a ** b ** c"), "(Power a (Power b c))");
+        assert_eq!(tree("a -> b -> c"), "(Arrow a (Arrow b c))");
+        assert_eq!(tree("a %= b %c"), "(RemainderAssign a (Remainder b c))");
+        assert_eq!(tree("a := b |= c"), "(Assign a (BitwiseOrAssign b c))");
+        assert_eq!(tree("a === b !== c"), "(NotIdentical (Identical a b) c)");
+        assert_eq!(tree("a::b::c"), "(Path (Path a b) c)");
+    }
+
+    #[test]
+    fn parse_attributes() {
+        let mut parser = Parser::new(lex("@merge\n@doc(1) @a (x) = 2"));
+
+        let Span {
+            value:
+                Expression::Attributed {
+                    attribute,
+                    expression,
+                },
+            range,
+        } = parser.parse_root_expression()
+        else {
+            panic!("expected an attributed expression");
+        };
+
+        assert_eq!(range, 0..25);
+        assert_eq!(
+            attribute,
+            Span {
+                value: Attribute {
+                    name: Span {
+                        value: "merge",
+                        range: 1..6,
+                    },
+                    arguments: None,
+                },
+                range: 0..6,
+            }
+        );
+
+        let Expression::Attributed {
+            attribute,
+            expression,
+        } = expression.value
+        else {
+            panic!("expected an attributed expression");
+        };
+
+        assert_eq!(attribute.range, 7..14);
+        assert_eq!(
+            attribute.value.arguments.map(|arguments| arguments.range),
+            Some(11..14)
+        );
+        assert!(matches!(
+            expression.value,
+            Expression::Attributed {
+                attribute: Span {
+                    value: Attribute {
+                        arguments: None,
+                        ..
+                    },
+                    ..
+                },
+                ..
+            }
+        ));
+        assert!(parser.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn parse_attributes_recovery() {
+        assert_eq!(
+            errors("@ 1"),
+            [("expected an attribute name, found `1`".into(), 2..3)]
+        );
+    }
+
+    #[test]
+    fn parse_grouped_recovery() {
+        assert_eq!(
+            errors("(a then b, 0xZZ, \"\\q\"\n c d]"),
+            [
+                (
+                    "expected a line break, `,` or `)`, found `then`".into(),
+                    3..7
+                ),
+                ("malformed number literal".into(), 11..15),
+                ("unknown escape sequence `\\q`".into(), 18..20),
+                (
+                    "expected a line break, `,` or `)`, found `]`".into(),
+                    26..27
+                ),
+                ("unclosed delimiter; expected `)`".into(), 27..27),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_grouped_mismatched_closing_delimiter() {
+        assert_eq!(
+            errors("[1\n)\n2]"),
+            [("expected `]`, found `)`".into(), 3..4)]
+        );
+    }
+
+    #[test]
+    fn parse_grouped_synchronizing_token_after_line_break() {
+        for (input, found, range) in [
+            ("(\nthen)", "then", 2..6),
+            ("[1\nelse]", "else", 3..7),
+            ("(\n=> x)", "=>", 2..4),
+            ("set {\n  x\n  in y\n}", "in", 12..14),
+        ] {
+            let expected = [(format!("expected an expression, found `{found}`"), range)];
+
+            assert_eq!(errors(input), expected, "{input:?}");
+            assert_eq!(module_errors(input), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn parse_match_case_recovery() {
+        assert_eq!(
+            errors("match x case 1 2 case 3 => 4 case => 5"),
+            [
+                ("expected `is`, `in` or `=>`, found `case`".into(), 17..21),
+                ("expected an expression, found `=>`".into(), 34..36),
+            ]
+        );
+
+        let mut parser = Parser::new(lex("match x case 1 2 case 3 => 4"));
+
+        let Expression::Match {
+            first_case,
+            other_cases,
+            ..
+        } = parser.parse_root_expression().value
+        else {
+            panic!("expected a match");
+        };
+
+        assert_eq!(parser.diagnostics().len(), 1);
+        assert_eq!(first_case.value.maps_to.value, Expression::Error);
+        assert_eq!(first_case.range, 8..16);
+        assert_eq!(other_cases.len(), 1);
+        assert_eq!(other_cases[0].value.pattern.range, 22..23);
+        assert_eq!(other_cases[0].value.maps_to.range, 27..28);
+    }
+
+    #[test]
+    fn parse_expression_start_recovery() {
+        assert_eq!(
+            errors("[* 1, if x then, 2e99999]"),
+            [
+                ("expected an expression, found `*`".into(), 1..2),
+                ("expected an expression, found `,`".into(), 15..16),
+                ("exponent is too large".into(), 17..24),
+            ]
+        );
+    }
+}
+
+mod OperatorTable {
+    use vine_lex::lex;
+
+    use super::{
+        super::*,
+        Parser::{print, tree},
+    };
+
+    #[test]
+    fn builtin_operators() {
+        for (index, operator) in BUILTIN_OPERATORS.iter().enumerate() {
+            assert!(
+                BUILTIN_OPERATORS[..index]
+                    .iter()
+                    .all(|other| other.token != operator.token
+                        && other.operation != operator.operation
+                        && other.fixity.precedence <= operator.fixity.precedence),
+                "{operator:?} is duplicated or out of order"
+            );
+            assert_eq!(
+                OperatorTable::new().infix(&operator.token),
+                Some((operator.fixity, InfixOperator::Builtin(operator.operation)))
+            );
+        }
+    }
+
+    #[test]
+    fn builtin_operators_bind_by_fixity() {
+        for first in BUILTIN_OPERATORS {
+            for second in BUILTIN_OPERATORS {
+                let groups_left = first.fixity.precedence > second.fixity.precedence
+                    || first.fixity.precedence == second.fixity.precedence
+                        && first.fixity.associativity == Associativity::Left;
+
+                let expected = if groups_left {
+                    format!("({:?} ({:?} a b) c)", second.operation, first.operation)
+                } else {
+                    format!("({:?} a ({:?} b c))", first.operation, second.operation)
+                };
+
+                assert_eq!(
+                    tree(&format!("a {} b {} c", first.token, second.token)),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn declare() {
+        let mut parser = Parser::new(lex(
+            "a `max` b * c\n@infix(right, 115)\n@merge\nmax = f\na `max` b * c `max` d",
+        ));
+        let module = parser.parse_module();
+
+        assert_eq!(parser.diagnostics(), []);
+        assert_eq!(
+            parser.operators().custom().collect::<Vec<_>>(),
+            [("max", Fixity::right(Precedence(115)))]
+        );
+        assert_eq!(print(&module.items[0].value), "(Multiply (max a b) c)");
+        assert_eq!(
+            print(&module.items[2].value),
+            "(max a (max (Multiply b c) d))"
+        );
+    }
+
+    #[test]
+    fn declare_invalid() {
+        let mut parser = Parser::new(lex(
+            "@infix(left)\na = 1\n@infix(up, 1)\na = 1\n@infix(left, 256)\na = 1\n@infix(left, 1) 1",
+        ));
+        parser.parse_module();
+
+        assert_eq!(
+            parser
+                .into_diagnostics()
+                .into_iter()
+                .map(|diagnostic| diagnostic.message)
+                .collect::<Vec<_>>(),
+            [
+                "malformed fixity declaration",
+                "malformed fixity declaration",
+                "malformed fixity declaration",
+                "fixity declarations must be attached to a definition of a name",
+            ]
+        );
+    }
+
+    #[test]
+    fn declare_nested() {
+        let mut parser = Parser::new(lex(
+            "f = {\n  @infix(left, 1)\n  max = g\n  1\n}\n@merge @infix(left, 2) min = h",
+        ));
+        parser.parse_module();
+
+        assert_eq!(
+            parser.operators().custom().collect::<Vec<_>>(),
+            [("min", Fixity::left(Precedence(2)))]
+        );
+        assert_eq!(
+            parser
+                .into_diagnostics()
+                .into_iter()
+                .map(|diagnostic| (diagnostic.primary_range(), diagnostic.message))
+                .collect::<Vec<_>>(),
+            [(
+                Some(8..23),
+                "fixity declarations are only allowed at module level".into()
+            )]
+        );
+    }
+}
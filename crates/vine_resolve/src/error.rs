@@ -0,0 +1,57 @@
+use std::ops::Range;
+
+use vine_diagnostics::Diagnostic;
+
+#[derive(Debug, Clone)]
+pub enum ErrorInfo<'source> {
+    /// An identifier that does not refer to any binding in scope.
+    UndefinedName {
+        name: &'source str,
+        range: Range<u32>,
+    },
+
+    /// A binding that hides a binding with the same name.
+    Shadowing {
+        name: &'source str,
+        range: Range<u32>,
+
+        /// The range of the hidden binding.
+        shadowed: Range<u32>,
+    },
+
+    /// A module-level name that is defined more than once.
+    Redefinition {
+        name: &'source str,
+        range: Range<u32>,
+        previous: Range<u32>,
+    },
+}
+
+impl ErrorInfo<'_> {
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        match self {
+            Self::UndefinedName { name, range } => {
+                Diagnostic::error(format!("cannot find `{name}` in this scope"))
+                    .with_primary(range.clone(), "not found")
+            }
+            Self::Shadowing {
+                name,
+                range,
+                shadowed,
+            } => Diagnostic::warning(format!("`{name}` shadows an earlier binding"))
+                .with_primary(range.clone(), "shadows the earlier binding")
+                .with_secondary(shadowed.clone(), "previously bound here")
+                .with_help("consider choosing a different name"),
+            Self::Redefinition {
+                name,
+                range,
+                previous,
+            } => Diagnostic::error(format!("`{name}` is defined multiple times"))
+                .with_primary(range.clone(), "redefined here")
+                .with_secondary(previous.clone(), "first defined here")
+                .with_note(
+                    "definitions can only be merged if all of them have a `@merge` attribute",
+                ),
+        }
+    }
+}
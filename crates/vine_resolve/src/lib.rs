@@ -0,0 +1,128 @@
+//! Name resolution.
+//!
+//! The [`Resolver`] walks the AST and assigns every identifier the [`Binding`]
+//! it introduces or refers to. Scopes are introduced by:
+//!
+//! - the module, whose definitions (`x = ...`) and `abstract` declarations are
+//!   visible everywhere in it, so definitions may refer to each other in any order;
+//! - braced blocks `{ ... }`, whose definitions are visible from the definition on;
+//! - functions, whose parameter pattern binds names for the body;
+//! - match cases, whose pattern binds names for the result.
+//!
+//! The names of record fields (`(left = ..., right = ...)`), of accessed
+//! fields (`a.b`) and of attributes are not resolved.
+
+mod error;
+mod resolver;
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+};
+
+pub use error::*;
+pub use resolver::*;
+use vine_diagnostics::Diagnostic;
+use vine_parse::ast::Module;
+
+/// The names that are defined before any module.
+pub const PRELUDE: &[&str] = &["Number", "String", "Character", "Boolean", "True", "False"];
+
+/// Identifies a [`Binding`] in a [`Resolution`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BindingId(u32);
+
+impl BindingId {
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BindingKind {
+    /// A name from the [`PRELUDE`].
+    Builtin,
+
+    /// A name bound by a definition `x = ...`.
+    Definition,
+
+    /// A name declared with `abstract`. In patterns, these match the value
+    /// itself instead of binding a new name.
+    Abstract,
+
+    /// A name bound by the parameter pattern of a function.
+    Parameter,
+
+    /// A name bound by the pattern of a match case.
+    Pattern,
+}
+
+/// A name introduced into a scope.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Binding<'source> {
+    pub name: &'source str,
+    pub kind: BindingKind,
+
+    /// The range of the identifier that introduces the binding.
+    /// [`None`] for builtins.
+    pub range: Option<Range<u32>>,
+}
+
+/// The result of name resolution.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Resolution<'source> {
+    bindings: Vec<Binding<'source>>,
+
+    /// The binding of each resolved identifier, by the start of its range.
+    identifiers: BTreeMap<u32, BindingId>,
+
+    /// The module-level bindings defined with a `@merge` attribute.
+    merged: BTreeSet<BindingId>,
+}
+
+impl<'source> Resolution<'source> {
+    pub fn binding(&self, id: BindingId) -> &Binding<'source> {
+        &self.bindings[id.index()]
+    }
+
+    pub fn bindings(&self) -> impl Iterator<Item = (BindingId, &Binding<'source>)> {
+        self.bindings
+            .iter()
+            .enumerate()
+            .map(|(index, binding)| (BindingId(index as u32), binding))
+    }
+
+    /// Returns the binding the identifier at `range` introduces or refers to.
+    ///
+    /// Returns [`None`] for identifiers that are not resolved, like field names,
+    /// and for undefined names.
+    pub fn binding_of(&self, range: &Range<u32>) -> Option<BindingId> {
+        self.identifiers.get(&range.start).copied()
+    }
+
+    /// Whether the binding is defined by one or more definitions with a `@merge` attribute.
+    pub fn is_merged(&self, id: BindingId) -> bool {
+        self.merged.contains(&id)
+    }
+
+    /// Returns the resolved identifiers by the start of their range.
+    pub fn identifiers(&self) -> impl Iterator<Item = (u32, BindingId)> {
+        self.identifiers.iter().map(|(start, id)| (*start, *id))
+    }
+
+    /// Returns the resolved identifiers that start in `range`.
+    pub fn identifiers_in(&self, range: Range<u32>) -> impl Iterator<Item = (u32, BindingId)> {
+        self.identifiers
+            .range(range)
+            .map(|(start, id)| (*start, *id))
+    }
+}
+
+/// Resolves the names in a module.
+pub fn resolve<'source>(module: &Module<'source>) -> (Resolution<'source>, Vec<Diagnostic>) {
+    let mut resolver = Resolver::new();
+    resolver.resolve_module(module);
+    resolver.finish()
+}
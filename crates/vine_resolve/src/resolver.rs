@@ -0,0 +1,391 @@
+use std::{collections::BTreeMap, iter, mem, ops::Range};
+
+use parser_tools::Span;
+use vine_diagnostics::Diagnostic;
+use vine_parse::{
+    ast::{BinaryOperation, Expression, GroupKind, MatchCase, Module},
+    lower::field,
+};
+
+use crate::{Binding, BindingId, BindingKind, ErrorInfo, PRELUDE, Resolution};
+
+/// The index of the module scope in [`Resolver::scopes`].
+const MODULE: usize = 1;
+
+type Scope<'source> = BTreeMap<&'source str, BindingId>;
+
+#[derive(Clone)]
+pub struct Resolver<'source> {
+    resolution: Resolution<'source>,
+
+    /// The scopes, innermost last. The first scope contains the [`PRELUDE`],
+    /// the second one the module-level names.
+    scopes: Vec<Scope<'source>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Default for Resolver<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'source> Resolver<'source> {
+    pub fn new() -> Self {
+        let mut resolver = Self {
+            resolution: Resolution::default(),
+            scopes: vec![Scope::new(), Scope::new()],
+            diagnostics: Vec::new(),
+        };
+
+        for name in PRELUDE {
+            let id = resolver.push_binding(name, BindingKind::Builtin, None);
+            resolver.scopes[0].insert(name, id);
+        }
+
+        resolver
+    }
+
+    pub const fn resolution(&self) -> &Resolution<'source> {
+        &self.resolution
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Removes and returns the diagnostics reported so far.
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        mem::take(&mut self.diagnostics)
+    }
+
+    pub fn finish(self) -> (Resolution<'source>, Vec<Diagnostic>) {
+        (self.resolution, self.diagnostics)
+    }
+
+    /// Resolves the names in a module.
+    ///
+    /// Resolving multiple modules with the same resolver puts
+    /// all of their module-level names into the same scope.
+    pub fn resolve_module(&mut self, module: &Module<'source>) {
+        // Abstract values are declared first, since they are
+        // matched against instead of bound in patterns.
+        let merge = |item| {
+            Expression::attributes(item).any(|attribute| attribute.value.name.value == "merge")
+        };
+
+        for item in &module.items {
+            if let Expression::Abstract { name } = &Expression::unattributed(item).value {
+                self.declare(
+                    name.value,
+                    name.range.clone(),
+                    BindingKind::Abstract,
+                    merge(item),
+                );
+            }
+        }
+
+        for item in &module.items {
+            if let Expression::Binary {
+                left,
+                operation: BinaryOperation::Definition,
+                ..
+            } = &Expression::unattributed(item).value
+            {
+                let merge = merge(item);
+
+                match left.value {
+                    Expression::Identifier(name) if name != "_" => {
+                        self.declare(name, left.range.clone(), BindingKind::Definition, merge);
+                    }
+                    _ => self.bind_pattern(left, BindingKind::Definition, &mut BTreeMap::new()),
+                }
+            }
+        }
+
+        for item in &module.items {
+            match &Expression::unattributed(item).value {
+                Expression::Binary {
+                    operation: BinaryOperation::Definition,
+                    right,
+                    ..
+                } => self.resolve_expression(right),
+                Expression::Abstract { .. } => {}
+                _ => self.resolve_expression(item),
+            }
+        }
+    }
+
+    /// Removes a module-level name, so that a following module may define it again.
+    /// The identifiers resolved so far keep referring to the removed binding, but
+    /// it no longer counts as a `@merge` definition.
+    pub fn undeclare(&mut self, name: &str) {
+        if let Some(id) = self.scopes[MODULE].remove(name) {
+            self.resolution.merged.remove(&id);
+        }
+    }
+
+    /// Resolves the names in an expression in the current scope.
+    pub fn resolve_expression(&mut self, expression: &Span<Expression<'source>>) {
+        match &expression.value {
+            Expression::Error
+            | Expression::Number(_)
+            | Expression::String(_)
+            | Expression::Character(_) => {}
+            Expression::Identifier(name) => self.refer(name, expression.range.clone()),
+            Expression::Grouped {
+                kind: GroupKind::Braced,
+                expression: items,
+            } => {
+                self.scopes.push(Scope::new());
+
+                for item in items {
+                    self.resolve_expression(item);
+                }
+
+                self.scopes.pop();
+            }
+            Expression::Grouped {
+                expression: items, ..
+            } => {
+                for item in items {
+                    self.resolve_expression(field(item).map_or(item, |(_, value)| value));
+                }
+            }
+            Expression::Unary { inner, .. } => self.resolve_expression(inner),
+            Expression::Binary {
+                left,
+                operation: BinaryOperation::Definition,
+                right,
+            } => {
+                // The name is bound first, so that functions can call themselves.
+                match left.value {
+                    Expression::Identifier(name) if name != "_" => {
+                        self.bind(name, left.range.clone(), BindingKind::Definition);
+                    }
+                    _ => self.bind_pattern(left, BindingKind::Definition, &mut BTreeMap::new()),
+                }
+
+                self.resolve_expression(right);
+            }
+            Expression::Binary {
+                left,
+                operation: BinaryOperation::Access | BinaryOperation::Path,
+                ..
+            } => self.resolve_expression(left),
+            Expression::Binary { left, right, .. }
+            | Expression::Call {
+                function: left,
+                argument: right,
+            } => {
+                self.resolve_expression(left);
+                self.resolve_expression(right);
+            }
+            Expression::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                self.resolve_expression(condition);
+                self.resolve_expression(then);
+
+                if let Some(otherwise) = otherwise {
+                    self.resolve_expression(otherwise);
+                }
+            }
+            Expression::Match {
+                on,
+                first_case,
+                other_cases,
+            } => {
+                self.resolve_expression(on);
+
+                for case in iter::once(first_case).chain(other_cases) {
+                    self.resolve_case(&case.value);
+                }
+            }
+            Expression::Infix {
+                left,
+                operator,
+                right,
+            } => {
+                self.resolve_expression(left);
+                self.refer(operator.value, operator.range.clone());
+                self.resolve_expression(right);
+            }
+            Expression::Function {
+                parameter_pattern,
+                parameter_domain,
+                body,
+            } => {
+                if let Some(domain) = parameter_domain {
+                    self.resolve_expression(domain);
+                }
+
+                self.scopes.push(Scope::new());
+                self.bind_pattern(
+                    parameter_pattern,
+                    BindingKind::Parameter,
+                    &mut BTreeMap::new(),
+                );
+                self.resolve_expression(body);
+                self.scopes.pop();
+            }
+            Expression::Abstract { name } => {
+                self.bind(name.value, name.range.clone(), BindingKind::Abstract);
+            }
+            Expression::Attributed { expression, .. } => self.resolve_expression(expression),
+        }
+    }
+
+    fn resolve_case(&mut self, case: &MatchCase<'source>) {
+        if let Some(domain) = &case.domain {
+            self.resolve_expression(domain);
+        }
+
+        self.scopes.push(Scope::new());
+        self.bind_pattern(&case.pattern, BindingKind::Pattern, &mut BTreeMap::new());
+        self.resolve_expression(&case.maps_to);
+        self.scopes.pop();
+    }
+
+    /// Binds the names in a pattern. `names` contains the names already bound by
+    /// the pattern, so that all alternatives of an `or` pattern bind the same names.
+    ///
+    /// Identifiers that refer to abstract values or builtins match these values.
+    /// Other parts of the pattern that are not identifiers, groups or alternatives
+    /// are resolved like expressions.
+    fn bind_pattern(
+        &mut self,
+        pattern: &Span<Expression<'source>>,
+        kind: BindingKind,
+        names: &mut Scope<'source>,
+    ) {
+        match &pattern.value {
+            Expression::Identifier("_") => {}
+            Expression::Identifier(name) => {
+                let range = pattern.range.clone();
+
+                if let Some(id) = names.get(name) {
+                    self.resolution.identifiers.insert(range.start, *id);
+                } else if let Some(id) = self.lookup(name)
+                    && matches!(
+                        self.resolution.binding(id).kind,
+                        BindingKind::Abstract | BindingKind::Builtin
+                    )
+                {
+                    self.resolution.identifiers.insert(range.start, id);
+                } else {
+                    let id = self.bind(name, range, kind);
+                    names.insert(name, id);
+                }
+            }
+            Expression::Grouped {
+                expression: items, ..
+            } => {
+                for item in items {
+                    self.bind_pattern(field(item).map_or(item, |(_, value)| value), kind, names);
+                }
+            }
+            Expression::Binary {
+                left,
+                operation: BinaryOperation::Or,
+                right,
+            } => {
+                self.bind_pattern(left, kind, names);
+                self.bind_pattern(right, kind, names);
+            }
+            Expression::Attributed { expression, .. } => self.bind_pattern(expression, kind, names),
+            _ => self.resolve_expression(pattern),
+        }
+    }
+
+    /// Binds a name in the innermost scope.
+    fn bind(&mut self, name: &'source str, range: Range<u32>, kind: BindingKind) -> BindingId {
+        if self.scopes.len() == MODULE + 1 {
+            return self.declare(name, range, kind, false);
+        }
+
+        if let Some(previous) = self.lookup(name)
+            && let Some(shadowed) = self.resolution.binding(previous).range.clone()
+        {
+            self.report(ErrorInfo::Shadowing {
+                name,
+                range: range.clone(),
+                shadowed,
+            });
+        }
+
+        let id = self.push_binding(name, kind, Some(range));
+        self.scopes.last_mut().unwrap().insert(name, id);
+        id
+    }
+
+    /// Binds a name in the module scope.
+    fn declare(
+        &mut self,
+        name: &'source str,
+        range: Range<u32>,
+        kind: BindingKind,
+        merge: bool,
+    ) -> BindingId {
+        if let Some(&previous) = self.scopes[MODULE].get(name) {
+            if !merge || !self.resolution.merged.contains(&previous) {
+                self.report(ErrorInfo::Redefinition {
+                    name,
+                    range: range.clone(),
+                    previous: self.resolution.binding(previous).range.clone().unwrap(),
+                });
+            }
+
+            self.resolution.identifiers.insert(range.start, previous);
+            return previous;
+        }
+
+        let id = self.push_binding(name, kind, Some(range));
+        self.scopes[MODULE].insert(name, id);
+
+        if merge {
+            self.resolution.merged.insert(id);
+        }
+
+        id
+    }
+
+    /// Resolves an identifier that refers to a binding.
+    fn refer(&mut self, name: &'source str, range: Range<u32>) {
+        match self.lookup(name) {
+            Some(id) => {
+                self.resolution.identifiers.insert(range.start, id);
+            }
+            None => self.report(ErrorInfo::UndefinedName { name, range }),
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<BindingId> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).copied())
+    }
+
+    fn push_binding(
+        &mut self,
+        name: &'source str,
+        kind: BindingKind,
+        range: Option<Range<u32>>,
+    ) -> BindingId {
+        let id = BindingId(self.resolution.bindings.len() as u32);
+
+        if let Some(range) = &range {
+            self.resolution.identifiers.insert(range.start, id);
+        }
+
+        self.resolution.bindings.push(Binding { name, kind, range });
+        id
+    }
+
+    fn report(&mut self, error: ErrorInfo<'source>) {
+        self.diagnostics.push(error.to_diagnostic());
+    }
+}
@@ -0,0 +1,215 @@
+#![allow(non_snake_case)]
+
+mod Resolver {
+    use std::ops::Range;
+
+    use vine_diagnostics::Severity;
+    use vine_parse::parse_module;
+
+    use super::super::*;
+
+    /// Resolves `source` and returns the resolution and the severity, message and
+    /// primary range of each diagnostic.
+    fn resolved(source: &str) -> (Resolution<'_>, Vec<(Severity, String, Range<u32>)>) {
+        let (resolution, diagnostics) = resolve(&parse_module(source).unwrap());
+
+        let diagnostics = diagnostics
+            .into_iter()
+            .map(|diagnostic| {
+                let range = diagnostic.primary_range().unwrap();
+                (diagnostic.severity, diagnostic.message, range)
+            })
+            .collect();
+
+        (resolution, diagnostics)
+    }
+
+    /// Returns the binding of each occurrence of the identifier `name` in `source`.
+    fn bindings_of(source: &str, resolution: &Resolution, name: &str) -> Vec<Option<BindingId>> {
+        source
+            .match_indices(name)
+            .filter(|(start, _)| {
+                let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+                !source[..*start].ends_with(is_identifier)
+                    && !source[start + name.len()..].starts_with(is_identifier)
+            })
+            .map(|(start, _)| {
+                let start = start as u32;
+                resolution.binding_of(&(start..start + name.len() as u32))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resolve_module() {
+        let source = include_str!("../../../language-design/finite.vn");
+        let (resolution, diagnostics) = resolved(source);
+        assert_eq!(diagnostics, []);
+
+        let zero = bindings_of(source, &resolution, "Zero");
+        assert!(zero.iter().all(|id| *id == zero[0]));
+        assert_eq!(
+            resolution.binding(zero[0].unwrap()).kind,
+            BindingKind::Abstract
+        );
+
+        let lr = bindings_of(source, &resolution, "lr");
+        assert_eq!(lr[0], lr[1]);
+        assert_ne!(lr[1], lr[2]);
+        assert_eq!(
+            resolution.binding(lr[0].unwrap()).kind,
+            BindingKind::Parameter
+        );
+
+        // Field names are not resolved.
+        assert!(
+            bindings_of(source, &resolution, "left")
+                .iter()
+                .all(Option::is_none)
+        );
+    }
+
+    #[test]
+    fn resolve_module_order() {
+        let source = "f = function x => g x\ng = function y => f y";
+        let (resolution, diagnostics) = resolved(source);
+
+        assert_eq!(diagnostics, []);
+        assert_eq!(
+            bindings_of(source, &resolution, "g"),
+            [Some(BindingId(PRELUDE.len() as u32 + 1)); 2]
+        );
+    }
+
+    #[test]
+    fn resolve_module_redefinition() {
+        assert_eq!(
+            resolved("a = 1\na = 2").1,
+            [(
+                Severity::Error,
+                "`a` is defined multiple times".into(),
+                6..7
+            )]
+        );
+
+        let source = "@merge\nf = function 1 => 2\n@merge\nf = function 2 => 1";
+        let (resolution, diagnostics) = resolved(source);
+        let f = bindings_of(source, &resolution, "f");
+
+        assert_eq!(diagnostics, []);
+        assert_eq!(f[0], f[1]);
+        assert!(resolution.is_merged(f[0].unwrap()));
+    }
+
+    #[test]
+    fn undeclare() {
+        let first = parse_module("@merge\na = 1").unwrap();
+        let second = parse_module("@merge\na = 2").unwrap();
+
+        let mut resolver = Resolver::new();
+        resolver.resolve_module(&first);
+        resolver.undeclare("a");
+        resolver.resolve_module(&second);
+        let (resolution, diagnostics) = resolver.finish();
+
+        assert_eq!(diagnostics, []);
+        assert_eq!(
+            resolution
+                .bindings()
+                .filter(|(id, binding)| binding.name == "a" && resolution.is_merged(*id))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn resolve_undefined() {
+        assert_eq!(
+            resolved("a = b\nf = function x => y\nz = 1 `min` 2").1,
+            [
+                (
+                    Severity::Error,
+                    "cannot find `b` in this scope".into(),
+                    4..5
+                ),
+                (
+                    Severity::Error,
+                    "cannot find `y` in this scope".into(),
+                    24..25
+                ),
+                (
+                    Severity::Error,
+                    "cannot find `min` in this scope".into(),
+                    32..37
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolve_shadowing() {
+        let source = "x = 1\nf = function x => x";
+        let (resolution, diagnostics) = resolved(source);
+        let x = bindings_of(source, &resolution, "x");
+
+        assert_eq!(
+            diagnostics,
+            [(
+                Severity::Warning,
+                "`x` shadows an earlier binding".into(),
+                19..20
+            )]
+        );
+        assert_ne!(x[0], x[1]);
+        assert_eq!(x[1], x[2]);
+
+        assert_eq!(
+            resolved("f = { a = 1\na = a }").1,
+            [(
+                Severity::Warning,
+                "`a` shadows an earlier binding".into(),
+                12..13
+            )]
+        );
+    }
+
+    #[test]
+    fn resolve_blocks() {
+        let source = "f = {\n    a = 1\n    b = a + 1\n}\ng = a";
+        let (resolution, diagnostics) = resolved(source);
+        let a = bindings_of(source, &resolution, "a");
+
+        assert_eq!(
+            diagnostics,
+            [(
+                Severity::Error,
+                "cannot find `a` in this scope".into(),
+                36..37
+            )]
+        );
+        assert_eq!(a[0], a[1]);
+        assert_eq!(a[2], None);
+    }
+
+    #[test]
+    fn resolve_patterns() {
+        let source = "abstract A\nf = function p => match p\n    case (a, A) or (A, a) => a\n    case (x = _, y = True) => A";
+        let (resolution, diagnostics) = resolved(source);
+        let a = bindings_of(source, &resolution, "a");
+
+        assert_eq!(diagnostics, []);
+        assert!(a.iter().all(|id| *id == a[0]));
+        assert_eq!(resolution.binding(a[0].unwrap()).kind, BindingKind::Pattern);
+        assert!(
+            bindings_of(source, &resolution, "A")
+                .iter()
+                .all(|id| *id == Some(BindingId(PRELUDE.len() as u32)))
+        );
+        assert_eq!(
+            resolution
+                .binding(bindings_of(source, &resolution, "True")[0].unwrap())
+                .kind,
+            BindingKind::Builtin
+        );
+    }
+}